@@ -0,0 +1,70 @@
+//! Times the expansion of `bytes!` on 1 MB and 8 MB hex literals and hex
+//! strings, which should grow linearly with the size. A proc macro crate only
+//! exports its macros, so each input is expanded by checking a generated crate
+//! that uses the macro, and the time includes the compiler reading the input
+//! and the output. Run with `cargo bench --bench expansion`.
+
+use std::{
+    env, fs,
+    path::Path,
+    process::Command,
+    time::{Duration, Instant},
+};
+
+fn main() {
+    let dir = env::temp_dir().join("bytes-lit-bench-expansion");
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"expansion\"\nversion = \"0.0.0\"\nedition = \"2021\"\n\n\
+             [dependencies]\nbytes-lit = {{ path = {:?} }}\n\n[workspace]\n",
+            env!("CARGO_MANIFEST_DIR")
+        ),
+    )
+    .unwrap();
+
+    // Build the dependencies first, so that they are not timed.
+    check(&dir, "");
+    let empty = check(&dir, "pub const B: [u8; 1] = bytes_lit::bytes!(0x00);");
+
+    for len in [1 << 20, 8 << 20] {
+        let hex: String = (0..len * 2)
+            .map(|i| b"0123456789abcdef"[i % 16] as char)
+            .collect();
+        for (form, source) in [
+            ("hex", format!("0x{}", hex)),
+            ("hex string", format!("{:?}", hex)),
+        ] {
+            let elapsed = check(
+                &dir,
+                &format!(
+                    "pub const B: [u8; {}] = bytes_lit::bytes!({});",
+                    len, source
+                ),
+            );
+            println!(
+                "{:>8} bytes of {:<10}  {:>12?}",
+                len,
+                form,
+                elapsed.saturating_sub(empty)
+            );
+        }
+    }
+}
+
+/// Writes the source of the crate and checks it, returning the time taken.
+fn check(dir: &Path, source: &str) -> Duration {
+    fs::write(dir.join("src").join("lib.rs"), source).unwrap();
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let start = Instant::now();
+    let status = Command::new(cargo)
+        .args(["check", "--quiet", "--release"])
+        .current_dir(dir)
+        .env("CARGO_TARGET_DIR", dir.join("target"))
+        .status()
+        .unwrap();
+    let elapsed = start.elapsed();
+    assert!(status.success(), "checking the generated crate failed");
+    elapsed
+}
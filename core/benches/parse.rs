@@ -0,0 +1,48 @@
+//! Times converting multi-kilobyte hex and binary literals to bytes, packing
+//! the digits directly as `parse_int_literal` does, and parsing the base 10
+//! digits of the value into a big integer as the macros did previously. Run
+//! with `cargo bench -p bytes-lit-core`.
+
+use std::{
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use num_bigint::BigUint;
+
+/// The number of times each conversion is repeated.
+const ITERATIONS: u32 = 10;
+
+fn main() {
+    for len in [4 << 10, 64 << 10, 256 << 10] {
+        let hex: String = (0..len * 2)
+            .map(|i| b"0123456789abcdef"[i % 16] as char)
+            .collect();
+        let binary: String = (0..len * 8)
+            .map(|i| if i % 3 == 0 { '1' } else { '0' })
+            .collect();
+        for (form, digits, radix) in [("hex", &hex, 16), ("binary", &binary, 2)] {
+            let prefix = if radix == 16 { "0x" } else { "0b" };
+            let lit = format!("{}{}", prefix, digits);
+            let packed = time(|| bytes_lit_core::parse_int_literal(&lit).unwrap().len());
+            let base10 = BigUint::parse_bytes(digits.as_bytes(), radix)
+                .unwrap()
+                .to_string();
+            let big = time(|| BigUint::from_str(&base10).unwrap().to_bytes_be().len());
+            println!(
+                "{:>7} bytes of {:<6}  packed {:>12?}  big integer {:>12?}",
+                len, form, packed, big
+            );
+        }
+    }
+}
+
+/// Returns the mean time of the conversion, which returns the number of bytes
+/// so that the conversion is not optimized away.
+fn time(f: impl Fn() -> usize) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        std::hint::black_box(f());
+    }
+    start.elapsed() / ITERATIONS
+}
@@ -0,0 +1,391 @@
+//! Convert integer literals to bytes, with the same semantics as the
+//! [bytes-lit](https://docs.rs/bytes-lit) macros, for use at runtime such as in
+//! build scripts and code generators.
+//!
+//! Leading zeros in hex (`0x`) and binary (`0b`) form are preserved. Leading
+//! zeros in octal (`0o`) form are preserved as 3 zero bits each, rounded up to
+//! whole bytes, and leading zeros in decimal form are an error since they
+//! cannot be preserved.
+//!
+//! ### Examples
+//!
+//! ```
+//! let bytes = bytes_lit_core::parse_int_literal("0x00ff_u16").unwrap();
+//! assert_eq!(bytes, [0, 255]);
+//! ```
+//!
+//! ```
+//! let bytes = bytes_lit_core::parse_int_literal_min("0x00ff").unwrap();
+//! assert_eq!(bytes, [255]);
+//! ```
+
+use std::{error, fmt, str::FromStr};
+
+use num_bigint::BigUint;
+
+/// The form of an integer literal, given by its base prefix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Form {
+    Hex,
+    Binary,
+    Octal,
+    Decimal,
+}
+
+impl Form {
+    /// Returns the radix of the digits of the form.
+    pub fn radix(self) -> u32 {
+        match self {
+            Form::Hex => 16,
+            Form::Binary => 2,
+            Form::Octal => 8,
+            Form::Decimal => 10,
+        }
+    }
+
+    /// Returns the number of bits each digit represents, or None if the form
+    /// does not map digits to bits, and so does not preserve leading zeros.
+    pub fn bits_per_digit(self) -> Option<usize> {
+        match self {
+            Form::Hex => Some(4),
+            Form::Binary => Some(1),
+            Form::Octal | Form::Decimal => None,
+        }
+    }
+
+    /// Returns the number of zero bits each leading zero digit preserves, or
+    /// None if the form does not preserve leading zeros. Octal digits are not
+    /// packed into bytes, as they do not divide a byte, but each leading zero
+    /// digit still preserves 3 bits.
+    pub fn bits_per_zero_digit(self) -> Option<usize> {
+        match self {
+            Form::Octal => Some(3),
+            _ => self.bits_per_digit(),
+        }
+    }
+}
+
+impl fmt::Display for Form {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Form::Hex => "hex",
+            Form::Binary => "binary",
+            Form::Octal => "octal",
+            Form::Decimal => "decimal",
+        })
+    }
+}
+
+/// An error converting an integer literal to bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The literal has no digits.
+    Empty,
+    /// The literal is negative.
+    Negative,
+    /// The literal has a digit that is invalid for its form.
+    InvalidDigit { form: Form, digit: char },
+    /// The literal has leading zeros in a form that cannot preserve them.
+    LeadingZeros { form: Form },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "integer literal has no digits"),
+            ParseError::Negative => write!(f, "negative values unsupported"),
+            ParseError::InvalidDigit { form, digit } => write!(
+                f,
+                "invalid digit `{}` in integer literal in {} form",
+                digit, form
+            ),
+            ParseError::LeadingZeros { form } => write!(
+                f,
+                "leading zeros are not preserved or supported on integer literals in {} form",
+                form
+            ),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// The digits of an integer literal, with underscores, the base prefix, and
+/// any integer type suffix removed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Literal {
+    pub form: Form,
+    pub digits: String,
+}
+
+impl FromStr for Literal {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_literal(s)
+    }
+}
+
+impl Literal {
+    /// Packs the digits of a hex or binary literal into bytes, most significant
+    /// first. The bytes hold every digit, so a leading byte is only partially
+    /// filled when the digits do not fill it. Returns None for octal and
+    /// decimal literals, whose digits do not divide bytes.
+    ///
+    /// ```
+    /// let lit: bytes_lit_core::Literal = "0xfff".parse().unwrap();
+    /// assert_eq!(lit.packed().unwrap(), Some(vec![15, 255]));
+    /// ```
+    pub fn packed(&self) -> Result<Option<Vec<u8>>, ParseError> {
+        match self.form.bits_per_digit() {
+            Some(bits_per_digit) => pack_digits(&self.digits, self.form, bits_per_digit).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+const SUFFIXES: &[&str] = &[
+    "u128", "i128", "usize", "isize", "u16", "i16", "u32", "i32", "u64", "i64", "u8", "i8",
+];
+
+/// Parses the digits of an integer literal as written in Rust source, such as
+/// `0x00ff`, `0b1010_1010`, `0o17`, or `255u8`.
+pub fn parse_literal(lit: &str) -> Result<Literal, ParseError> {
+    let lit = lit.trim();
+    if lit.starts_with('-') {
+        return Err(ParseError::Negative);
+    }
+    let (form, rest) = if let Some(rest) = lit.strip_prefix("0x") {
+        (Form::Hex, rest)
+    } else if let Some(rest) = lit.strip_prefix("0b") {
+        (Form::Binary, rest)
+    } else if let Some(rest) = lit.strip_prefix("0o") {
+        (Form::Octal, rest)
+    } else {
+        (Form::Decimal, lit)
+    };
+    let rest = SUFFIXES
+        .iter()
+        .find_map(|s| rest.strip_suffix(s))
+        .unwrap_or(rest);
+    let digits = rest.replace('_', "");
+    if let Some(digit) = digits.chars().find(|d| !d.is_digit(form.radix())) {
+        return Err(ParseError::InvalidDigit { form, digit });
+    }
+    if digits.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    Ok(Literal { form, digits })
+}
+
+/// Converts an integer literal to bytes, most significant first, as the
+/// `bytes!` macro does.
+///
+/// Hex and binary digits are packed directly, so leading zero digits are
+/// preserved, and a leading byte is only partially filled when the digits do
+/// not fill it. Octal and decimal literals are converted to the fewest bytes
+/// that hold the value. Each leading zero of an octal literal preserves 3 zero
+/// bits before the bits of the value, and the total is rounded up to whole
+/// bytes, so `0o0377` is 11 bits in 2 bytes and `0o000377` is 17 bits in 3
+/// bytes. It is an error for decimal literals to have leading zeros.
+pub fn parse_int_literal(lit: &str) -> Result<Vec<u8>, ParseError> {
+    let literal = parse_literal(lit)?;
+    if let Some(bytes) = literal.packed()? {
+        return Ok(bytes);
+    }
+    let Literal { form, digits } = literal;
+    let zero_bits = match form.bits_per_zero_digit() {
+        Some(bits) => digits.bytes().take_while(|d| *d == b'0').count() * bits,
+        None if digits.starts_with('0') => return Err(ParseError::LeadingZeros { form }),
+        None => 0,
+    };
+    let int = BigUint::parse_bytes(digits.as_bytes(), form.radix()).expect("valid digits");
+    let total_bits = zero_bits + int.bits() as usize;
+    let mut bytes = int.to_bytes_be();
+    let len = (total_bits + 7) / 8;
+    if len > bytes.len() {
+        bytes.splice(0..0, vec![0; len - bytes.len()]);
+    }
+    Ok(bytes)
+}
+
+/// Converts an integer literal to the fewest bytes that hold its value, most
+/// significant first, as the `bytesmin!` macro does. Leading zeros are
+/// discarded, leaving at least one byte.
+pub fn parse_int_literal_min(lit: &str) -> Result<Vec<u8>, ParseError> {
+    let literal = parse_literal(lit)?;
+    let bytes = match literal.packed()? {
+        Some(bytes) => bytes,
+        None => BigUint::parse_bytes(literal.digits.as_bytes(), literal.form.radix())
+            .expect("valid digits")
+            .to_bytes_be(),
+    };
+    let start = bytes
+        .iter()
+        .position(|b| *b != 0)
+        .unwrap_or(bytes.len() - 1);
+    Ok(bytes[start..].to_vec())
+}
+
+/// Packs the digits of the form, of the given bits per digit, into bytes, most
+/// significant first, erroring on a digit that is invalid for the form.
+fn pack_digits(digits: &str, form: Form, bits_per_digit: usize) -> Result<Vec<u8>, ParseError> {
+    if digits.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    let digits_per_byte = 8 / bits_per_digit;
+    let mut bytes = vec![0u8; (digits.len() + digits_per_byte - 1) / digits_per_byte];
+    let len = bytes.len();
+    // Fill from the least significant digit, which is at the end.
+    for (i, digit) in digits.chars().rev().enumerate() {
+        let value = digit
+            .to_digit(form.radix())
+            .ok_or(ParseError::InvalidDigit { form, digit })? as u8;
+        bytes[len - 1 - i / digits_per_byte] |= value << (i % digits_per_byte * bits_per_digit);
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_int_literal, parse_int_literal_min, Form, Literal, ParseError};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn literals() {
+        let table: &[(&str, Result<Vec<u8>, ParseError>)] = &[
+            ("0x00ff", Ok(vec![0, 255])),
+            ("0xfff", Ok(vec![15, 255])),
+            ("0x00_ff_u16", Ok(vec![0, 255])),
+            ("0b0000_0001", Ok(vec![1])),
+            ("0o17", Ok(vec![15])),
+            ("256", Ok(vec![1, 0])),
+            ("255u8", Ok(vec![255])),
+            (
+                "0",
+                Err(ParseError::LeadingZeros {
+                    form: Form::Decimal,
+                }),
+            ),
+            ("0o017", Ok(vec![15])),
+            ("0o0377", Ok(vec![0, 255])),
+            ("0o000377", Ok(vec![0, 0, 255])),
+            ("0o0", Ok(vec![0])),
+            ("0o000", Ok(vec![0, 0])),
+            (
+                "00",
+                Err(ParseError::LeadingZeros {
+                    form: Form::Decimal,
+                }),
+            ),
+            ("-1", Err(ParseError::Negative)),
+            ("0x", Err(ParseError::Empty)),
+            (
+                "0xfg",
+                Err(ParseError::InvalidDigit {
+                    form: Form::Hex,
+                    digit: 'g',
+                }),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse_int_literal(t.0), t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn literals_min() {
+        let table: &[(&str, Result<Vec<u8>, ParseError>)] = &[
+            ("0x00ff", Ok(vec![255])),
+            ("0x0000", Ok(vec![0])),
+            ("0", Ok(vec![0])),
+            ("0o0017", Ok(vec![15])),
+            ("65536", Ok(vec![1, 0, 0])),
+            ("-1", Err(ParseError::Negative)),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse_int_literal_min(t.0), t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn packed() {
+        let table: &[(Form, &str, Option<&[u8]>)] = &[
+            (Form::Hex, "0", Some(&[0x00])),
+            (Form::Hex, "1", Some(&[0x01])),
+            (Form::Hex, "abc", Some(&[0x0a, 0xbc])),
+            (Form::Hex, "00aBcD", Some(&[0x00, 0xab, 0xcd])),
+            (Form::Binary, "1", Some(&[0x01])),
+            (Form::Binary, "101", Some(&[0x05])),
+            (Form::Binary, "000000001", Some(&[0x00, 0x01])),
+            (Form::Binary, "110000000", Some(&[0x01, 0x80])),
+            (Form::Octal, "17", None),
+            (Form::Decimal, "255", None),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let literal = Literal {
+                form: t.0,
+                digits: t.1.to_string(),
+            };
+            let packed = literal.packed().unwrap();
+            assert_eq!(packed.as_deref(), t.2, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn packed_errors() {
+        let table: &[(Form, &str, ParseError)] = &[
+            (Form::Hex, "", ParseError::Empty),
+            (
+                Form::Hex,
+                "0g",
+                ParseError::InvalidDigit {
+                    form: Form::Hex,
+                    digit: 'g',
+                },
+            ),
+            (
+                Form::Binary,
+                "102",
+                ParseError::InvalidDigit {
+                    form: Form::Binary,
+                    digit: '2',
+                },
+            ),
+            (
+                Form::Hex,
+                "0é",
+                ParseError::InvalidDigit {
+                    form: Form::Hex,
+                    digit: 'é',
+                },
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let literal = Literal {
+                form: t.0,
+                digits: t.1.to_string(),
+            };
+            assert_eq!(literal.packed(), Err(t.2.clone()), "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            ParseError::LeadingZeros {
+                form: Form::Decimal
+            }
+            .to_string(),
+            "leading zeros are not preserved or supported on integer literals in decimal form"
+        );
+        assert_eq!(
+            ParseError::InvalidDigit {
+                form: Form::Binary,
+                digit: '2'
+            }
+            .to_string(),
+            "invalid digit `2` in integer literal in binary form"
+        );
+    }
+}
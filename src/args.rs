@@ -0,0 +1,431 @@
+use std::{fmt::Display, str::FromStr};
+
+use proc_macro2::Span;
+use syn::{
+    ext::IdentExt, parse::ParseStream, Error, Expr, ExprLit, Ident, Lit, LitStr, RangeLimits, Token,
+};
+
+/// An option following the input to a macro, either a flag such as `strict`,
+/// or a named value such as `size = 4`.
+pub struct Arg {
+    pub name: Ident,
+    pub value: Option<Expr>,
+}
+
+/// The options following the input to a macro, in the grammar shared by all
+/// macros that take options: the options are separated from the input by a
+/// comma or semicolon, and from each other by commas, and each option is a
+/// flag or a `name = value` pair, such as
+/// `bytes!(0x1234; size = 8, reverse)`.
+///
+/// Options are taken by name as their values are read. Rules between options
+/// are checked with [`Args::exclusive`] and [`Args::requires`] before they are
+/// taken, and [`Args::finish`] errors for any option that is left.
+#[derive(Default)]
+pub struct Args(Vec<Arg>);
+
+impl Args {
+    /// Parses options from the remainder of the input stream.
+    pub fn parse(input: ParseStream) -> syn::Result<Self> {
+        Self::parse_separated(input, true)
+    }
+
+    /// Parses options that are the whole input of a macro, without a
+    /// separator before the first option.
+    pub fn parse_only(input: ParseStream) -> syn::Result<Self> {
+        Self::parse_separated(input, false)
+    }
+
+    fn parse_separated(input: ParseStream, leading: bool) -> syn::Result<Self> {
+        let mut opts: Vec<Arg> = Vec::new();
+        let mut first = true;
+        while !input.is_empty() {
+            if first && !leading {
+                // The first option has no separator.
+            } else if first && input.peek(Token![;]) {
+                input.parse::<Token![;]>()?;
+            } else {
+                input.parse::<Token![,]>()?;
+            }
+            first = false;
+            // Allow a trailing comma.
+            if input.is_empty() {
+                break;
+            }
+            let name = input.call(Ident::parse_any)?;
+            let value = if input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+                Some(input.parse()?)
+            } else {
+                None
+            };
+            if opts.iter().any(|o| o.name == name) {
+                return Err(Error::new(
+                    name.span(),
+                    format!("duplicate option `{}`", name),
+                ));
+            }
+            opts.push(Arg { name, value });
+        }
+        Ok(Self(opts))
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.0.iter().position(|o| o.name == name)
+    }
+
+    fn take(&mut self, name: &str) -> Option<Arg> {
+        let i = self.position(name)?;
+        Some(self.0.remove(i))
+    }
+
+    /// Returns the span of the name of the option, or the call site if it was
+    /// not given, for errors about its value found after it is taken.
+    pub fn span(&self, name: &str) -> Span {
+        self.position(name)
+            .map_or_else(Span::call_site, |i| self.0[i].name.span())
+    }
+
+    /// Returns true if the flag was given.
+    pub fn flag(&mut self, name: &str) -> syn::Result<bool> {
+        match self.take(name) {
+            Some(Arg { value: None, .. }) => Ok(true),
+            Some(Arg {
+                name,
+                value: Some(_),
+            }) => Err(Error::new(
+                name.span(),
+                format!("option `{}` is a flag and does not take a value", name),
+            )),
+            None => Ok(false),
+        }
+    }
+
+    /// Returns the value of the named option, which must be an integer
+    /// literal.
+    pub fn int<N>(&mut self, name: &str) -> syn::Result<Option<N>>
+    where
+        N: FromStr,
+        N::Err: Display,
+    {
+        match self.take(name) {
+            Some(Arg {
+                value:
+                    Some(Expr::Lit(ExprLit {
+                        lit: Lit::Int(lit), ..
+                    })),
+                ..
+            }) => Ok(Some(lit.base10_parse()?)),
+            Some(Arg { name, .. }) => Err(Error::new(
+                name.span(),
+                format!("option `{}` requires an integer value", name),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the value of the named option, which must be a range of integer
+    /// literals such as `0..4`, `0..=3`, `4..`, or `..4`, as the start and the
+    /// exclusive end, if the range has an end.
+    pub fn range(&mut self, name: &str) -> syn::Result<Option<(usize, Option<usize>)>> {
+        let opt = match self.take(name) {
+            Some(opt) => opt,
+            None => return Ok(None),
+        };
+        let invalid = || {
+            Error::new(
+                opt.name.span(),
+                format!(
+                    "option `{}` requires a range of integers, such as `0..4`",
+                    opt.name
+                ),
+            )
+        };
+        let range = match &opt.value {
+            Some(Expr::Range(range)) => range,
+            _ => return Err(invalid()),
+        };
+        let bound = |expr: &Option<Box<Expr>>| -> syn::Result<Option<usize>> {
+            match expr.as_deref() {
+                Some(Expr::Lit(ExprLit {
+                    lit: Lit::Int(lit), ..
+                })) => Ok(Some(lit.base10_parse()?)),
+                Some(_) => Err(invalid()),
+                None => Ok(None),
+            }
+        };
+        let start = bound(&range.start)?.unwrap_or(0);
+        let end = match (bound(&range.end)?, &range.limits) {
+            (Some(end), RangeLimits::Closed(_)) => Some(end + 1),
+            (end, _) => end,
+        };
+        Ok(Some((start, end)))
+    }
+
+    /// Returns the value of the named option, which must be one of the given
+    /// identifiers.
+    pub fn ident(&mut self, name: &str, allowed: &[&str]) -> syn::Result<Option<Ident>> {
+        match self.take(name) {
+            Some(Arg {
+                value: Some(Expr::Path(path)),
+                ..
+            }) if path
+                .path
+                .get_ident()
+                .map_or(false, |i| allowed.iter().any(|a| i == a)) =>
+            {
+                Ok(path.path.get_ident().cloned())
+            }
+            Some(Arg { name, .. }) => Err(Error::new(
+                name.span(),
+                format!("option `{}` requires one of: {}", name, allowed.join(", ")),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the value of the named option, which may be given as a flag for
+    /// the default, or with one of the allowed identifiers.
+    pub fn flag_or_ident(
+        &mut self,
+        name: &str,
+        default: &str,
+        allowed: &[&str],
+    ) -> syn::Result<Option<String>> {
+        match self.take(name) {
+            Some(Arg { value: None, .. }) => Ok(Some(default.to_string())),
+            Some(opt) => {
+                self.0.push(opt);
+                Ok(self.ident(name, allowed)?.map(|i| i.to_string()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the value of the named option, which must be an identifier.
+    pub fn take_ident(&mut self, name: &str) -> syn::Result<Option<Ident>> {
+        match self.take(name) {
+            Some(Arg {
+                value: Some(Expr::Path(path)),
+                ..
+            }) if path.path.get_ident().is_some() => Ok(path.path.get_ident().cloned()),
+            Some(Arg { name, .. }) => Err(Error::new(
+                name.span(),
+                format!("option `{}` requires an identifier value", name),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the value of the named option, which must be a string literal.
+    pub fn str(&mut self, name: &str) -> syn::Result<Option<LitStr>> {
+        match self.take(name) {
+            Some(Arg {
+                value:
+                    Some(Expr::Lit(ExprLit {
+                        lit: Lit::Str(lit), ..
+                    })),
+                ..
+            }) => Ok(Some(lit)),
+            Some(Arg { name, .. }) => Err(Error::new(
+                name.span(),
+                format!("option `{}` requires a string value", name),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the value of the named option, which must be a literal.
+    pub fn lit(&mut self, name: &str) -> syn::Result<Option<Lit>> {
+        match self.take(name) {
+            Some(Arg {
+                value: Some(Expr::Lit(ExprLit { lit, .. })),
+                ..
+            }) => Ok(Some(lit)),
+            Some(Arg { name, .. }) => Err(Error::new(
+                name.span(),
+                format!("option `{}` requires a literal value", name),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Errors if both of the named options are given, at the option given
+    /// last.
+    pub fn exclusive(&self, a: &str, b: &str) -> syn::Result<()> {
+        match (self.position(a), self.position(b)) {
+            (Some(i), Some(j)) => Err(Error::new(
+                self.0[i.max(j)].name.span(),
+                format!("options `{}` and `{}` cannot be used together", a, b),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Errors if the named option is given without the option it requires.
+    pub fn requires(&self, name: &str, required: &str) -> syn::Result<()> {
+        match (self.position(name), self.position(required)) {
+            (Some(i), None) => Err(Error::new(
+                self.0[i].name.span(),
+                format!("option `{}` requires the `{}` option", name, required),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Errors if any options remain that have not been taken.
+    pub fn finish(self) -> syn::Result<()> {
+        match self.0.into_iter().next() {
+            Some(opt) => Err(Error::new(
+                opt.name.span(),
+                format!("unknown option `{}`", opt.name),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Args;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::TokenStream as TokenStream2;
+    use syn::parse::Parser;
+
+    /// An error message and the column it is reported at.
+    type Located = (&'static str, usize);
+
+    /// Parses the options from the source, with a separator before the first
+    /// option if leading, as [`Args::parse`] does, or without as
+    /// [`Args::parse_only`] does.
+    fn parse(source: &str, leading: bool) -> syn::Result<Args> {
+        let tokens: TokenStream2 = source.parse().unwrap();
+        if leading {
+            Args::parse.parse2(tokens)
+        } else {
+            Args::parse_only.parse2(tokens)
+        }
+    }
+
+    /// Returns the message of the error and the column it is reported at.
+    fn error(e: syn::Error) -> (String, usize) {
+        (e.to_string(), e.span().start().column)
+    }
+
+    #[test]
+    fn parsing() {
+        // The names of the options parsed, joined by commas.
+        let table: &[(&str, bool, Result<&str, Located>)] = &[
+            ("", true, Ok("")),
+            ("; size = 4, reverse", true, Ok("size, reverse")),
+            (", size = 4, reverse", true, Ok("size, reverse")),
+            // A trailing comma is allowed.
+            ("; size = 4,", true, Ok("size")),
+            (",", true, Ok("")),
+            // Keywords are allowed as names.
+            ("; type = 1", true, Ok("type")),
+            ("size = 4, reverse", false, Ok("size, reverse")),
+            ("size = 4,", false, Ok("size")),
+            ("size = 4", true, Err(("expected `,`", 0))),
+            // Only the first separator may be a semicolon.
+            ("; size = 4; reverse", true, Err(("expected `,`", 10))),
+            ("; size = 4", false, Err(("expected ident", 0))),
+            (
+                "; size = 4, reverse, size = 8",
+                true,
+                Err(("duplicate option `size`", 21)),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let parsed = parse(t.0, t.1)
+                .map(|args| {
+                    let names: Vec<_> = args.0.iter().map(|a| a.name.to_string()).collect();
+                    names.join(", ")
+                })
+                .map_err(error);
+            let expect =
+                t.2.map(str::to_string)
+                    .map_err(|(message, column)| (message.to_string(), column));
+            assert_eq!(parsed, expect, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn rules() {
+        type Rule = fn(&mut Args) -> syn::Result<()>;
+        let table: &[(&str, Rule, Result<(), Located>)] = &[
+            (
+                "; strict, allow_odd",
+                |args| args.exclusive("strict", "allow_odd"),
+                Err((
+                    "options `strict` and `allow_odd` cannot be used together",
+                    10,
+                )),
+            ),
+            // The error is at the option given last, whatever the order of the
+            // names.
+            (
+                "; allow_odd, strict",
+                |args| args.exclusive("strict", "allow_odd"),
+                Err((
+                    "options `strict` and `allow_odd` cannot be used together",
+                    13,
+                )),
+            ),
+            (
+                "; strict",
+                |args| args.exclusive("strict", "allow_odd"),
+                Ok(()),
+            ),
+            (
+                "; reverse, separator = \":\"",
+                |args| args.requires("separator", "group"),
+                Err(("option `separator` requires the `group` option", 11)),
+            ),
+            (
+                "; separator = \":\", group = 2",
+                |args| args.requires("separator", "group"),
+                Ok(()),
+            ),
+            (
+                "; group = 2",
+                |args| args.requires("separator", "group"),
+                Ok(()),
+            ),
+            (
+                "; size = 4, sise = 8",
+                |args| {
+                    args.int::<usize>("size")?;
+                    std::mem::take(args).finish()
+                },
+                Err(("unknown option `sise`", 12)),
+            ),
+            (
+                "; size = 4, reverse",
+                |args| {
+                    args.int::<usize>("size")?;
+                    args.flag("reverse")?;
+                    std::mem::take(args).finish()
+                },
+                Ok(()),
+            ),
+            (
+                "; reverse = 1",
+                |args| args.flag("reverse").map(|_| ()),
+                Err(("option `reverse` is a flag and does not take a value", 2)),
+            ),
+            (
+                "; size = x",
+                |args| args.int::<usize>("size").map(|_| ()),
+                Err(("option `size` requires an integer value", 2)),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let mut args = parse(t.0, true).unwrap();
+            let result = (t.1)(&mut args).map_err(error);
+            let expect =
+                t.2.map_err(|(message, column)| (message.to_string(), column));
+            assert_eq!(result, expect, "table entry: {}", i);
+        }
+    }
+}
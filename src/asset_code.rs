@@ -0,0 +1,134 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitStr,
+};
+
+use crate::{args::Args, emit, error::StrError};
+
+struct Input {
+    lit: LitStr,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn asset_code(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let size = input.args.int::<usize>("size")?;
+    input.args.finish()?;
+
+    let code = input.lit.value();
+    if let Some(i) = code.bytes().position(|c| !c.is_ascii_alphanumeric()) {
+        return Err(StrError::new(
+            i,
+            "asset codes may only contain the characters a-z, A-Z, and 0-9",
+        )
+        .into_error(&input.lit));
+    }
+    if code.is_empty() || code.len() > 12 {
+        return Err(Error::new(
+            input.lit.span(),
+            format!(
+                "asset codes must be 1 to 12 characters, found {}",
+                code.len()
+            ),
+        ));
+    }
+
+    // Codes of up to 4 characters use the 4 byte form, and longer codes the 12
+    // byte form, unless a size is given.
+    let size = match size {
+        Some(size @ (4 | 12)) => size,
+        Some(_) => return Err(Error::new(input.lit.span(), "size must be 4 or 12")),
+        None if code.len() <= 4 => 4,
+        None => 12,
+    };
+    if code.len() > size {
+        return Err(Error::new(
+            input.lit.span(),
+            format!(
+                "asset code of {} characters does not fit in {} bytes",
+                code.len(),
+                size
+            ),
+        ));
+    }
+
+    let mut bytes = code.into_bytes();
+    bytes.resize(size, 0);
+    Ok(emit::array(&bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::asset_code;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn padded() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!("USD"), parse_quote!([85u8, 83u8, 68u8, 0u8])),
+            (quote!("USDC"), parse_quote!([85u8, 83u8, 68u8, 67u8])),
+            (
+                quote!("yUSDC"),
+                parse_quote!([121u8, 85u8, 83u8, 68u8, 67u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8]),
+            ),
+            (
+                quote!("USD", size = 12),
+                parse_quote!([85u8, 83u8, 68u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8]),
+            ),
+            (quote!("X", size = 4), parse_quote!([88u8, 0u8, 0u8, 0u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = asset_code(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table = &[
+            (
+                quote!(""),
+                "asset codes must be 1 to 12 characters, found 0",
+            ),
+            (
+                quote!("ABCDEFGHIJKLM"),
+                "asset codes must be 1 to 12 characters, found 13",
+            ),
+            (
+                quote!("US-D"),
+                "asset codes may only contain the characters a-z, A-Z, and 0-9 at index 2",
+            ),
+            (
+                quote!("USDCX", size = 4),
+                "asset code of 5 characters does not fit in 4 bytes",
+            ),
+            (quote!("USD", size = 8), "size must be 4 or 12"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = asset_code(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
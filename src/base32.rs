@@ -0,0 +1,366 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitStr,
+};
+
+use crate::{args::Args, emit, error::StrError};
+
+/// The symbols of the Crockford alphabet, followed by the five extra symbols of
+/// check values 32 to 36.
+const CROCKFORD: &[u8; 37] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+
+struct Input {
+    lit: LitStr,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn base32(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let alphabet = match input
+        .args
+        .ident("alphabet", &["standard", "hex", "crockford"])?
+    {
+        Some(a) if a == "hex" => Alphabet::Hex,
+        Some(a) if a == "crockford" => Alphabet::Crockford,
+        _ => Alphabet::Standard,
+    };
+    let pad = input.args.ident("pad", &["optional", "required", "none"])?;
+    let check = input.args.flag("check")?;
+    input.args.finish()?;
+
+    let bytes = if alphabet == Alphabet::Crockford {
+        if let Some(pad) = pad {
+            return Err(Error::new(
+                pad.span(),
+                "option `pad` is not supported by the crockford alphabet, which has no padding",
+            ));
+        }
+        decode_crockford(&input.lit.value(), check)
+    } else {
+        if check {
+            return Err(Error::new(
+                input.lit.span(),
+                "option `check` requires `alphabet = crockford`",
+            ));
+        }
+        let pad = match pad {
+            Some(p) if p == "required" => Pad::Required,
+            Some(p) if p == "none" => Pad::None,
+            _ => Pad::Optional,
+        };
+        decode(&input.lit.value(), alphabet, pad)
+    };
+    let bytes = bytes.map_err(|e| e.into_error(&input.lit))?;
+    Ok(emit::array(&bytes))
+}
+
+/// The base32 alphabets defined by RFC 4648, and the alphabet of Douglas
+/// Crockford.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alphabet {
+    Standard,
+    Hex,
+    /// Case insensitive digits and letters without U, where I and L are read
+    /// as 1 and O as 0.
+    Crockford,
+}
+
+impl Alphabet {
+    fn value(self, c: u8) -> Option<u8> {
+        match (self, c) {
+            (Alphabet::Standard, b'A'..=b'Z') => Some(c - b'A'),
+            (Alphabet::Standard, b'2'..=b'7') => Some(c - b'2' + 26),
+            (Alphabet::Hex, b'0'..=b'9') => Some(c - b'0'),
+            (Alphabet::Hex, b'A'..=b'V') => Some(c - b'A' + 10),
+            (Alphabet::Crockford, _) => {
+                let c = c.to_ascii_uppercase();
+                match c {
+                    b'O' => Some(0),
+                    b'I' | b'L' => Some(1),
+                    _ => CROCKFORD[..32]
+                        .iter()
+                        .position(|a| *a == c)
+                        .map(|v| v as u8),
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Whether padding is required, optional, or not allowed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pad {
+    Optional,
+    Required,
+    None,
+}
+
+/// Decodes base32. Padding, if present, must be complete.
+pub fn decode(s: &str, alphabet: Alphabet, pad: Pad) -> Result<Vec<u8>, StrError> {
+    let input = s.as_bytes();
+    let data_len = input.len() - input.iter().rev().take_while(|c| **c == b'=').count();
+    let padding = input.len() - data_len;
+    match pad {
+        Pad::None if padding > 0 => {
+            return Err(StrError::new(data_len, "padding is not allowed"));
+        }
+        Pad::Required if input.len() % 8 != 0 || (padding == 0 && data_len % 8 != 0) => {
+            return Err(StrError::new(input.len(), "padding is required"));
+        }
+        _ => {}
+    }
+    if padding > 0 && (padding > 6 || input.len() % 8 != 0) {
+        return Err(StrError::new(data_len, "invalid padding"));
+    }
+
+    let symbols = input[..data_len]
+        .iter()
+        .enumerate()
+        .map(|(i, c)| symbol(alphabet, i, *c))
+        .collect::<Result<Vec<_>, _>>()?;
+    pack(&symbols)
+}
+
+/// Decodes Crockford base32, where hyphens are ignored. If check is set, the
+/// last symbol is the check symbol, the value of the other symbols as a number
+/// modulo 37.
+pub fn decode_crockford(s: &str, check: bool) -> Result<Vec<u8>, StrError> {
+    let mut symbols = s
+        .bytes()
+        .enumerate()
+        .filter(|(_, c)| *c != b'-')
+        .collect::<Vec<_>>();
+    let expected = match (check, symbols.pop()) {
+        (true, Some((i, c))) => {
+            let v = CROCKFORD
+                .iter()
+                .position(|a| *a == c.to_ascii_uppercase())
+                .ok_or_else(|| StrError::new(i, format!("invalid check symbol {:?}", c as char)))?;
+            Some((i, v))
+        }
+        (true, None) => return Err(StrError::new(0, "missing check symbol")),
+        (false, Some(last)) => {
+            symbols.push(last);
+            None
+        }
+        (false, None) => None,
+    };
+    let symbols = symbols
+        .into_iter()
+        .map(|(i, c)| symbol(Alphabet::Crockford, i, c))
+        .collect::<Result<Vec<_>, _>>()?;
+    if let Some((i, expected)) = expected {
+        let found = symbols
+            .iter()
+            .fold(0, |acc, (_, v)| (acc * 32 + usize::from(*v)) % 37);
+        if found != expected {
+            return Err(StrError::new(
+                i,
+                format!(
+                    "invalid check symbol, expected {:?}",
+                    CROCKFORD[found] as char
+                ),
+            ));
+        }
+    }
+    pack(&symbols)
+}
+
+/// Returns the index and the value of the symbol.
+fn symbol(alphabet: Alphabet, i: usize, c: u8) -> Result<(usize, u8), StrError> {
+    let v = alphabet
+        .value(c)
+        .ok_or_else(|| StrError::new(i, format!("invalid base32 character {:?}", c as char)))?;
+    Ok((i, v))
+}
+
+/// Packs the 5-bit values of the symbols, with their indexes, into bytes.
+fn pack(symbols: &[(usize, u8)]) -> Result<Vec<u8>, StrError> {
+    let mut bytes = Vec::with_capacity(symbols.len() * 5 / 8);
+    let mut acc = 0u32;
+    let mut acc_bits = 0;
+    for (_, v) in symbols {
+        acc = acc << 5 | u32::from(*v);
+        acc_bits += 5;
+        if acc_bits >= 8 {
+            acc_bits -= 8;
+            bytes.push((acc >> acc_bits) as u8);
+            acc &= (1 << acc_bits) - 1;
+        }
+    }
+    if let Some((last, _)) = symbols.last() {
+        if matches!(symbols.len() % 8, 1 | 3 | 6) {
+            return Err(StrError::new(*last, "invalid length"));
+        }
+        if acc != 0 {
+            return Err(StrError::new(*last, "non-zero trailing bits"));
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{base32, decode, decode_crockford, Alphabet, Pad};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn rfc4648_vectors() {
+        let table: &[(&str, &str, &[u8])] = &[
+            ("", "", b""),
+            ("MY======", "CO======", b"f"),
+            ("MZXQ====", "CPNG====", b"fo"),
+            ("MZXW6===", "CPNMU===", b"foo"),
+            ("MZXW6YQ=", "CPNMUOG=", b"foob"),
+            ("MZXW6YTB", "CPNMUOJ1", b"fooba"),
+            ("MZXW6YTBOI======", "CPNMUOJ1E8======", b"foobar"),
+            ("MZXW6YTBOI", "CPNMUOJ1E8", b"foobar"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                decode(t.0, Alphabet::Standard, Pad::Optional).as_deref(),
+                Ok(t.2),
+                "table entry: {}",
+                i
+            );
+            assert_eq!(
+                decode(t.1, Alphabet::Hex, Pad::Optional).as_deref(),
+                Ok(t.2),
+                "table entry: {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn padding() {
+        assert_eq!(
+            decode("MZXW6===", Alphabet::Standard, Pad::Required).as_deref(),
+            Ok(&b"foo"[..])
+        );
+        assert_eq!(
+            decode("MZXW6", Alphabet::Standard, Pad::Required),
+            Err(StrError::new(5, "padding is required"))
+        );
+        assert_eq!(
+            decode("MZXW6===", Alphabet::Standard, Pad::None),
+            Err(StrError::new(5, "padding is not allowed"))
+        );
+        assert_eq!(
+            decode("MZXW6==", Alphabet::Standard, Pad::Optional),
+            Err(StrError::new(5, "invalid padding"))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(&str, StrError)] = &[
+            ("MZXW1", StrError::new(4, "invalid base32 character '1'")),
+            ("mzxw6", StrError::new(0, "invalid base32 character 'm'")),
+            ("MZX", StrError::new(2, "invalid length")),
+            ("MZ", StrError::new(1, "non-zero trailing bits")),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                decode(t.0, Alphabet::Standard, Pad::Optional).as_ref(),
+                Err(&t.1),
+                "table entry: {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn crockford() {
+        let table: &[(&str, bool, &[u8])] = &[
+            ("", false, b""),
+            ("CSQPYRK1E8", false, b"foobar"),
+            ("csqpyrk1e8", false, b"foobar"),
+            ("CSQPY-RK1E8", false, b"foobar"),
+            ("CSQPYRKIE8", false, b"foobar"),
+            ("CSQPYRKlE8", false, b"foobar"),
+            ("CSQPYQ", true, b"foo"),
+            ("CSQ-PY-Q", true, b"foo"),
+            ("0", true, b""),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                decode_crockford(t.0, t.1).as_deref(),
+                Ok(t.2),
+                "table entry: {}",
+                i
+            );
+        }
+
+        let table: &[(&str, bool, StrError)] = &[
+            (
+                "CSQPYU",
+                false,
+                StrError::new(5, "invalid base32 character 'U'"),
+            ),
+            (
+                "CSQPYR",
+                true,
+                StrError::new(5, "invalid check symbol, expected 'Q'"),
+            ),
+            ("CSQPY#", true, StrError::new(5, "invalid check symbol '#'")),
+            ("", true, StrError::new(0, "missing check symbol")),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                decode_crockford(t.0, t.1).as_ref(),
+                Err(&t.2),
+                "table entry: {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn options() {
+        let tokens = base32(quote! {"CPNMU", alphabet = hex, pad = none});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([102u8, 111u8, 111u8]);
+        assert_eq!(parsed, expect);
+
+        let tokens = base32(quote! {"csqpy-q", alphabet = crockford, check});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([102u8, 111u8, 111u8]);
+        assert_eq!(parsed, expect);
+
+        let tokens = base32(quote! {"MZXW6", check});
+        let expect = Error::new(
+            Span::call_site(),
+            "option `check` requires `alphabet = crockford`",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+
+        let tokens = base32(quote! {"MZXW6", pad = required});
+        let expect = Error::new(Span::call_site(), "padding is required at index 5")
+            .to_compile_error()
+            .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
@@ -0,0 +1,111 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::LitStr;
+
+use crate::{emit, error::StrError};
+
+/// The Base45 alphabet of RFC 9285, the characters of the QR code
+/// alphanumeric mode.
+const ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+pub fn base45(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<LitStr>(input).and_then(|lit| {
+        let bytes = decode(&lit.value()).map_err(|e| e.into_error(&lit))?;
+        Ok(emit::array(&bytes))
+    }) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Decodes Base45, as specified by RFC 9285. Each group of three characters,
+/// least significant first, is two bytes, and a final group of two characters
+/// is one byte.
+pub fn decode(s: &str) -> Result<Vec<u8>, StrError> {
+    let mut values = Vec::with_capacity(s.len());
+    for (i, c) in s.char_indices() {
+        let v = ALPHABET
+            .iter()
+            .position(|a| char::from(*a) == c)
+            .ok_or_else(|| StrError::new(i, format!("invalid base45 character {:?}", c)))?;
+        values.push(v as u32);
+    }
+    if values.len() % 3 == 1 {
+        return Err(StrError::new(
+            s.len() - 1,
+            "invalid base45 length, the final group has a single character",
+        ));
+    }
+
+    let mut bytes = Vec::with_capacity(values.len() / 3 * 2 + 1);
+    for (group, chunk) in values.chunks(3).enumerate() {
+        let value = chunk.iter().rev().fold(0, |acc, v| acc * 45 + v);
+        if chunk.len() == 3 {
+            if value > 0xffff {
+                return Err(StrError::new(group * 3, "base45 group overflows 16 bits"));
+            }
+            bytes.extend_from_slice(&(value as u16).to_be_bytes());
+        } else {
+            if value > 0xff {
+                return Err(StrError::new(
+                    group * 3,
+                    "base45 final group of two characters overflows 8 bits",
+                ));
+            }
+            bytes.push(value as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{base45, decode};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray};
+
+    #[test]
+    fn values() {
+        // The examples of RFC 9285.
+        let table: &[(&str, &[u8])] = &[
+            ("", b""),
+            ("BB8", b"AB"),
+            ("%69 VD92EX0", b"Hello!!"),
+            ("UJCLQE7W581", b"base-45"),
+            ("QED8WEX0", b"ietf!"),
+            ("FGW", &[0xff, 0xff]),
+            ("U5", &[0xff]),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(decode(t.0), Ok(t.1.to_vec()), "table entry: {}", i);
+        }
+
+        let tokens = base45(quote!("BB8"));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([65u8, 66u8]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(&str, StrError)] = &[
+            ("BB8a", StrError::new(3, "invalid base45 character 'a'")),
+            (
+                "BB8B",
+                StrError::new(
+                    3,
+                    "invalid base45 length, the final group has a single character",
+                ),
+            ),
+            ("GGW", StrError::new(0, "base45 group overflows 16 bits")),
+            (
+                "BB8V5",
+                StrError::new(3, "base45 final group of two characters overflows 8 bits"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(decode(t.0).as_ref(), Err(&t.1), "table entry: {}", i);
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use num_bigint::BigUint;
+use proc_macro2::TokenStream as TokenStream2;
+use sha2::{Digest, Sha256};
+use syn::{Error, LitStr};
+
+use crate::{emit, error::StrError};
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+pub fn base58(input: TokenStream2) -> TokenStream2 {
+    let lit = match syn::parse2::<LitStr>(input) {
+        Ok(lit) => lit,
+        Err(e) => return e.to_compile_error(),
+    };
+    match decode(&lit.value()) {
+        Ok(bytes) => emit::array(&bytes),
+        Err(e) => e.into_error(&lit).to_compile_error(),
+    }
+}
+
+pub fn base58check(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<LitStr>(input).and_then(|lit| expand_check(&lit)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand_check(lit: &LitStr) -> Result<TokenStream2, Error> {
+    let bytes = decode(&lit.value()).map_err(|e| e.into_error(lit))?;
+    if bytes.len() < 4 {
+        return Err(Error::new(
+            lit.span(),
+            "base58check value is too short to contain a checksum",
+        ));
+    }
+    let (payload, checksum) = bytes.split_at(bytes.len() - 4);
+    let expected = &Sha256::digest(Sha256::digest(payload))[..4];
+    if checksum != expected {
+        return Err(Error::new(
+            lit.span(),
+            format!(
+                "base58check checksum mismatch: expected {}, found {}",
+                hex(expected),
+                hex(checksum)
+            ),
+        ));
+    }
+    Ok(emit::array(payload))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decodes base58 using the Bitcoin alphabet. Each leading `1` character
+/// decodes to a leading zero byte.
+pub fn decode(s: &str) -> Result<Vec<u8>, StrError> {
+    let mut int = BigUint::default();
+    for (i, c) in s.bytes().enumerate() {
+        let v = ALPHABET
+            .iter()
+            .position(|a| *a == c)
+            .ok_or_else(|| StrError::new(i, format!("invalid base58 character {:?}", c as char)))?;
+        int = int * 58u32 + v;
+    }
+    let zeros = s.bytes().take_while(|c| *c == b'1').count();
+    let mut bytes = vec![0; zeros];
+    if int.bits() > 0 {
+        bytes.extend(int.to_bytes_be());
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{base58, base58check, decode};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn vectors() {
+        let table: &[(&str, &[u8])] = &[
+            ("", b""),
+            ("2g", b"a"),
+            ("a3gV", b"bbb"),
+            ("1", b"\0"),
+            ("111", b"\0\0\0"),
+            ("1112", b"\0\0\0\x01"),
+            ("StV1DL6CwTryKyV", b"hello world"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(decode(t.0).as_deref(), Ok(t.1), "table entry: {}", i);
+        }
+        assert_eq!(
+            decode("0OIl"),
+            Err(StrError::new(0, "invalid base58 character '0'"))
+        );
+    }
+
+    #[test]
+    fn base58_macro() {
+        let tokens = base58(quote! {"1112"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([0u8, 0u8, 0u8, 1u8]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn check() {
+        // The genesis block coinbase address.
+        let tokens = base58check(quote! {"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            0u8, 98u8, 233u8, 7u8, 177u8, 92u8, 191u8, 39u8, 213u8, 66u8, 83u8, 153u8, 235u8,
+            246u8, 240u8, 251u8, 80u8, 235u8, 184u8, 143u8, 24u8
+        ]);
+        assert_eq!(parsed, expect);
+
+        let tokens = base58check(quote! {"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb"});
+        let expect = Error::new(
+            Span::call_site(),
+            "base58check checksum mismatch: expected c29b7d93, found c29b7d94",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+
+        let tokens = base58check(quote! {"2g"});
+        let expect = Error::new(
+            Span::call_site(),
+            "base58check value is too short to contain a checksum",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
@@ -0,0 +1,167 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitStr,
+};
+
+use crate::{args::Args, emit, error::StrError};
+
+struct Input {
+    lit: LitStr,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn base64(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let alphabet = match input.args.ident("alphabet", &["standard", "url"])? {
+        Some(a) if a == "url" => Alphabet::Url,
+        _ => Alphabet::Standard,
+    };
+    input.args.finish()?;
+
+    let bytes = decode(&input.lit.value(), alphabet).map_err(|e| e.into_error(&input.lit))?;
+    Ok(emit::array(&bytes))
+}
+
+/// The base64 alphabets defined by RFC 4648.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alphabet {
+    Standard,
+    Url,
+}
+
+impl Alphabet {
+    fn value(self, c: u8) -> Option<u8> {
+        match (self, c) {
+            (_, b'A'..=b'Z') => Some(c - b'A'),
+            (_, b'a'..=b'z') => Some(c - b'a' + 26),
+            (_, b'0'..=b'9') => Some(c - b'0' + 52),
+            (Alphabet::Standard, b'+') | (Alphabet::Url, b'-') => Some(62),
+            (Alphabet::Standard, b'/') | (Alphabet::Url, b'_') => Some(63),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes base64, with or without padding. Padding, if present, must be
+/// complete.
+pub fn decode(s: &str, alphabet: Alphabet) -> Result<Vec<u8>, StrError> {
+    let input = s.as_bytes();
+    let data_len = input.len() - input.iter().rev().take_while(|c| **c == b'=').count();
+    let padding = input.len() - data_len;
+    if padding > 0 && (padding > 2 || input.len() % 4 != 0) {
+        return Err(StrError::new(data_len, "invalid padding"));
+    }
+
+    let mut bytes = Vec::with_capacity(data_len * 3 / 4);
+    let mut acc = 0u32;
+    let mut acc_bits = 0;
+    for (i, c) in input[..data_len].iter().enumerate() {
+        let v = alphabet.value(*c).ok_or_else(|| {
+            StrError::new(i, format!("invalid base64 character {:?}", *c as char))
+        })?;
+        acc = acc << 6 | u32::from(v);
+        acc_bits += 6;
+        if acc_bits >= 8 {
+            acc_bits -= 8;
+            bytes.push((acc >> acc_bits) as u8);
+            acc &= (1 << acc_bits) - 1;
+        }
+    }
+    if data_len % 4 == 1 {
+        return Err(StrError::new(data_len - 1, "invalid length"));
+    }
+    if acc != 0 {
+        return Err(StrError::new(data_len - 1, "non-zero trailing bits"));
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{base64, decode, Alphabet};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn rfc4648_vectors() {
+        let table: &[(&str, &[u8])] = &[
+            ("", b""),
+            ("Zg==", b"f"),
+            ("Zm8=", b"fo"),
+            ("Zm9v", b"foo"),
+            ("Zm9vYg==", b"foob"),
+            ("Zm9vYmE=", b"fooba"),
+            ("Zm9vYmFy", b"foobar"),
+            ("Zm9vYg", b"foob"),
+            ("Zm9vYmE", b"fooba"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                decode(t.0, Alphabet::Standard).as_deref(),
+                Ok(t.1),
+                "table entry: {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn alphabets() {
+        let tokens = base64(quote! {"3q2+7w=="});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([222u8, 173u8, 190u8, 239u8]);
+        assert_eq!(parsed, expect);
+
+        let tokens = base64(quote! {"3q2-7w", alphabet = url});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        assert_eq!(parsed, expect);
+
+        assert_eq!(
+            decode("3q2-7w==", Alphabet::Standard),
+            Err(StrError::new(3, "invalid base64 character '-'"))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(&str, StrError)] = &[
+            ("Zg=", StrError::new(2, "invalid padding")),
+            ("Zg===", StrError::new(2, "invalid padding")),
+            ("Zm9vY", StrError::new(4, "invalid length")),
+            ("Zh==", StrError::new(1, "non-zero trailing bits")),
+            ("Zm 9v", StrError::new(2, "invalid base64 character ' '")),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                decode(t.0, Alphabet::Standard).as_ref(),
+                Err(&t.1),
+                "table entry: {}",
+                i
+            );
+        }
+
+        let tokens = base64(quote! {"Zm9v!"});
+        let expect = Error::new(Span::call_site(), "invalid base64 character '!' at index 4")
+            .to_compile_error()
+            .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
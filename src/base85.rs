@@ -0,0 +1,227 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::LitStr;
+
+use crate::{emit, error::StrError};
+
+/// The Z85 alphabet of ZeroMQ.
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+pub fn ascii85(input: TokenStream2) -> TokenStream2 {
+    let lit = match syn::parse2::<LitStr>(input) {
+        Ok(lit) => lit,
+        Err(e) => return e.to_compile_error(),
+    };
+    match decode_ascii85(&lit.value()) {
+        Ok(bytes) => emit::array(&bytes),
+        Err(e) => e.into_error(&lit).to_compile_error(),
+    }
+}
+
+pub fn z85(input: TokenStream2) -> TokenStream2 {
+    let lit = match syn::parse2::<LitStr>(input) {
+        Ok(lit) => lit,
+        Err(e) => return e.to_compile_error(),
+    };
+    match decode_z85(&lit.value()) {
+        Ok(bytes) => emit::array(&bytes),
+        Err(e) => e.into_error(&lit).to_compile_error(),
+    }
+}
+
+/// Decodes Z85, where every five characters decode to four bytes, so the
+/// length must be a multiple of five.
+pub fn decode_z85(s: &str) -> Result<Vec<u8>, StrError> {
+    if s.len() % 5 != 0 {
+        return Err(StrError::new(
+            s.len(),
+            format!(
+                "invalid Z85 length, expected a multiple of 5 characters, found {}",
+                s.len()
+            ),
+        ));
+    }
+    let mut bytes = Vec::with_capacity(s.len() / 5 * 4);
+    let mut digits = Vec::with_capacity(5);
+    for (start, group) in s.as_bytes().chunks(5).enumerate() {
+        let start = start * 5;
+        digits.clear();
+        for (i, c) in group.iter().enumerate() {
+            let v = Z85_ALPHABET.iter().position(|a| a == c).ok_or_else(|| {
+                StrError::new(start + i, format!("invalid Z85 character {:?}", *c as char))
+            })?;
+            digits.push(v as u32);
+        }
+        bytes.extend(group_bytes(start, &digits)?);
+    }
+    Ok(bytes)
+}
+
+/// Decodes Ascii85, optionally framed by `<~` and `~>`. Whitespace is ignored,
+/// `z` outside of a group is four zero bytes, and a final group of n characters
+/// is padded with `u` and decodes to n - 1 bytes.
+pub fn decode_ascii85(s: &str) -> Result<Vec<u8>, StrError> {
+    let (offset, body) = match s.strip_prefix("<~") {
+        Some(rest) => match rest.strip_suffix("~>") {
+            Some(body) => (2, body),
+            None => return Err(StrError::new(s.len(), "missing closing `~>`")),
+        },
+        None => (0, s.strip_suffix("~>").unwrap_or(s)),
+    };
+
+    let mut bytes = Vec::new();
+    // The index of the first character of the group, and its digits.
+    let mut group: Option<usize> = None;
+    let mut digits = Vec::with_capacity(5);
+    for (i, c) in body.bytes().enumerate() {
+        let i = offset + i;
+        match c {
+            b' ' | b'\t' | b'\n' | b'\r' | b'\x0c' => {}
+            b'z' if digits.is_empty() => bytes.extend([0; 4]),
+            b'z' => return Err(StrError::new(i, "invalid ascii85 `z` inside a group")),
+            b'!'..=b'u' => {
+                let start = *group.get_or_insert(i);
+                digits.push(u32::from(c - b'!'));
+                if digits.len() == 5 {
+                    bytes.extend(group_bytes(start, &digits)?);
+                    digits.clear();
+                    group = None;
+                }
+            }
+            _ => {
+                return Err(StrError::new(
+                    i,
+                    format!("invalid ascii85 character {:?}", c as char),
+                ))
+            }
+        }
+    }
+
+    if let Some(start) = group {
+        if digits.len() == 1 {
+            return Err(StrError::new(
+                start,
+                "incomplete ascii85 group, a final group must have at least 2 characters",
+            ));
+        }
+        let n = digits.len() - 1;
+        digits.resize(5, 84);
+        bytes.extend(&group_bytes(start, &digits)?[..n]);
+    }
+    Ok(bytes)
+}
+
+/// Returns the four big endian bytes of a group of five base 85 digits.
+fn group_bytes(start: usize, digits: &[u32]) -> Result<[u8; 4], StrError> {
+    let value = digits
+        .iter()
+        .try_fold(0u32, |acc, &d| acc.checked_mul(85)?.checked_add(d))
+        .ok_or_else(|| StrError::new(start, "base85 group overflows 32 bits"))?;
+    Ok(value.to_be_bytes())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ascii85, decode_ascii85, decode_z85, z85};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray};
+
+    #[test]
+    fn ascii85_values() {
+        let table: &[(&str, &[u8])] = &[
+            ("", b""),
+            ("<~~>", b""),
+            ("87cURD_*#4DfTZ)", b"Hello, World"),
+            ("<~87cURD_*#4DfTZ)~>", b"Hello, World"),
+            ("87cURD_*#4DfTZ)~>", b"Hello, World"),
+            ("87cUR D_*#4\nDfTZ)", b"Hello, World"),
+            ("z", &[0, 0, 0, 0]),
+            ("zz!!", &[0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            ("s8W-!", &[0xff, 0xff, 0xff, 0xff]),
+            ("9jqo^", b"Man "),
+            ("9jqo", b"Man"),
+            ("9jn", b"Ma"),
+            ("9`", b"M"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(decode_ascii85(t.0), Ok(t.1.to_vec()), "table entry: {}", i);
+        }
+
+        let tokens = ascii85(quote!("<~9jqo^~>"));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([77u8, 97u8, 110u8, 32u8]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn ascii85_errors() {
+        let table: &[(&str, StrError)] = &[
+            ("<~9jqo^", StrError::new(7, "missing closing `~>`")),
+            ("9jqo^v", StrError::new(5, "invalid ascii85 character 'v'")),
+            (
+                "9jzo^",
+                StrError::new(2, "invalid ascii85 `z` inside a group"),
+            ),
+            (
+                "9jqo^s8W-\"",
+                StrError::new(5, "base85 group overflows 32 bits"),
+            ),
+            (
+                "9jqo^9",
+                StrError::new(
+                    5,
+                    "incomplete ascii85 group, a final group must have at least 2 characters",
+                ),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                decode_ascii85(t.0).as_ref(),
+                Err(&t.1),
+                "table entry: {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn z85_values() {
+        let table: &[(&str, &[u8])] = &[
+            ("", b""),
+            (
+                "HelloWorld",
+                &[0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b],
+            ),
+            ("00000", &[0, 0, 0, 0]),
+            ("%nSc0", &[0xff, 0xff, 0xff, 0xff]),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(decode_z85(t.0), Ok(t.1.to_vec()), "table entry: {}", i);
+        }
+
+        let tokens = z85(quote!("HelloWorld"));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([134u8, 79u8, 210u8, 111u8, 181u8, 89u8, 247u8, 91u8]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn z85_errors() {
+        let table: &[(&str, StrError)] = &[
+            (
+                "HelloWorl",
+                StrError::new(
+                    9,
+                    "invalid Z85 length, expected a multiple of 5 characters, found 9",
+                ),
+            ),
+            ("Hello,orld", StrError::new(5, "invalid Z85 character ','")),
+            ("%nSc1", StrError::new(0, "base85 group overflows 32 bits")),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(decode_z85(t.0).as_ref(), Err(&t.1), "table entry: {}", i);
+        }
+    }
+}
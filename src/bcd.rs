@@ -0,0 +1,135 @@
+use bytes_lit_core::Form;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitInt,
+};
+
+use crate::{args::Args, bytes::raw_digits, emit};
+
+struct Input {
+    lit: LitInt,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn bcd(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let unpacked = input.args.flag("unpacked")?;
+    let pad = input.args.ident("pad", &["left", "right"])?;
+    input.args.finish()?;
+
+    let raw = raw_digits(&input.lit);
+    if raw.digits.starts_with('-') {
+        return Err(Error::new(input.lit.span(), "negative values unsupported"));
+    }
+    if raw.form != Form::Decimal {
+        return Err(Error::new(
+            input.lit.span(),
+            format!(
+                "bcd requires a decimal integer literal, found {} form",
+                raw.form
+            ),
+        ));
+    }
+    // Digits of a decimal literal validated by syn are all ascii digits.
+    let digits: Vec<u8> = raw.digits.bytes().map(|d| d - b'0').collect();
+
+    if unpacked {
+        if let Some(pad) = pad {
+            return Err(Error::new(
+                pad.span(),
+                "option `pad` is not supported with `unpacked`",
+            ));
+        }
+        return Ok(emit::array(&digits));
+    }
+
+    // Packed digits are two per byte, so an odd number of digits is zero
+    // padded on the left by default.
+    let mut digits = digits;
+    if digits.len() % 2 == 1 {
+        match pad {
+            Some(pad) if pad == "right" => digits.push(0),
+            _ => digits.insert(0, 0),
+        }
+    }
+    let bytes: Vec<u8> = digits.chunks(2).map(|c| (c[0] << 4) | c[1]).collect();
+    Ok(emit::array(&bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::bcd;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn packed() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(1234), parse_quote!([18u8, 52u8])),
+            (quote!(0), parse_quote!([0u8])),
+            (quote!(99), parse_quote!([153u8])),
+            (quote!(123), parse_quote!([1u8, 35u8])),
+            (quote!(123, pad = left), parse_quote!([1u8, 35u8])),
+            (quote!(123, pad = right), parse_quote!([18u8, 48u8])),
+            (quote!(0012), parse_quote!([0u8, 18u8])),
+            (quote!(12_34), parse_quote!([18u8, 52u8])),
+            (quote!(1234u32), parse_quote!([18u8, 52u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = bcd(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn unpacked() {
+        let tokens = bcd(quote! {01234, unpacked});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([0u8, 1u8, 2u8, 3u8, 4u8]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(_, &str)] = &[
+            (quote!(-12), "negative values unsupported"),
+            (
+                quote!(0x1234),
+                "bcd requires a decimal integer literal, found hex form",
+            ),
+            (
+                quote!(12, unpacked, pad = left),
+                "option `pad` is not supported with `unpacked`",
+            ),
+            (
+                quote!(12, pad = center),
+                "option `pad` requires one of: left, right",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bcd(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
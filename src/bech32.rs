@@ -0,0 +1,331 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitStr,
+};
+
+use crate::{args::Args, emit, error::StrError};
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+struct Input {
+    lit: LitStr,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn bech32(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let expected_hrp = input.args.str("hrp")?;
+    let segwit = input.args.flag("segwit")?;
+    input.args.finish()?;
+
+    let lit = &input.lit;
+    let decoded = decode(&lit.value()).map_err(|e| e.into_error(lit))?;
+    if let Some(expected) = expected_hrp {
+        if decoded.hrp != expected.value().to_lowercase() {
+            return Err(Error::new(
+                lit.span(),
+                format!(
+                    "human-readable part {:?} does not match expected {:?}",
+                    decoded.hrp,
+                    expected.value()
+                ),
+            ));
+        }
+    }
+
+    let bytes = if segwit {
+        // The first group of a segwit address is the witness version, and the
+        // remaining groups are the witness program.
+        let (version, program) = decoded
+            .data
+            .split_first()
+            .ok_or_else(|| Error::new(lit.span(), "missing witness version"))?;
+        let expected_variant = if *version == 0 {
+            Variant::Bech32
+        } else {
+            Variant::Bech32m
+        };
+        if decoded.variant != expected_variant {
+            return Err(Error::new(
+                lit.span(),
+                format!(
+                    "witness version {} requires {} encoding",
+                    version,
+                    expected_variant.name()
+                ),
+            ));
+        }
+        convert_bits(program).map_err(|e| Error::new(lit.span(), e))?
+    } else {
+        convert_bits(&decoded.data).map_err(|e| Error::new(lit.span(), e))?
+    };
+    Ok(emit::array(&bytes))
+}
+
+/// The checksum variant of a bech32 string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Variant {
+    Bech32,
+    Bech32m,
+}
+
+impl Variant {
+    fn name(self) -> &'static str {
+        match self {
+            Variant::Bech32 => "bech32",
+            Variant::Bech32m => "bech32m",
+        }
+    }
+}
+
+/// A decoded bech32 string, with the human-readable part in lowercase and
+/// the data part as 5-bit groups excluding the checksum.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Decoded {
+    pub hrp: String,
+    pub data: Vec<u8>,
+    pub variant: Variant,
+}
+
+fn polymod(values: impl IntoIterator<Item = u8>) -> u32 {
+    const GEN: [u32; 5] = [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ];
+    let mut chk = 1u32;
+    for v in values {
+        let b = chk >> 25;
+        chk = (chk & 0x1ff_ffff) << 5 ^ u32::from(v);
+        for (i, g) in GEN.iter().enumerate() {
+            if (b >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+/// Decodes a bech32 or bech32m string, verifying the checksum.
+pub fn decode(s: &str) -> Result<Decoded, StrError> {
+    if let Some(i) = s.bytes().position(|c| !(33..=126).contains(&c)) {
+        return Err(StrError::new(i, "invalid character"));
+    }
+    // Find the first character with a case differing from the first cased
+    // character.
+    let upper = s
+        .bytes()
+        .find(u8::is_ascii_alphabetic)
+        .map(|c| c.is_ascii_uppercase());
+    if let Some(i) = s
+        .bytes()
+        .position(|c| c.is_ascii_alphabetic() && Some(c.is_ascii_uppercase()) != upper)
+    {
+        return Err(StrError::new(i, "mixed case"));
+    }
+    let s = s.to_ascii_lowercase();
+    let sep = s
+        .rfind('1')
+        .ok_or_else(|| StrError::new(0, "missing separator '1'"))?;
+    if sep == 0 {
+        return Err(StrError::new(0, "empty human-readable part"));
+    }
+    if s.len() - sep - 1 < 6 {
+        return Err(StrError::new(
+            sep,
+            "data part is too short to contain a checksum",
+        ));
+    }
+    let hrp = &s[..sep];
+    let data = s[sep + 1..]
+        .bytes()
+        .enumerate()
+        .map(|(i, c)| {
+            CHARSET
+                .iter()
+                .position(|a| *a == c)
+                .map(|v| v as u8)
+                .ok_or_else(|| {
+                    StrError::new(
+                        sep + 1 + i,
+                        format!("invalid bech32 character {:?}", c as char),
+                    )
+                })
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    let hrp_expanded = hrp
+        .bytes()
+        .map(|c| c >> 5)
+        .chain([0])
+        .chain(hrp.bytes().map(|c| c & 31));
+    let variant = match polymod(hrp_expanded.chain(data.iter().copied())) {
+        BECH32_CONST => Variant::Bech32,
+        BECH32M_CONST => Variant::Bech32m,
+        _ => return Err(StrError::new(sep + 1, "invalid checksum")),
+    };
+    Ok(Decoded {
+        hrp: hrp.to_string(),
+        data: data[..data.len() - 6].to_vec(),
+        variant,
+    })
+}
+
+/// Converts 5-bit groups into bytes. Any remaining bits must be padding of
+/// fewer than 5 zero bits.
+pub fn convert_bits(groups: &[u8]) -> Result<Vec<u8>, &'static str> {
+    let mut bytes = Vec::with_capacity(groups.len() * 5 / 8);
+    let mut acc = 0u32;
+    let mut acc_bits = 0;
+    for g in groups {
+        acc = acc << 5 | u32::from(*g);
+        acc_bits += 5;
+        if acc_bits >= 8 {
+            acc_bits -= 8;
+            bytes.push((acc >> acc_bits) as u8);
+            acc &= (1 << acc_bits) - 1;
+        }
+    }
+    if acc_bits >= 5 || acc != 0 {
+        return Err("invalid padding in data part");
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bech32, decode, Variant};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn checksums() {
+        let valid: &[(&str, Variant)] = &[
+            ("A12UEL5L", Variant::Bech32),
+            ("a12uel5l", Variant::Bech32),
+            (
+                "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+                Variant::Bech32,
+            ),
+            (
+                "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+                Variant::Bech32,
+            ),
+            ("A1LQFN3A", Variant::Bech32m),
+            (
+                "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+                Variant::Bech32m,
+            ),
+        ];
+        for (i, t) in valid.iter().enumerate() {
+            assert_eq!(
+                decode(t.0).map(|d| d.variant),
+                Ok(t.1),
+                "table entry: {}",
+                i
+            );
+        }
+
+        let invalid: &[(&str, StrError)] = &[
+            ("A12uEL5L", StrError::new(3, "mixed case")),
+            ("pzry9x0s0muk", StrError::new(0, "missing separator '1'")),
+            (
+                "1pzry9x0s0muk",
+                StrError::new(0, "empty human-readable part"),
+            ),
+            (
+                "x1b4n0q5v",
+                StrError::new(2, "invalid bech32 character 'b'"),
+            ),
+            (
+                "li1dgmt3",
+                StrError::new(2, "data part is too short to contain a checksum"),
+            ),
+            ("A12UEL5M", StrError::new(2, "invalid checksum")),
+        ];
+        for (i, t) in invalid.iter().enumerate() {
+            assert_eq!(
+                decode(t.0).as_ref().map(|_| ()),
+                Err(&t.1),
+                "table entry: {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn data() {
+        let tokens = bech32(quote! {"abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            0u8, 68u8, 50u8, 20u8, 199u8, 66u8, 84u8, 182u8, 53u8, 207u8, 132u8, 101u8, 58u8, 86u8,
+            215u8, 198u8, 117u8, 190u8, 119u8, 223u8
+        ]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn segwit() {
+        let tokens = bech32(quote! {
+            "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4", hrp = "bc", segwit
+        });
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            117u8, 30u8, 118u8, 232u8, 25u8, 145u8, 150u8, 212u8, 84u8, 148u8, 28u8, 69u8, 209u8,
+            179u8, 163u8, 35u8, 241u8, 67u8, 59u8, 214u8
+        ]);
+        assert_eq!(parsed, expect);
+
+        let tokens = bech32(quote! {
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0", segwit
+        });
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            121u8, 190u8, 102u8, 126u8, 249u8, 220u8, 187u8, 172u8, 85u8, 160u8, 98u8, 149u8,
+            206u8, 135u8, 11u8, 7u8, 2u8, 155u8, 252u8, 219u8, 45u8, 206u8, 40u8, 217u8, 89u8,
+            242u8, 129u8, 91u8, 22u8, 248u8, 23u8, 152u8
+        ]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn errors() {
+        let table = &[
+            (
+                quote!("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4", hrp = "tb"),
+                "human-readable part \"bc\" does not match expected \"tb\"",
+            ),
+            (quote!("A1LQFN3A", segwit), "missing witness version"),
+            (quote!("A12UEL5M"), "invalid checksum at index 2"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bech32(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
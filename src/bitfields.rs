@@ -0,0 +1,175 @@
+use std::str::FromStr;
+
+use num_bigint::BigUint;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitInt, Token,
+};
+
+use crate::{args::Args, bytes::unsigned, emit};
+
+/// Input to the bitfields macro: one or more comma separated fields, and then
+/// options.
+struct Input {
+    fields: Vec<Field>,
+    args: Args,
+}
+
+/// A field, `width => value`, of a value in a number of bits.
+struct Field {
+    width: LitInt,
+    value: LitInt,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut fields = vec![input.parse()?];
+        // Another field follows a comma when the next token is a literal,
+        // otherwise any comma starts the options.
+        while input.peek(Token![,]) && input.peek2(LitInt) {
+            input.parse::<Token![,]>()?;
+            fields.push(input.parse()?);
+        }
+        let args = Args::parse(input)?;
+        Ok(Self { fields, args })
+    }
+}
+
+impl Parse for Field {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let width = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let value = input.parse()?;
+        Ok(Self { width, value })
+    }
+}
+
+pub fn bitfields(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let lsb_first = input.args.flag("lsb_first")?;
+    let pad = input.args.flag("pad")?;
+    input.args.finish()?;
+
+    // The bits of the fields in order, each field with its most significant
+    // bit first, or its least significant bit first with lsb_first.
+    let mut bits = Vec::new();
+    for field in &input.fields {
+        let width = field.width.base10_parse::<usize>()?;
+        if width == 0 {
+            return Err(Error::new(
+                field.width.span(),
+                "width must be greater than zero",
+            ));
+        }
+        unsigned(&field.value)?;
+        let value = BigUint::from_str(field.value.base10_digits()).expect("valid integer");
+        let needed = value.bits() as usize;
+        if needed > width {
+            return Err(Error::new(
+                field.value.span(),
+                format!(
+                    "value needs {} bits, more than the width of {} bits",
+                    needed, width
+                ),
+            ));
+        }
+        let field_bits = (0..width as u64).map(|i| value.bit(i));
+        if lsb_first {
+            bits.extend(field_bits);
+        } else {
+            bits.extend(field_bits.rev());
+        }
+    }
+
+    if bits.len() % 8 != 0 {
+        if !pad {
+            return Err(Error::new(
+                Span::call_site(),
+                format!(
+                    "fields total {} bits, which is not a multiple of 8, use the `pad` option to zero pad the last byte",
+                    bits.len()
+                ),
+            ));
+        }
+        bits.resize(bits.len() + 8 - bits.len() % 8, false);
+    }
+
+    // The bits fill each byte from its most significant bit, or from its least
+    // significant bit with lsb_first.
+    let mut bytes = vec![0u8; bits.len() / 8];
+    for (i, bit) in bits.iter().enumerate() {
+        if *bit {
+            let shift = if lsb_first { i % 8 } else { 7 - i % 8 };
+            bytes[i / 8] |= 1 << shift;
+        }
+    }
+    Ok(emit::array(&bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::bitfields;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn fields() {
+        let table: &[(_, ExprArray)] = &[
+            (
+                quote!(4 => 0b1010, 6 => 0x2A, 6 => 0),
+                parse_quote!([170u8, 128u8]),
+            ),
+            (quote!(8 => 0xff), parse_quote!([255u8])),
+            (quote!(1 => 1, 7 => 0), parse_quote!([128u8])),
+            (quote!(16 => 0x1234), parse_quote!([18u8, 52u8])),
+            (quote!(4 => 0xf, pad), parse_quote!([240u8])),
+            // The first field is in the least significant bits.
+            (quote!(1 => 1, 7 => 0; lsb_first), parse_quote!([1u8])),
+            (
+                quote!(4 => 0b1010, 6 => 0x2A, 6 => 0, lsb_first),
+                parse_quote!([170u8, 2u8]),
+            ),
+            (quote!(3 => 0b101, lsb_first, pad), parse_quote!([5u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = bitfields(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table = &[
+            (
+                quote!(4 => 0x1f, 4 => 0),
+                "value needs 5 bits, more than the width of 4 bits",
+            ),
+            (
+                quote!(4 => 1, 6 => 0),
+                "fields total 10 bits, which is not a multiple of 8, use the `pad` option to zero pad the last byte",
+            ),
+            (quote!(0 => 0, 8 => 0), "width must be greater than zero"),
+            (
+                quote!(8 => -1),
+                "negative values unsupported, use ibytes! with `size = 1` for two's complement bytes",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bitfields(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitInt,
+};
+
+use crate::{args::Args, bytes::parse_int, emit};
+
+struct Input {
+    lit: LitInt,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn bits(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    input.args.requires("pad", "len")?;
+    let len = input.args.int::<usize>("len")?;
+    let pad = input.args.ident("pad", &["left", "right"])?;
+    let packed = input.args.flag("packed")?;
+    input.args.finish()?;
+
+    let int_lit = parse_int(&input.lit)?;
+    let total_bits = int_lit.total_bits();
+
+    // Output the bits most significant first, with the leading zero bits
+    // preceding the bits of the value.
+    let mut bits = (0..total_bits)
+        .rev()
+        .map(|i| int_lit.bit(i))
+        .collect::<Vec<_>>();
+
+    // The length zero pads the bits on the left by default, as leading zeros
+    // do, or on the right.
+    match len {
+        Some(0) => {
+            return Err(Error::new(
+                input.lit.span(),
+                "len must be greater than zero",
+            ))
+        }
+        Some(len) if total_bits > len => {
+            return Err(Error::new(
+                input.lit.span(),
+                format!(
+                    "value needs {} bits, more than the length of {} bits",
+                    total_bits, len
+                ),
+            ))
+        }
+        Some(len) => match pad {
+            Some(pad) if pad == "right" => bits.resize(len, false),
+            _ => {
+                bits.splice(0..0, vec![false; len - total_bits]);
+            }
+        },
+        None => {}
+    }
+
+    if packed {
+        return Ok(emit::array(&pack(&bits)));
+    }
+    Ok(quote! { [#(#bits),*] })
+}
+
+/// Packs bits into bytes, most significant first. The bits are aligned to the
+/// end of the bytes, so the first byte is partially filled when the number of
+/// bits is not a multiple of 8, as for the digits of hex and binary literals.
+fn pack(bits: &[bool]) -> Vec<u8> {
+    let mut bytes = vec![0u8; (bits.len() + 7) / 8];
+    let len = bytes.len();
+    for (i, bit) in bits.iter().rev().enumerate() {
+        if *bit {
+            bytes[len - 1 - i / 8] |= 1 << (i % 8);
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod test {
+    use super::bits;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn leading_zeros_preserved() {
+        let table: &[(_, ExprArray)] = &[
+            // Base 2.
+            (quote!(0b1), parse_quote!([true])),
+            (quote!(0b1011), parse_quote!([true, false, true, true])),
+            (quote!(0b0011), parse_quote!([false, false, true, true])),
+            (quote!(0b0), parse_quote!([false])),
+            (quote!(0b0_1), parse_quote!([false, true])),
+            // Base 16.
+            (quote!(0xA), parse_quote!([true, false, true, false])),
+            (quote!(0x1), parse_quote!([true])),
+            (
+                quote!(0x01),
+                parse_quote!([false, false, false, false, true]),
+            ),
+            // Base 8.
+            (quote!(0o5), parse_quote!([true, false, true])),
+            (
+                quote!(0o05),
+                parse_quote!([false, false, false, true, false, true]),
+            ),
+            // Base 10.
+            (quote!(6), parse_quote!([true, true, false])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = bits(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn lengths() {
+        let table: &[(_, ExprArray)] = &[
+            (
+                quote!(0b101, len = 6),
+                parse_quote!([false, false, false, true, false, true]),
+            ),
+            (
+                quote!(0b101, len = 6, pad = left),
+                parse_quote!([false, false, false, true, false, true]),
+            ),
+            (
+                quote!(0b101, len = 6, pad = right),
+                parse_quote!([true, false, true, false, false, false]),
+            ),
+            (quote!(0b101, len = 3), parse_quote!([true, false, true])),
+            (quote!(0b101, packed), parse_quote!([5u8])),
+            (quote!(0b101, len = 12, packed), parse_quote!([0u8, 5u8])),
+            (
+                quote!(0b101, len = 12, pad = right, packed),
+                parse_quote!([10u8, 0u8]),
+            ),
+            (quote!(0x0fff, packed), parse_quote!([15u8, 255u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = bits(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+
+        let table: &[(_, &str)] = &[
+            (
+                quote!(0b101, len = 2),
+                "value needs 3 bits, more than the length of 2 bits",
+            ),
+            (
+                quote!(0x01, len = 4),
+                "value needs 5 bits, more than the length of 4 bits",
+            ),
+            (quote!(0b1, len = 0), "len must be greater than zero"),
+            (
+                quote!(0b1, pad = left),
+                "option `pad` requires the `len` option",
+            ),
+            (
+                quote!(0b1, len = 4, pad = up),
+                "option `pad` requires one of: left, right",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bits(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn leading_zeros_unsupported() {
+        let tokens = bits(quote! {007});
+        let expect = Error::new(
+            Span::call_site(),
+            "leading zeros are not preserved or supported on integer literals in decimal form, write the value in hex as `0x07`, where leading zero digits are preserved, or remove the zeros",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
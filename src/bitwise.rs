@@ -0,0 +1,175 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, Lit, Token,
+};
+
+use crate::{args::Args, bytes::lit_bytes, emit};
+
+/// Input to the bitwise macros: two literals separated by a comma, and then
+/// options.
+struct Input {
+    a: Lit,
+    b: Lit,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let a = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let b = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { a, b, args })
+    }
+}
+
+pub fn xor(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(|input| expand(input, |a, b| a ^ b)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn and(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(|input| expand(input, |a, b| a & b)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn or(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(|input| expand(input, |a, b| a | b)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn not(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Lit>(input).and_then(|lit| lit_bytes(&lit)) {
+        Ok(bytes) => {
+            let bytes: Vec<u8> = bytes.iter().map(|b| !b).collect();
+            emit::array(&bytes)
+        }
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Expands to the bytes of the two literals combined byte by byte with the
+/// operation.
+fn expand(mut input: Input, op: fn(u8, u8) -> u8) -> Result<TokenStream2, Error> {
+    let pad = input.args.ident("pad", &["left", "right"])?;
+    input.args.finish()?;
+
+    let mut a = lit_bytes(&input.a)?;
+    let mut b = lit_bytes(&input.b)?;
+    if a.len() != b.len() {
+        // Zero pad the shorter literal, on the left by default so that integer
+        // literals are aligned at their least significant byte.
+        let pad = match pad {
+            Some(pad) => pad,
+            None => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    format!(
+                        "literals differ in length, {} byte(s) and {} byte(s), use the `pad` option to zero pad the shorter",
+                        a.len(),
+                        b.len()
+                    ),
+                ))
+            }
+        };
+        let len = a.len().max(b.len());
+        for bytes in [&mut a, &mut b] {
+            let zeros = vec![0; len - bytes.len()];
+            if pad == "right" {
+                bytes.extend(zeros);
+            } else {
+                bytes.splice(0..0, zeros);
+            }
+        }
+    }
+
+    let bytes: Vec<u8> = a.iter().zip(&b).map(|(a, b)| op(*a, *b)).collect();
+    Ok(emit::array(&bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{and, not, or, xor};
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn combine() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(0xFF00, 0x0FF0), parse_quote!([240u8, 240u8])),
+            (
+                quote!("deadbeef", 0xdeadbeef),
+                parse_quote!([0u8, 0u8, 0u8, 0u8]),
+            ),
+            (quote!(0xFF00, 0x01, pad = left), parse_quote!([255u8, 1u8])),
+            (
+                quote!(0xFF00, 0x01, pad = right),
+                parse_quote!([254u8, 0u8]),
+            ),
+            (
+                quote!(0x01, 0xFF00; pad = right),
+                parse_quote!([254u8, 0u8]),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let parsed = syn::parse2::<ExprArray>(xor(t.0.clone())).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn and_or_not() {
+        let table: &[(_, ExprArray)] = &[
+            (and(quote!(0xFF00, 0x0FF0)), parse_quote!([15u8, 0u8])),
+            (or(quote!(0xFF00, 0x00F0)), parse_quote!([255u8, 240u8])),
+            (
+                or(quote!(0xFF00, 0xF0, pad = left)),
+                parse_quote!([255u8, 240u8]),
+            ),
+            (not(quote!(0xFF00)), parse_quote!([0u8, 255u8])),
+            (not(quote!("0f")), parse_quote!([240u8])),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let parsed = syn::parse2::<ExprArray>(t.0.clone()).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+
+        let expect = Error::new(
+            Span::call_site(),
+            "literals differ in length, 1 byte(s) and 2 byte(s), use the `pad` option to zero pad the shorter",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(and(quote!(0xFF, 0x0F00)).to_string(), expect);
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(_, &str)] = &[
+            (
+                quote!(0xFF00, 0x01),
+                "literals differ in length, 2 byte(s) and 1 byte(s), use the `pad` option to zero pad the shorter",
+            ),
+            (
+                quote!(0xFF, 0x01, pad = middle),
+                "option `pad` requires one of: left, right",
+            ),
+            (quote!(0xFF, b"a"), "expected an integer or string literal"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(xor(t.0.clone()).to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
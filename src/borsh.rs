@@ -0,0 +1,75 @@
+use std::convert::TryFrom;
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::Error;
+
+use crate::{bytes, diagnostic, emit};
+
+pub fn borsh_bytes(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<bytes::Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Expands to the Borsh encoding of the bytes: as a `Vec<u8>`, prefixed by
+/// their length as a little endian u32, or with the fixed flag as a `[u8; N]`,
+/// which Borsh encodes as the bytes alone.
+fn expand(mut input: bytes::Input) -> Result<TokenStream2, Error> {
+    let fixed = input.args.flag("fixed")?;
+    let bytes::Output { bytes, warnings } = bytes::value(input)?;
+    if fixed {
+        return Ok(diagnostic::with_warnings(&warnings, emit::array(&bytes)));
+    }
+    let len = u32::try_from(bytes.len()).map_err(|_| {
+        Error::new(
+            Span::call_site(),
+            format!(
+                "{} bytes exceed the maximum length of a Borsh vector",
+                bytes.len()
+            ),
+        )
+    })?;
+    let mut encoded = len.to_le_bytes().to_vec();
+    encoded.extend(bytes);
+    Ok(diagnostic::with_warnings(&warnings, emit::array(&encoded)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::borsh_bytes;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn encoding() {
+        let table: &[(_, ExprArray)] = &[
+            (
+                quote!(0xdeadbeef),
+                parse_quote!([4u8, 0u8, 0u8, 0u8, 222u8, 173u8, 190u8, 239u8]),
+            ),
+            (quote!(b""), parse_quote!([0u8, 0u8, 0u8, 0u8])),
+            (
+                quote!(0xdeadbeef, fixed),
+                parse_quote!([222u8, 173u8, 190u8, 239u8]),
+            ),
+            (quote!(0x01, size = 2, fixed), parse_quote!([0u8, 1u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = borsh_bytes(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+
+        let tokens = borsh_bytes(quote!(0x01, fixed = 1));
+        let expect = Error::new(
+            Span::call_site(),
+            "option `fixed` is a flag and does not take a value",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
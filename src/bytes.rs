@@ -1,116 +1,2285 @@
-use std::{convert::TryInto, str::FromStr};
+use std::str::FromStr;
 
+use bytes_lit_core::{Form, ParseError};
 use num_bigint::BigUint;
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Delimiter, Literal, Span, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{Error, LitInt};
+use sha2::{Digest, Sha256};
+use syn::{
+    parse::{Parse, ParseStream},
+    Attribute, Error, Expr, ExprLit, Lit, LitByteStr, LitInt, LitStr, Token, Type, TypeArray,
+};
+
+use crate::{
+    args::Args,
+    crc,
+    diagnostic::{self, Warning},
+    eager, emit, expr, hex, suggest, xdr,
+};
+
+/// The maximum number of bytes that options and repeat counts may make the
+/// bytes, so that a mistyped length errors rather than exhausting the memory
+/// of the compiler.
+const MAX_LEN: usize = 1 << 24;
+
+/// Input to the bytes macro: one or more comma separated pieces that are
+/// concatenated, an optional type ascription, an optional output type, and
+/// then options.
+pub struct Input {
+    pieces: Vec<Piece>,
+    ascription: Option<Ascription>,
+    conversion: Option<xdr::Conversion>,
+    /// The options, which macros that take the input of the bytes macro may
+    /// take options of their own from before the bytes are converted.
+    pub args: Args,
+}
+
+/// A type ascription, `=> [u8; N]`, stating the length of the bytes.
+struct Ascription {
+    ty: TypeArray,
+    len: usize,
+}
+
+/// A piece of the input: an integer literal, a hex string literal, a byte
+/// string literal, a range of byte values, or an integer expression, optionally
+/// followed by a semicolon and a count of times to repeat the bytes. Pieces may
+/// be preceded by doc attributes, or doc comments, that label them and are
+/// ignored.
+struct Piece {
+    value: Value,
+    repeat: Option<LitInt>,
+}
+
+enum Value {
+    Expr(Expr),
+    Digits(Digits),
+    Int(LitInt),
+    Str(Vec<LitStr>),
+    ByteStr(LitByteStr),
+    Range {
+        start: LitInt,
+        end: LitInt,
+        inclusive: bool,
+    },
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut pieces = vec![input.parse()?];
+        // Another piece follows a comma when the next token starts a piece,
+        // otherwise any comma starts the options.
+        while peek_piece(input) {
+            input.parse::<Token![,]>()?;
+            pieces.push(input.parse()?);
+        }
+        let ascription = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        parse_labels(input)?;
+        // An output type, `as Type`, is the first of the options.
+        let conversion =
+            if (input.peek(Token![;]) || input.peek(Token![,])) && input.peek2(Token![as]) {
+                if input.peek(Token![;]) {
+                    input.parse::<Token![;]>()?;
+                } else {
+                    input.parse::<Token![,]>()?;
+                }
+                input.parse::<Token![as]>()?;
+                Some(input.parse()?)
+            } else {
+                None
+            };
+        let args = Args::parse(input)?;
+        Ok(Self {
+            pieces,
+            ascription,
+            conversion,
+            args,
+        })
+    }
+}
+
+impl Parse for Ascription {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty: Type = input.parse()?;
+        let invalid = || {
+            Error::new_spanned(
+                &ty,
+                "type ascription requires an array of bytes, such as `[u8; 4]`",
+            )
+        };
+        let array = match &ty {
+            Type::Array(array) => array,
+            _ => return Err(invalid()),
+        };
+        let len = match (&*array.elem, &array.len) {
+            (
+                Type::Path(elem),
+                Expr::Lit(ExprLit {
+                    lit: Lit::Int(len), ..
+                }),
+            ) if elem.qself.is_none() && elem.path.is_ident("u8") => len.base10_parse()?,
+            _ => return Err(invalid()),
+        };
+        Ok(Self {
+            ty: array.clone(),
+            len,
+        })
+    }
+}
+
+impl Parse for Piece {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        parse_labels(input)?;
+        let value = input.parse()?;
+        let repeat = parse_repeat(input)?;
+        Ok(Self { value, repeat })
+    }
+}
+
+impl Parse for Value {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if expr::peek(input) {
+            return Ok(Value::Expr(input.parse()?));
+        }
+        if let Some(digits) = Digits::parse(input)? {
+            return Ok(Value::Digits(digits));
+        }
+        if input.peek(LitStr) || eager::peek(input.cursor()) {
+            // Adjacent string literals are concatenated, so that long values
+            // can be split across lines.
+            let mut lits = vec![parse_str(input)?];
+            while input.peek(LitStr) || eager::peek(input.cursor()) {
+                lits.push(parse_str(input)?);
+            }
+            return Ok(Value::Str(lits));
+        }
+        if input.peek(LitByteStr) {
+            return Ok(Value::ByteStr(input.parse()?));
+        }
+        let start = input.parse()?;
+        if input.peek(Token![..=]) {
+            input.parse::<Token![..=]>()?;
+            let end = input.parse()?;
+            Ok(Value::Range {
+                start,
+                end,
+                inclusive: true,
+            })
+        } else if input.peek(Token![..]) {
+            input.parse::<Token![..]>()?;
+            let end = input.parse()?;
+            Ok(Value::Range {
+                start,
+                end,
+                inclusive: false,
+            })
+        } else {
+            Ok(Value::Int(start))
+        }
+    }
+}
+
+/// A hex or binary integer literal without a suffix, parsed from the token
+/// directly rather than as a syn::LitInt. Syn converts integer literals to
+/// base10 digits, which takes time quadratic in the length of the literal and
+/// is slow for the very long literals of embedded images.
+struct Digits {
+    span: Span,
+    literal: bytes_lit_core::Literal,
+}
+
+impl Digits {
+    /// Parses the next token if it is a hex or binary integer literal without
+    /// a suffix that does not start a range. Any other token is left to be
+    /// parsed as a syn::LitInt.
+    fn parse(input: ParseStream) -> syn::Result<Option<Self>> {
+        input.step(|cursor| {
+            let (lit, rest) = match cursor.literal() {
+                Some(next) => next,
+                None => return Ok((None, *cursor)),
+            };
+            if let Some((punct, _)) = rest.punct() {
+                if punct.as_char() == '.' {
+                    return Ok((None, *cursor));
+                }
+            }
+            let raw = lit.to_string();
+            let (form, body) = if let Some(body) = raw.strip_prefix("0x") {
+                (Form::Hex, body)
+            } else if let Some(body) = raw.strip_prefix("0b") {
+                (Form::Binary, body)
+            } else {
+                return Ok((None, *cursor));
+            };
+            let valid = |c: char| c == '_' || c.is_digit(form.radix());
+            if !body.chars().all(valid) || !body.chars().any(|c| c != '_') {
+                return Ok((None, *cursor));
+            }
+            let digits = body.replace('_', "");
+            Ok((
+                Some(Digits {
+                    span: lit.span(),
+                    literal: bytes_lit_core::Literal { form, digits },
+                }),
+                rest,
+            ))
+        })
+    }
+}
+
+/// Returns true if the next token is a comma followed by the start of another
+/// piece: a literal, a minus sign, a label, or a call of a builtin macro. The
+/// tokens are inspected without parsing the literal, which is slow for very
+/// long integer literals.
+fn peek_piece(input: ParseStream) -> bool {
+    match input.cursor().punct() {
+        Some((comma, rest)) if comma.as_char() == ',' => {
+            rest.literal().is_some()
+                || rest.group(Delimiter::Parenthesis).is_some()
+                || rest
+                    .punct()
+                    .map_or(false, |(punct, _)| matches!(punct.as_char(), '-' | '#'))
+                || eager::peek(rest)
+        }
+        _ => false,
+    }
+}
+
+/// Parses a string literal, or a call of a builtin macro that expands to one.
+fn parse_str(input: ParseStream) -> syn::Result<LitStr> {
+    if eager::peek(input.cursor()) {
+        eager::parse(input)
+    } else {
+        input.parse()
+    }
+}
+
+/// Parses and discards any doc attributes, which label the pieces of the
+/// input. Doc comments are doc attributes by the time the macro sees them.
+fn parse_labels(input: ParseStream) -> syn::Result<()> {
+    for attr in input.call(Attribute::parse_outer)? {
+        if !attr.path().is_ident("doc") {
+            return Err(Error::new_spanned(
+                attr,
+                "only doc attributes are accepted as labels",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn parse_repeat(input: ParseStream) -> syn::Result<Option<LitInt>> {
+    if input.peek(Token![;]) && input.peek2(LitInt) {
+        input.parse::<Token![;]>()?;
+        Ok(Some(input.parse()?))
+    } else {
+        Ok(None)
+    }
+}
 
 pub fn bytes(input: TokenStream2) -> TokenStream2 {
-    let lit = match syn::parse2::<LitInt>(input) {
-        Ok(lit) => lit,
-        Err(e) => return e.to_compile_error(),
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    input.args.requires("chunk_pad", "chunk")?;
+    let chunk = input.args.int::<usize>("chunk")?;
+    if let (Some(ascription), Some(_)) = (&input.ascription, chunk) {
+        return Err(Error::new_spanned(
+            &ascription.ty,
+            "option `chunk` cannot be used with a type ascription",
+        ));
+    }
+    let chunk_pad = input.args.flag("chunk_pad")?;
+    let byte_str = input.args.flag("byte_str")?;
+    let emit_array = if byte_str {
+        emit::byte_string
+    } else {
+        emit::array
+    };
+    if let Some(conversion) = input.conversion.take() {
+        if chunk.is_some() || byte_str {
+            return Err(Error::new(
+                Span::call_site(),
+                format!(
+                    "option `{}` cannot be used with an output type",
+                    if byte_str { "byte_str" } else { "chunk" }
+                ),
+            ));
+        }
+        let Output { bytes, warnings } = value(input)?;
+        return Ok(diagnostic::with_warnings(
+            &warnings,
+            conversion.expand(&bytes)?,
+        ));
+    }
+    let Output {
+        mut bytes,
+        warnings,
+    } = value(input)?;
+
+    let size = match chunk {
+        Some(0) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "chunk size must be greater than zero",
+            ))
+        }
+        Some(size) => size,
+        None => return Ok(diagnostic::with_warnings(&warnings, emit_array(&bytes))),
+    };
+    if bytes.len() % size != 0 {
+        if !chunk_pad {
+            return Err(Error::new(
+                Span::call_site(),
+                format!(
+                    "{} byte(s) is not a multiple of the chunk size of {} bytes, use the `chunk_pad` option to zero pad",
+                    bytes.len(),
+                    size
+                ),
+            ));
+        }
+        bytes.resize(bytes.len() + size - bytes.len() % size, 0);
+    }
+    let chunks = emit::chunks(&bytes, size, emit_array);
+    Ok(diagnostic::with_warnings(&warnings, chunks))
+}
+
+pub fn bytes_ref(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(value) {
+        Ok(Output { bytes, warnings }) => {
+            let array = emit::array(&bytes);
+            let warnings = diagnostic::warnings(&warnings);
+            quote! {{
+                #warnings
+                const BYTES: &[u8] = &#array;
+                BYTES
+            }}
+        }
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn bytesvec(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(value) {
+        Ok(Output { bytes, warnings }) => {
+            let array = emit::array(&bytes);
+            // With the alloc feature the Vec is referenced through the alloc
+            // crate, for no_std crates that declare `extern crate alloc`.
+            let krate = if cfg!(feature = "alloc") {
+                quote!(alloc)
+            } else {
+                quote!(std)
+            };
+            let vec = quote! { ::#krate::vec::Vec::<u8>::from(#array) };
+            diagnostic::with_warnings(&warnings, vec)
+        }
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+#[cfg(feature = "bytes")]
+pub fn bytes_buf(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(value) {
+        Ok(Output { bytes, warnings }) => {
+            let len = bytes.len();
+            let array = emit::array(&bytes);
+            let warnings = diagnostic::warnings(&warnings);
+            quote! {{
+                #warnings
+                static BYTES: [u8; #len] = #array;
+                ::bytes::Bytes::from_static(&BYTES)
+            }}
+        }
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn bytes_pat(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(value) {
+        Ok(Output { bytes, warnings }) => {
+            // A pattern cannot contain the items that emit warnings, so the
+            // warnings are errors.
+            let mut errors = warnings.iter().map(|w| Error::new(w.span, &w.message));
+            match errors.next() {
+                Some(mut error) => {
+                    error.extend(errors);
+                    error.to_compile_error()
+                }
+                None => emit::elements(&bytes),
+            }
+        }
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn bytes_len(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(value) {
+        Ok(Output { bytes, warnings }) => {
+            let len = Literal::usize_suffixed(bytes.len());
+            diagnostic::with_warnings(&warnings, quote!(#len))
+        }
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn hex_str(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand_hex_str) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Expands to the lower case hex string of the bytes, with an optional `0x`
+/// prefix, and groups of bytes separated by a space or the separator given.
+fn expand_hex_str(mut input: Input) -> Result<TokenStream2, Error> {
+    input.args.requires("separator", "group")?;
+    let prefix = input.args.flag("prefix")?;
+    let group_span = input.args.span("group");
+    let group = input.args.int::<usize>("group")?;
+    let separator = match input.args.str("separator")? {
+        Some(separator) => separator.value(),
+        None => " ".to_string(),
+    };
+    let Output { bytes, warnings } = value(input)?;
+
+    let groups = match group {
+        Some(0) => {
+            return Err(Error::new(
+                group_span,
+                "group size must be greater than zero",
+            ))
+        }
+        Some(size) => bytes.chunks(size).map(hex::encode).collect(),
+        None => vec![hex::encode(&bytes)],
+    };
+    let mut s = groups.join(&separator);
+    if prefix {
+        s.insert_str(0, "0x");
+    }
+    Ok(diagnostic::with_warnings(&warnings, quote!(#s)))
+}
+
+/// The bytes of the input to the bytes macro, and any warnings about the
+/// input to emit with them.
+pub struct Output {
+    pub bytes: Vec<u8>,
+    pub warnings: Vec<Warning>,
+}
+
+/// Converts the input of the bytes macro into its bytes.
+pub fn value(mut input: Input) -> Result<Output, Error> {
+    if let Some(conversion) = &input.conversion {
+        return Err(Error::new(
+            conversion.span(),
+            "an output type, `as Type`, is only supported by bytes!",
+        ));
+    }
+    input.args.exclusive("strict", "allow_odd")?;
+    input.args.exclusive("size", "pad_right")?;
+    if input.ascription.is_none() {
+        input.args.requires("truncate", "size")?;
+    }
+    let append = match input.args.take_ident("append")? {
+        Some(name) => Some(crc::Algorithm::from_name(&name)?),
+        None => None,
+    };
+    let strict = input.args.flag("strict")?;
+    let allow_odd = input.args.flag("allow_odd")?;
+    // The errors about the values of options are at the option.
+    let size_span = input.args.span("size");
+    let size = input.args.int::<usize>("size")?;
+    let pad_right_span = input.args.span("pad_right");
+    let pad_right = input.args.int::<usize>("pad_right")?;
+    let truncate = input
+        .args
+        .flag_or_ident("truncate", "leading", &["leading", "trailing"])?;
+    let reverse = input.args.flag("reverse")?;
+    let swap_span = input.args.span("swap");
+    let swap = input.args.int::<usize>("swap")?;
+    let range_span = input.args.span("range");
+    let range = input.args.range("range")?;
+    let bitreflect = input.args.flag("bitreflect")?;
+    let align_span = input.args.span("align");
+    let align = input.args.int::<usize>("align")?;
+    let pad_span = input.args.span("pad");
+    let pad = input.args.flag("pad")?;
+    let sha256 = input.args.lit("sha256")?;
+    input.args.finish()?;
+    if pad && input.ascription.is_none() {
+        return Err(Error::new(
+            pad_span,
+            "option `pad` requires a type ascription, such as `=> [u8; 4]`",
+        ));
+    }
+    let partial = if strict {
+        PartialBytes::Deny
+    } else if allow_odd {
+        PartialBytes::Allow
+    } else {
+        PartialBytes::Warn
+    };
+
+    let mut bytes = Vec::new();
+    let mut warnings = Vec::new();
+    for piece in &input.pieces {
+        bytes.extend(piece_bytes(piece, partial, size.is_some(), &mut warnings)?);
+    }
+
+    match size {
+        Some(0) => {
+            return Err(Error::new(
+                size_span,
+                "size must be greater than zero",
+            ))
+        }
+        Some(size) if size > MAX_LEN => {
+            return Err(Error::new(
+                size_span,
+                format!(
+                    "size of {} byte(s) is larger than the maximum of {} bytes",
+                    size, MAX_LEN
+                ),
+            ))
+        }
+        // Keep the leading bytes, such as for a short identifier from the
+        // prefix of a hash, or the trailing bytes, like integer truncation.
+        Some(size) if bytes.len() > size && truncate.is_some() => {
+            if truncate.as_deref() == Some("trailing") {
+                bytes.drain(..bytes.len() - size);
+            } else {
+                bytes.truncate(size);
+            }
+        }
+        Some(size) if bytes.len() > size => {
+            return Err(Error::new(
+                size_span,
+                format!(
+                    "{} byte(s) do not fit in the size of {} byte(s), use the `truncate` option to keep only {} byte(s)",
+                    bytes.len(),
+                    size,
+                    size
+                ),
+            ))
+        }
+        // Zero pad on the left, like the leading zeros of an integer.
+        Some(size) => {
+            bytes.splice(0..0, vec![0; size - bytes.len()]);
+        }
+        None => {}
+    }
+
+    match pad_right {
+        Some(0) => {
+            return Err(Error::new(
+                pad_right_span,
+                "pad_right must be greater than zero",
+            ))
+        }
+        Some(pad_right) if pad_right > MAX_LEN => {
+            return Err(Error::new(
+                pad_right_span,
+                format!(
+                    "pad_right of {} byte(s) is larger than the maximum of {} bytes",
+                    pad_right, MAX_LEN
+                ),
+            ))
+        }
+        Some(pad_right) if bytes.len() > pad_right => {
+            return Err(Error::new(
+                pad_right_span,
+                format!(
+                    "{} byte(s) do not fit in the pad_right of {} byte(s)",
+                    bytes.len(),
+                    pad_right
+                ),
+            ))
+        }
+        // Zero pad on the right, like the padding of a fixed size text field.
+        Some(pad_right) => bytes.resize(pad_right, 0),
+        None => {}
+    }
+
+    if let Some(algorithm) = append {
+        let crc = algorithm.checksum_bytes(&bytes);
+        bytes.extend(crc);
+    }
+
+    if reverse {
+        bytes.reverse();
+    }
+    // Reverse the order of the bytes of each word, such as to write the little
+    // endian words of a register dump in big endian order.
+    match swap {
+        Some(0) => return Err(Error::new(swap_span, "swap must be greater than zero")),
+        Some(swap) if bytes.len() % swap != 0 => {
+            return Err(Error::new(
+                swap_span,
+                format!(
+                    "{} byte(s) is not a multiple of the swap size of {} bytes",
+                    bytes.len(),
+                    swap
+                ),
+            ))
+        }
+        Some(swap) => {
+            for word in bytes.chunks_mut(swap) {
+                word.reverse();
+            }
+        }
+        None => {}
+    }
+    if bitreflect {
+        for b in &mut bytes {
+            *b = b.reverse_bits();
+        }
+    }
+
+    if let Some((start, end)) = range {
+        let end = end.unwrap_or(bytes.len());
+        if start > end {
+            return Err(Error::new(
+                range_span,
+                "range start must not be greater than range end",
+            ));
+        }
+        if end > bytes.len() {
+            return Err(Error::new(
+                range_span,
+                format!(
+                    "range {}..{} is out of bounds of the {} byte(s)",
+                    start,
+                    end,
+                    bytes.len()
+                ),
+            ));
+        }
+        bytes = bytes[start..end].to_vec();
+    }
+
+    match align {
+        Some(0) => return Err(Error::new(align_span, "align must be greater than zero")),
+        Some(align) if align > MAX_LEN => {
+            return Err(Error::new(
+                align_span,
+                format!(
+                    "align of {} byte(s) is larger than the maximum of {} bytes",
+                    align, MAX_LEN
+                ),
+            ))
+        }
+        // Zero pad on the right to the next multiple, like XDR padding.
+        Some(align) => {
+            let len = (bytes.len() + align - 1) / align * align;
+            bytes.resize(len, 0);
+        }
+        None => {}
+    }
+
+    // The ascribed length applies to the bytes after all other options, and
+    // the bytes are only padded or truncated to it when asked.
+    if let Some(Ascription { ty, len }) = &input.ascription {
+        let len = *len;
+        if bytes.len() < len && pad {
+            bytes.splice(0..0, vec![0; len - bytes.len()]);
+        } else if bytes.len() > len && truncate.as_deref() == Some("trailing") {
+            bytes.drain(..bytes.len() - len);
+        } else if bytes.len() > len && truncate.is_some() {
+            bytes.truncate(len);
+        }
+        if bytes.len() != len {
+            let fix = if bytes.len() < len {
+                "use the `pad` option to zero pad".to_string()
+            } else {
+                format!("use the `truncate` option to keep only {} byte(s)", len)
+            };
+            return Err(Error::new_spanned(
+                ty,
+                format!(
+                    "{} byte(s) do not match the ascribed type `[u8; {}]`, {}",
+                    bytes.len(),
+                    len,
+                    fix
+                ),
+            ));
+        }
+    }
+
+    if let Some(expected) = sha256 {
+        verify_sha256(&bytes, &expected)?;
+    }
+
+    Ok(Output { bytes, warnings })
+}
+
+/// Errors if the SHA-256 digest of the bytes is not the expected digest, such
+/// as when a large embedded blob has been corrupted or replaced.
+fn verify_sha256(bytes: &[u8], expected: &Lit) -> Result<(), Error> {
+    let expected_digest = lit_bytes(expected)?;
+    if expected_digest.len() != 32 {
+        return Err(Error::new(
+            expected.span(),
+            format!(
+                "sha256 digest must be 32 bytes, found {} byte(s)",
+                expected_digest.len()
+            ),
+        ));
+    }
+    let digest = Sha256::digest(bytes);
+    if digest[..] != expected_digest[..] {
+        return Err(Error::new(
+            expected.span(),
+            format!(
+                "sha256 digest of the bytes does not match, expected 0x{}, found 0x{}",
+                hex::encode(&expected_digest),
+                hex::encode(&digest)
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// How to treat hex and binary integer literals whose digits do not fill whole
+/// bytes, such as `0xFFF`, which leave the first byte partially filled.
+#[derive(Clone, Copy)]
+enum PartialBytes {
+    Warn,
+    Deny,
+    Allow,
+}
+
+/// Checks that the digits of a hex or binary integer literal fill whole bytes,
+/// erroring or warning if they do not according to the policy.
+fn check_whole_bytes(
+    span: Span,
+    digit_count: usize,
+    bits_per_digit: usize,
+    partial: PartialBytes,
+    warnings: &mut Vec<Warning>,
+) -> Result<(), Error> {
+    let leading_bits = digit_count * bits_per_digit % 8;
+    if leading_bits == 0 {
+        return Ok(());
+    }
+    let problem = if bits_per_digit == 4 {
+        "odd number of hex digits, the first byte holds a single digit".to_string()
+    } else {
+        format!(
+            "{} binary digits is not a multiple of 8, the first byte holds {} digit(s)",
+            digit_count, leading_bits
+        )
+    };
+    match partial {
+        PartialBytes::Allow => {}
+        PartialBytes::Deny => {
+            return Err(Error::new(
+                span,
+                format!("{}, add leading zeros to fill the byte", problem),
+            ))
+        }
+        PartialBytes::Warn => warnings.push(Warning::new(
+            span,
+            format!(
+                "{}, add leading zeros to fill the byte or use the `allow_odd` option",
+                problem
+            ),
+        )),
+    }
+    Ok(())
+}
+
+/// Converts a piece of the input into bytes, repeating them if the piece has
+/// a repeat count. If sized, the size option gives the bytes a fixed width,
+/// so a decimal or octal zero is unambiguous.
+fn piece_bytes(
+    piece: &Piece,
+    partial: PartialBytes,
+    sized: bool,
+    warnings: &mut Vec<Warning>,
+) -> Result<Vec<u8>, Error> {
+    let bytes = match &piece.value {
+        Value::Expr(e) => expr::eval(e)?.to_bytes_be(),
+        Value::Digits(Digits { span, literal }) => {
+            let bits_per_digit = literal.form.bits_per_digit().expect("hex or binary");
+            check_whole_bytes(
+                *span,
+                literal.digits.len(),
+                bits_per_digit,
+                partial,
+                warnings,
+            )?;
+            literal
+                .packed()
+                .map_err(|e| Error::new(*span, e))?
+                .expect("hex or binary")
+        }
+        Value::Int(lit) => {
+            let raw = raw_digits(lit);
+            match raw.form.bits_per_digit() {
+                Some(bits_per_digit) => check_whole_bytes(
+                    lit.span(),
+                    raw.digits.len(),
+                    bits_per_digit,
+                    partial,
+                    warnings,
+                )?,
+                // Octal digits do not divide bytes, so a partially filled
+                // first byte is expected.
+                None if raw.bits_per_zero_digit.is_some() => {}
+                // The size option gives decimal literals a fixed width, which
+                // is suggested in place of leading zeros.
+                None if raw.digits == "0" && sized => {
+                    allowed_form(lit, raw.form)?;
+                    return repeat(vec![0], &piece.repeat);
+                }
+                None if raw.digits.starts_with('0') => return Err(leading_zeros_error(lit, &raw)),
+                None => {}
+            }
+            let bytes = int_bytes(lit)?;
+            // The type suffix does not set the size, so warn when it suggests
+            // a different size.
+            match suggest::suffix_size(lit.suffix()) {
+                Some(size) if size != bytes.len() => warnings.push(Warning::new(
+                    lit.span(),
+                    format!(
+                        "the `{}` suffix is ignored, the literal is {} byte(s) rather than {}",
+                        lit.suffix(),
+                        bytes.len(),
+                        size
+                    ),
+                )),
+                _ => {}
+            }
+            bytes
+        }
+        Value::Str(lits) => {
+            let mut bytes = Vec::new();
+            for lit in lits {
+                bytes.extend(str_bytes(lit)?);
+            }
+            bytes
+        }
+        Value::ByteStr(lit) => lit.value(),
+        Value::Range {
+            start,
+            end,
+            inclusive,
+        } => range_bytes(start, end, *inclusive)?,
+    };
+
+    repeat(bytes, &piece.repeat)
+}
+
+/// Repeats the bytes of a piece by its repeat count, if it has one.
+fn repeat(bytes: Vec<u8>, repeat: &Option<LitInt>) -> Result<Vec<u8>, Error> {
+    match repeat {
+        Some(repeat) => {
+            let count = repeat.base10_parse::<usize>()?;
+            if count == 0 {
+                return Err(Error::new(
+                    repeat.span(),
+                    "repeat count must be greater than zero",
+                ));
+            }
+            match bytes.len().checked_mul(count) {
+                Some(len) if len <= MAX_LEN => Ok(bytes.repeat(count)),
+                _ => Err(Error::new(
+                    repeat.span(),
+                    format!(
+                        "{} byte(s) repeated {} times are larger than the maximum of {} bytes",
+                        bytes.len(),
+                        count,
+                        MAX_LEN
+                    ),
+                )),
+            }
+        }
+        None => Ok(bytes),
+    }
+}
+
+/// Returns the error for a decimal literal with leading zeros in the input of
+/// the bytes macro, suggesting the size option.
+fn leading_zeros_error(lit: &LitInt, raw: &RawDigits) -> Error {
+    let int = BigUint::from_str(lit.base10_digits()).expect("valid integer literal");
+    let e = ParseError::LeadingZeros { form: raw.form };
+    Error::new(
+        lit.span(),
+        format!("{}, {}", e, suggest::fixed_width(&int, raw.digits.len())),
+    )
+}
+
+/// Converts an integer or string literal into bytes, as a piece of the input of
+/// the bytes macro.
+pub fn lit_bytes(lit: &Lit) -> Result<Vec<u8>, Error> {
+    match lit {
+        Lit::Int(lit) => int_bytes(lit),
+        Lit::Str(lit) => str_bytes(lit),
+        _ => Err(Error::new(
+            lit.span(),
+            "expected an integer or string literal",
+        )),
+    }
+}
+
+/// Converts a string literal of hex, or of `\x` escapes, into bytes.
+fn str_bytes(lit: &LitStr) -> Result<Vec<u8>, Error> {
+    let value = lit.value();
+    let decoded = if value.contains("\\x") {
+        hex::decode_escaped(&value)
+    } else {
+        hex::decode_separated(&value)
+    };
+    decoded.map_err(|e| e.into_error(lit))
+}
+
+/// Converts a range of integer literals into the sequence of byte values in
+/// the range.
+fn range_bytes(start: &LitInt, end: &LitInt, inclusive: bool) -> Result<Vec<u8>, Error> {
+    let bound = |lit: &LitInt| {
+        lit.base10_parse::<u8>()
+            .map_err(|_| Error::new(lit.span(), "range bound must be a byte value (0 to 255)"))
     };
+    let (lo, hi) = (bound(start)?, bound(end)?);
+    if lo > hi {
+        return Err(Error::new(
+            start.span(),
+            "range start must not be greater than range end",
+        ));
+    }
+    let bytes: Vec<u8> = if inclusive {
+        (lo..=hi).collect()
+    } else {
+        (lo..hi).collect()
+    };
+    if bytes.is_empty() {
+        return Err(Error::new(start.span(), "range is empty"));
+    }
+    Ok(bytes)
+}
+
+/// The value of an integer literal, and the number of leading zero bits
+/// preserved from its leading zero digits.
+pub struct IntLit {
+    /// The bytes of the value, most significant first, which may include
+    /// leading zero bytes.
+    pub bytes: Vec<u8>,
+    pub leading_zero_bits: usize,
+}
+
+impl IntLit {
+    /// Returns the number of bits of the value, without any leading zeros.
+    pub fn bits(&self) -> usize {
+        match self.bytes.iter().position(|b| *b != 0) {
+            Some(i) => (self.bytes.len() - i) * 8 - self.bytes[i].leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// Returns the bit of the value at the index, counting from the least
+    /// significant bit.
+    pub fn bit(&self, i: usize) -> bool {
+        let len = self.bytes.len();
+        i / 8 < len && self.bytes[len - 1 - i / 8] >> (i % 8) & 1 == 1
+    }
+
+    /// Returns the total number of bits of the literal, being the leading zero
+    /// bits followed by the bits of the value.
+    pub fn total_bits(&self) -> usize {
+        self.leading_zero_bits
+            .checked_add(self.bits())
+            .expect("overflow")
+    }
+}
+
+/// The digits of an integer literal as they appear in the token stream.
+pub struct RawDigits {
+    /// The form of the literal: hex, binary, octal, or decimal.
+    pub form: Form,
+    /// The number of bits each leading zero digit represents, or None if the
+    /// form does not preserve leading zeros.
+    pub bits_per_zero_digit: Option<usize>,
+    /// The digits with underscores, the base prefix, and any suffix removed.
+    pub digits: String,
+}
+
+/// Returns the digits of an integer literal as written, including any leading
+/// zeros.
+pub fn raw_digits(lit: &LitInt) -> RawDigits {
+    // Remove underscores between digits.
+    let normalized = literal_text(lit).replace('_', "");
+
+    // Remove any leading prefix that indicates the base, and use the base to
+    // determine how many bits per leading zero needs to be prefilled into the
+    // bytes generated. If bits_per_zero_digit is None, leading zero digits are
+    // unsupported.
+    let (form, remainder) = match normalized.as_str() {
+        r if r.starts_with("0x") => (Form::Hex, &r[2..]),
+        r if r.starts_with("0b") => (Form::Binary, &r[2..]),
+        r if r.starts_with("0o") => (Form::Octal, &r[2..]),
+        r => (Form::Decimal, r),
+    };
+
+    RawDigits {
+        form,
+        bits_per_zero_digit: form.bits_per_zero_digit(),
+        digits: remainder.to_string(),
+    }
+}
+
+/// Parses an integer literal, counting the leading zero bits for the forms
+/// that preserve them.
+pub fn parse_int(lit: &LitInt) -> Result<IntLit, Error> {
+    unsigned(lit)?;
+
+    let RawDigits {
+        form,
+        bits_per_zero_digit,
+        digits,
+    } = raw_digits(lit);
+    allowed_form(lit, form)?;
+
+    // Count the leading zero bits by counting the number of leading zeros and
+    // multiplying by the bits per digit.
+    let leading_zero_count = digits.bytes().take_while(|d| *d == b'0').count();
+    let leading_zero_bits = if let Some(bits_per_digit) = bits_per_zero_digit {
+        leading_zero_count
+            .checked_mul(bits_per_digit)
+            .expect("overflow")
+    } else if leading_zero_count > 0 {
+        // If there are leading zeros without a bits per digit error, since a
+        // caller may expect the zeros to be preserved, and so it is better for
+        // us to error. They can proceed by removing the zeros.
+        return Err(int_error(lit, ParseError::LeadingZeros { form }));
+    } else {
+        0
+    };
+
+    // Hex and binary digits are packed directly into bytes, which is linear in
+    // the number of digits. Other forms are converted from their base10 string
+    // via a big integer. The conversion should never fail because syn::LitInt
+    // already validated the integer.
+    let bytes = match (bytes_lit_core::Literal { form, digits }).packed() {
+        Ok(Some(bytes)) => bytes,
+        Ok(None) => BigUint::from_str(lit.base10_digits())
+            .expect("valid integer literal")
+            .to_bytes_be(),
+        Err(e) => return Err(int_error(lit, e)),
+    };
+
+    Ok(IntLit {
+        bytes,
+        leading_zero_bits,
+    })
+}
+
+/// Errors if an integer literal is negative, suggesting ibytes! for two's
+/// complement bytes.
+pub fn unsigned(lit: &LitInt) -> Result<(), Error> {
+    if lit.base10_digits().starts_with('-') {
+        return Err(Error::new(
+            lit.span(),
+            format!("negative values unsupported, {}", suggest::negative(lit)),
+        ));
+    }
+    Ok(())
+}
+
+/// Converts an integer literal into bytes, preserving leading zeros for the
+/// forms that support it.
+pub fn int_bytes(lit: &LitInt) -> Result<Vec<u8>, Error> {
+    let bytes =
+        bytes_lit_core::parse_int_literal(&literal_text(lit)).map_err(|e| int_error(lit, e))?;
+    allowed_form(lit, raw_digits(lit).form)?;
+    Ok(bytes)
+}
+
+/// Errors if the form of a non-negative integer literal is denied by the
+/// `deny-decimal` or `deny-octal` features, suggesting the value in hex.
+pub fn allowed_form(lit: &LitInt, form: Form) -> Result<(), Error> {
+    let feature = match form {
+        Form::Decimal if cfg!(feature = "deny-decimal") => "deny-decimal",
+        Form::Octal if cfg!(feature = "deny-octal") => "deny-octal",
+        _ => return Ok(()),
+    };
+    let int = BigUint::from_str(lit.base10_digits()).expect("valid integer literal");
+    Err(Error::new(
+        lit.span(),
+        format!(
+            "integer literals in {} form are denied by the `{}` feature, write the value in hex as `{}` or in binary",
+            form,
+            feature,
+            suggest::hex_literal(&int)
+        ),
+    ))
+}
+
+/// Returns the text of an integer literal as it appears in the token stream,
+/// without any type suffix.
+pub fn literal_text(lit: &LitInt) -> String {
+    let raw = lit.to_string();
+    raw.strip_suffix(lit.suffix()).unwrap_or(&raw).to_string()
+}
+
+/// Converts an error from converting an integer literal into an error at the
+/// literal, with a suggestion for how to fix it.
+pub fn int_error(lit: &LitInt, e: ParseError) -> Error {
+    let message = match e {
+        ParseError::Negative => format!("{}, {}", e, suggest::negative(lit)),
+        ParseError::LeadingZeros { .. } => {
+            let int = BigUint::from_str(lit.base10_digits()).expect("valid integer literal");
+            format!("{}, {}", e, suggest::leading_zeros(&int))
+        }
+        _ => e.to_string(),
+    };
+    Error::new(lit.span(), message)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bytes, bytes_len, bytes_pat, bytes_ref, bytesvec};
+    use pretty_assertions::assert_eq;
+    use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+    use quote::quote;
+    use syn::{
+        parse::Parser, parse_quote, Error, Expr, ExprArray, ExprBlock, ExprCall, ExprLit,
+        ExprUnary, Lit, LitStr, Pat, Stmt,
+    };
+
+    /// Returns the array expression emitted, which is at the end of a block
+    /// when there are warnings.
+    fn array(tokens: TokenStream2) -> ExprArray {
+        match syn::parse2::<Expr>(tokens).unwrap() {
+            Expr::Array(array) => array,
+            Expr::Block(block) => match block.block.stmts.last() {
+                Some(Stmt::Expr(Expr::Array(array), None)) => array.clone(),
+                _ => panic!("expected an array at the end of the block"),
+            },
+            _ => panic!("expected an array"),
+        }
+    }
+
+    #[test]
+    fn neg() {
+        let tokens = bytes(quote! {-0x1});
+        let expect = Error::new(
+            Span::call_site(),
+            "negative values unsupported, use ibytes! with `size = 1` for two's complement bytes",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+
+    #[test]
+    fn hex() {
+        let tokens = bytes(quote! {0x1});
+        let expect = syn::parse_quote!([1u8]);
+        assert_eq!(array(tokens), expect);
+
+        let tokens = bytes(quote! {0x928374892abc});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect = syn::parse_quote!([146u8, 131u8, 116u8, 137u8, 42u8, 188u8]);
+        assert_eq!(parsed, expect);
+
+        let tokens =
+            bytes(quote! {0xfded3f55dec47250a52a8c0bb7038e72fa6ffaae33562f77cd2b629ef7fd424d});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect = syn::parse_quote!([
+            253u8, 237u8, 63u8, 85u8, 222u8, 196u8, 114u8, 80u8, 165u8, 42u8, 140u8, 11u8, 183u8,
+            3u8, 142u8, 114u8, 250u8, 111u8, 250u8, 174u8, 51u8, 86u8, 47u8, 119u8, 205u8, 43u8,
+            98u8, 158u8, 247u8, 253u8, 66u8, 77u8
+        ]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn repeat() {
+        let tokens = bytes(quote! {0xAB; 4});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect = syn::parse_quote!([171u8, 171u8, 171u8, 171u8]);
+        assert_eq!(parsed, expect);
+
+        let tokens = bytes(quote! {0x00ff; 2});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect = syn::parse_quote!([0u8, 255u8, 0u8, 255u8]);
+        assert_eq!(parsed, expect);
+
+        let tokens = bytes(quote! {0xAB; 0});
+        let expect = Error::new(Span::call_site(), "repeat count must be greater than zero")
+            .to_compile_error()
+            .to_string();
+        assert_eq!(tokens.to_string(), expect);
+
+        let tokens = bytes(quote! {0x00; 4000000000});
+        let expect = Error::new(
+            Span::call_site(),
+            "1 byte(s) repeated 4000000000 times are larger than the maximum of 16777216 bytes",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+
+    #[test]
+    fn range() {
+        let tokens = bytes(quote! {0x10..0x14});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect = syn::parse_quote!([16u8, 17u8, 18u8, 19u8]);
+        assert_eq!(parsed, expect);
+
+        let tokens = bytes(quote! {0xFC..=0xFF});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect = syn::parse_quote!([252u8, 253u8, 254u8, 255u8]);
+        assert_eq!(parsed, expect);
+
+        let tokens = bytes(quote! {0x00..=0xFF});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        assert_eq!(parsed.elems.len(), 256);
+
+        let tokens = bytes(quote! {0..2; 2});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect = syn::parse_quote!([0u8, 1u8, 0u8, 1u8]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn range_errors() {
+        let table: &[(_, &str)] = &[
+            (
+                quote!(0x00..=0x100),
+                "range bound must be a byte value (0 to 255)",
+            ),
+            (
+                quote!(0x20..0x10),
+                "range start must not be greater than range end",
+            ),
+            (quote!(0x10..0x10), "range is empty"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn hex_str() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!("00ff"), parse_quote!([0u8, 255u8])),
+            (
+                quote!("DEADbeef"),
+                parse_quote!([222u8, 173u8, 190u8, 239u8]),
+            ),
+            (
+                quote!("DE:AD:BE:EF"),
+                parse_quote!([222u8, 173u8, 190u8, 239u8]),
+            ),
+            (
+                quote!("de-ad be\tef"),
+                parse_quote!([222u8, 173u8, 190u8, 239u8]),
+            ),
+            (quote!("00 01"; 2), parse_quote!([0u8, 1u8, 0u8, 1u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = bytes(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+
+        let tokens = bytes(quote! {"DE:AD:BE:EG"});
+        let expect = Error::new(Span::call_site(), "invalid hex character 'G' at index 10")
+            .to_compile_error()
+            .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+
+    #[test]
+    fn append_crc() {
+        let table: &[(_, ExprArray)] = &[
+            (
+                quote!(0x313233343536373839; append = crc32),
+                parse_quote!([
+                    49u8, 50u8, 51u8, 52u8, 53u8, 54u8, 55u8, 56u8, 57u8, 203u8, 244u8, 57u8, 38u8
+                ]),
+            ),
+            (
+                quote!("31 32 33 34 35 36 37 38 39", append = crc16),
+                parse_quote!([49u8, 50u8, 51u8, 52u8, 53u8, 54u8, 55u8, 56u8, 57u8, 41u8, 177u8]),
+            ),
+            (
+                quote!(0x00; 2, append = crc16_xmodem),
+                parse_quote!([0u8, 0u8, 0u8, 0u8]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = bytes(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+
+        let tokens = bytes(quote! {0x00; append = crc8});
+        let expect = Error::new(
+            Span::call_site(),
+            "unsupported CRC, expected one of: crc32, crc16, crc16_ccitt, crc16_xmodem",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+
+    #[test]
+    fn escaped_str() {
+        let tokens = bytes(quote! {r"\xDE\xAD\xBE\xEF"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([222u8, 173u8, 190u8, 239u8]);
+        assert_eq!(parsed, expect);
+
+        let tokens = bytes(quote! {"OK\\x00"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([79u8, 75u8, 0u8]);
+        assert_eq!(parsed, expect);
+
+        let tokens = bytes(quote! {r"\xDE\xA"});
+        let expect = Error::new(
+            Span::call_site(),
+            "escape `\\x` must be followed by two hex digits at index 4",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+
+    #[test]
+    fn multiline_str() {
+        let expect: ExprArray = parse_quote!([222u8, 173u8, 190u8, 239u8, 0u8, 1u8]);
+        let table = &[
+            quote!(
+                "
+                dead beef
+                0001
+                "
+            ),
+            quote!("dead" "beef" "0001"),
+            quote!("de ad"
+                   r"\xBE\xEF"
+                   "00 01"),
+            quote!(
+                "dead\
+                    beef\
+                    0001"
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.clone());
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, expect, "table entry: {}", i);
+        }
+
+        let tokens = bytes(quote! {"dead" "bee" "f0"});
+        let expect = Error::new(Span::call_site(), "odd number of hex digits at index 2")
+            .to_compile_error()
+            .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+
+    #[test]
+    fn concatenation() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(0xCAFE, 0x01), parse_quote!([202u8, 254u8, 1u8])),
+            (
+                quote!(0xCAFE /* magic */, 0x01 /* version */),
+                parse_quote!([202u8, 254u8, 1u8]),
+            ),
+            (
+                quote!(
+                    /// Magic.
+                    0xCAFE,
+                    #[doc = "Version."]
+                    0x01,
+                    /// Reserved.
+                    0x00; 2,
+                    "ff" "ee",
+                    0..3,
+                ),
+                parse_quote!([202u8, 254u8, 1u8, 0u8, 0u8, 255u8, 238u8, 0u8, 1u8, 2u8]),
+            ),
+            (
+                quote!(0x3132, 0x33; append = crc16_xmodem),
+                parse_quote!([49u8, 50u8, 51u8, 151u8, 82u8]),
+            ),
+            (
+                quote!(0x01, 0x02, append = crc16_xmodem),
+                parse_quote!([1u8, 2u8, 19u8, 115u8]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = bytes(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn lengths() {
+        let table: &[(_, usize)] = &[
+            (quote!(0x0001), 2),
+            (quote!(0x00000000), 4),
+            (quote!(256), 2),
+            (quote!("DE:AD:BE:EF"), 4),
+            (quote!(0x01, size = 32), 32),
+            (quote!(0x01, pad_right = 12, append = crc32), 16),
+            (quote!(0xff; 3, align = 4), 4),
+            (quote!(0x01 => [u8; 8], pad), 8),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes_len(t.0.clone());
+            let expect = Literal::usize_suffixed(t.1).to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn hex_str_output() {
+        let table: &[(_, &str)] = &[
+            (quote!(0xDEADBEEF), "deadbeef"),
+            (quote!("DE:AD:BE:EF", prefix), "0xdeadbeef"),
+            (quote!(0x0001, size = 4), "00000001"),
+            (quote!(0xdeadbeef01, group = 2), "dead beef 01"),
+            (
+                quote!(0xdeadbeef, group = 1, separator = ":"),
+                "de:ad:be:ef",
+            ),
+            (quote!(b"", prefix), "0x"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = super::hex_str(t.0.clone());
+            let parsed = syn::parse2::<LitStr>(tokens).unwrap();
+            assert_eq!(parsed.value(), t.1, "table entry: {}", i);
+        }
+
+        let table: &[(_, &str)] = &[
+            (
+                quote!(0xdead, group = 0),
+                "group size must be greater than zero",
+            ),
+            (
+                quote!(0xdead, separator = ":"),
+                "option `separator` requires the `group` option",
+            ),
+            (
+                quote!(0xdead, group = "2"),
+                "option `group` requires an integer value",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = super::hex_str(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn sha256() {
+        let digest = quote!(0xba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad);
+        let tokens = bytes(quote!(0x616263; sha256 = #digest));
+        let expect: ExprArray = parse_quote!([97u8, 98u8, 99u8]);
+        assert_eq!(array(tokens), expect);
+
+        let tokens = bytes(quote!(
+            b"abc",
+            sha256 = "BA7816BF 8F01CFEA 414140DE 5DAE2223 B00361A3 96177A9C B410FF61 F20015AD"
+        ));
+        assert_eq!(array(tokens), expect);
+
+        let table: &[(_, &str)] = &[
+            (
+                quote!(0x616264; sha256 = #digest),
+                "sha256 digest of the bytes does not match, expected 0xba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad, found 0xa52d159f262b2c6ddb724a61840befc36eb30c88877a4030b65cbe86298449c9",
+            ),
+            (
+                quote!(0x616263; sha256 = 0xba78),
+                "sha256 digest must be 32 bytes, found 2 byte(s)",
+            ),
+            (
+                quote!(0x616263; sha256 = abc),
+                "option `sha256` requires a literal value",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn builtin_macros() {
+        std::env::set_var("BYTES_LIT_TEST_BUILTIN", "be:ef");
+        let table: &[(_, ExprArray)] = &[
+            (
+                quote!(concat!("dead", "beef")),
+                parse_quote!([222u8, 173u8, 190u8, 239u8]),
+            ),
+            (
+                quote!(0xdead, env!("BYTES_LIT_TEST_BUILTIN")),
+                parse_quote!([222u8, 173u8, 190u8, 239u8]),
+            ),
+            (
+                quote!("de" concat!("ad", stringify!(be)) "ef"),
+                parse_quote!([222u8, 173u8, 190u8, 239u8]),
+            ),
+            (
+                quote!(concat!("01", "02"), size = 4),
+                parse_quote!([0u8, 0u8, 1u8, 2u8]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = bytes(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+
+        let tokens = bytes(quote!(concat!("de", "a")));
+        let expect = Error::new(Span::call_site(), "odd number of hex digits at index 2")
+            .to_compile_error()
+            .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+
+    #[test]
+    fn concatenation_errors() {
+        let table: &[(_, &str)] = &[
+            (
+                quote!(0x01, -0x02),
+                "negative values unsupported, use ibytes! with `size = 1` for two's complement bytes",
+            ),
+            (
+                quote!(0x01, 0x02; 0),
+                "repeat count must be greater than zero",
+            ),
+            (
+                quote!(
+                    #[allow(unused)]
+                    0x01
+                ),
+                "only doc attributes are accepted as labels",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn chunks() {
+        let table: &[(_, ExprArray)] = &[
+            (
+                quote!(0x01020304; chunk = 2),
+                parse_quote!([[1u8, 2u8], [3u8, 4u8]]),
+            ),
+            (
+                quote!(0x0102030405, chunk = 2, chunk_pad),
+                parse_quote!([[1u8, 2u8], [3u8, 4u8], [5u8, 0u8]]),
+            ),
+            (
+                quote!(0x00..16, chunk = 16),
+                parse_quote!([[
+                    0u8, 1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 12u8, 13u8, 14u8,
+                    15u8
+                ]]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = bytes(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+
+        let table: &[(_, &str)] = &[
+            (
+                quote!(0x010203; chunk = 2),
+                "3 byte(s) is not a multiple of the chunk size of 2 bytes, use the `chunk_pad` option to zero pad",
+            ),
+            (quote!(0x01; chunk = 0), "chunk size must be greater than zero"),
+            (
+                quote!(0x01; chunk_pad),
+                "option `chunk_pad` requires the `chunk` option",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+
+    /// Returns the value of a dereferenced byte string expression.
+    fn byte_string(expr: &Expr) -> Vec<u8> {
+        match expr {
+            Expr::Unary(ExprUnary { expr, .. }) => match &**expr {
+                Expr::Lit(ExprLit {
+                    lit: Lit::ByteStr(lit),
+                    ..
+                }) => lit.value(),
+                _ => panic!("expected a byte string"),
+            },
+            _ => panic!("expected a dereferenced byte string"),
+        }
+    }
+
+    #[test]
+    fn byte_str() {
+        let tokens = bytes(quote! {0x0001; byte_str});
+        let parsed = syn::parse2::<Expr>(tokens).unwrap();
+        assert_eq!(byte_string(&parsed), [0, 1]);
+
+        let tokens = bytes(quote! {0x00010203, chunk = 2, byte_str});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let rows: Vec<_> = parsed.elems.iter().map(byte_string).collect();
+        assert_eq!(rows, [[0, 1], [2, 3]]);
+
+        // Large arrays are emitted as byte strings without the option.
+        let tokens = bytes(quote! {0x00..=0xff; 2});
+        let parsed = syn::parse2::<Expr>(tokens).unwrap();
+        let expect: Vec<u8> = (0..=255).chain(0..=255).collect();
+        assert_eq!(byte_string(&parsed), expect);
+    }
+
+    #[test]
+    fn partial_bytes() {
+        let warning = |note: &str| -> Stmt {
+            parse_quote! {
+                const _: () = {
+                    #[deprecated(note = #note)]
+                    struct Warning;
+                    let _ = Warning;
+                };
+            }
+        };
+        let table: &[(TokenStream2, Stmt)] = &[
+            (
+                quote!(0xFFF),
+                warning("odd number of hex digits, the first byte holds a single digit, add leading zeros to fill the byte or use the `allow_odd` option"),
+            ),
+            (
+                quote!(0xFFFu16),
+                warning("odd number of hex digits, the first byte holds a single digit, add leading zeros to fill the byte or use the `allow_odd` option"),
+            ),
+            (
+                quote!(0b1_0000_0000),
+                warning("9 binary digits is not a multiple of 8, the first byte holds 1 digit(s), add leading zeros to fill the byte or use the `allow_odd` option"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let block = syn::parse2::<ExprBlock>(bytes(t.0.clone())).unwrap();
+            assert_eq!(block.block.stmts[0], t.1, "table entry: {}", i);
+        }
+
+        let tokens = bytes(quote!(0xFFF, allow_odd));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        assert_eq!(parsed, parse_quote!([15u8, 255u8]));
+
+        let tokens = bytes(quote!(0x0FFF, 0b0000_0001; strict));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        assert_eq!(parsed, parse_quote!([15u8, 255u8, 1u8]));
+
+        let table: &[(TokenStream2, &str)] = &[
+            (
+                quote!(0xFFF; strict),
+                "odd number of hex digits, the first byte holds a single digit, add leading zeros to fill the byte",
+            ),
+            (
+                quote!(0x00, 0b101; strict),
+                "3 binary digits is not a multiple of 8, the first byte holds 3 digit(s), add leading zeros to fill the byte",
+            ),
+            (
+                quote!(0x00; strict, allow_odd),
+                "options `strict` and `allow_odd` cannot be used together",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(bytes(t.0.clone()).to_string(), expect, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn strict_binary() {
+        let tokens = bytes(quote!(0b0000_1111, 0b1010_0101u8; strict));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        assert_eq!(parsed, parse_quote!([15u8, 165u8]));
+
+        let tokens = bytes(quote!(0b0000_1111, 0b1111; strict));
+        let expect = Error::new(
+            Span::call_site(),
+            "4 binary digits is not a multiple of 8, the first byte holds 4 digit(s), add leading zeros to fill the byte",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+
+    #[test]
+    fn reverse() {
+        let table: &[(TokenStream2, ExprArray)] = &[
+            (quote!(0x0102; reverse), parse_quote!([2u8, 1u8])),
+            (
+                quote!(0x0001, "0203"; reverse),
+                parse_quote!([3u8, 2u8, 1u8, 0u8]),
+            ),
+            (
+                quote!(0x31323334, append = crc16, reverse),
+                parse_quote!([73u8, 83u8, 52u8, 51u8, 50u8, 49u8]),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let parsed = syn::parse2::<ExprArray>(bytes(t.0.clone())).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn swap() {
+        let table: &[(TokenStream2, ExprArray)] = &[
+            (
+                quote!(0x0102030405060708; swap = 4),
+                parse_quote!([4u8, 3u8, 2u8, 1u8, 8u8, 7u8, 6u8, 5u8]),
+            ),
+            (
+                quote!(0x01020304; swap = 2),
+                parse_quote!([2u8, 1u8, 4u8, 3u8]),
+            ),
+            (quote!(0x0102; swap = 1), parse_quote!([1u8, 2u8])),
+            (
+                quote!(0x0102; swap = 4, size = 4),
+                parse_quote!([2u8, 1u8, 0u8, 0u8]),
+            ),
+            (
+                quote!(0x01020304; swap = 2, reverse),
+                parse_quote!([3u8, 4u8, 1u8, 2u8]),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let parsed = syn::parse2::<ExprArray>(bytes(t.0.clone())).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+
+        let table = &[
+            (
+                quote!(0x010203; swap = 2),
+                "3 byte(s) is not a multiple of the swap size of 2 bytes",
+            ),
+            (quote!(0x01; swap = 0), "swap must be greater than zero"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
 
-    // Convert the integer literal into a base10 string, and into a slice of
-    // bytes, via a big integer. The conversion should never fail because
-    // syn::LitInt already validated the integer, unless the value is negative.
-    // Any leading zeroes are discarded.
-    let int = match BigUint::from_str(lit.base10_digits()) {
-        Ok(int) => int,
-        Err(_) => return Error::new(lit.span(), "negative values unsupported").to_compile_error(),
-    };
+    #[test]
+    fn bitreflect() {
+        let table: &[(TokenStream2, ExprArray)] = &[
+            (quote!(0x01F0; bitreflect), parse_quote!([128u8, 15u8])),
+            (
+                quote!(0x01F0; bitreflect, reverse),
+                parse_quote!([15u8, 128u8]),
+            ),
+            (quote!(0b1000_0010; bitreflect), parse_quote!([65u8])),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let parsed = syn::parse2::<ExprArray>(bytes(t.0.clone())).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
 
-    // Get the raw integer literal as it appears in the token stream.
-    let raw = lit.to_string();
+    #[test]
+    fn expressions() {
+        let table: &[(TokenStream2, ExprArray)] = &[
+            (quote!(0x10 + 0x0F), parse_quote!([31u8])),
+            (
+                quote!(0x10 + 0x0F, size = 4),
+                parse_quote!([0u8, 0u8, 0u8, 31u8]),
+            ),
+            (quote!(1 << 16), parse_quote!([1u8, 0u8, 0u8])),
+            (quote!((0xff00 | 0x00f0) >> 4), parse_quote!([15u8, 255u8])),
+            (quote!(0x01, (2 * 3); 2), parse_quote!([1u8, 6u8, 6u8])),
+            (quote!(1 - 1), parse_quote!([0u8])),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let parsed = syn::parse2::<ExprArray>(bytes(t.0.clone())).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
 
-    // Remove underscores between digits.
-    let normalized = raw.replace('_', "");
+        let tokens = bytes(quote!(1 << 255));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        assert_eq!(parsed.elems.len(), 32);
+    }
 
-    // Remove any leading prefix that indicates the base, and use the base to
-    // determine how many bits per leading zero needs to be prefilled into the
-    // bytes generated. If bits_per_digit is None, leading zero digits are
-    // unsupported.
-    let (form, bits_per_zero_digit, remainder) = match normalized.as_bytes() {
-        [b'0', b'x', r @ ..] => ("hex", Some(4), r),
-        [b'0', b'b', r @ ..] => ("binary", Some(1), r),
-        [b'0', b'o', r @ ..] => ("octal", None, r),
-        [r @ ..] => ("decimal", None, r),
-    };
+    #[test]
+    fn size() {
+        let table: &[(TokenStream2, Result<ExprArray, &str>)] = &[
+            (
+                quote!(255, size = 4),
+                Ok(parse_quote!([0u8, 0u8, 0u8, 255u8])),
+            ),
+            (quote!(0, size = 2), Ok(parse_quote!([0u8, 0u8]))),
+            (quote!(0, 0xff, size = 3), Ok(parse_quote!([0u8, 0u8, 255u8]))),
+            (
+                quote!(0),
+                Err("leading zeros are not preserved or supported on integer literals in decimal form, remove the zeros and use the `size` option for a fixed width, such as `0, size = 1`, or write the value in hex as `0x00`, where leading zero digits are preserved"),
+            ),
+            (
+                quote!(00, size = 2),
+                Err("leading zeros are not preserved or supported on integer literals in decimal form, remove the zeros and use the `size` option for a fixed width, such as `0, size = 1`, or write the value in hex as `0x00`, where leading zero digits are preserved"),
+            ),
+            (quote!(0x0102; size = 2), Ok(parse_quote!([1u8, 2u8]))),
+            (
+                quote!(0x01, "02"; size = 3),
+                Ok(parse_quote!([0u8, 1u8, 2u8])),
+            ),
+            (
+                quote!(0x010203, size = 2),
+                Err("3 byte(s) do not fit in the size of 2 byte(s), use the `truncate` option to keep only 2 byte(s)"),
+            ),
+            (
+                quote!(0x01, size = 0),
+                Err("size must be greater than zero"),
+            ),
+            (
+                quote!(0x01, size = 4000000000),
+                Err("size of 4000000000 byte(s) is larger than the maximum of 16777216 bytes"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.0.clone());
+            match t.1.clone() {
+                Ok(expect) => {
+                    let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+                    assert_eq!(parsed, expect, "table entry: {}", i);
+                }
+                Err(message) => {
+                    let expect = Error::new(Span::call_site(), message)
+                        .to_compile_error()
+                        .to_string();
+                    assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+                }
+            }
+        }
+    }
 
-    // Count the leading zero bits by counting the number of leading zeros and
-    // multiplying by the bits per digit.
-    let leading_zero_count = remainder.iter().take_while(|d| **d == b'0').count();
-    let leading_zero_bits = if let Some(bits_per_digit) = bits_per_zero_digit {
-        leading_zero_count
-            .checked_mul(bits_per_digit)
-            .expect("overflow")
-    } else if leading_zero_count > 0 {
-        // If there are leading zeros without a bits per digit error, since a
-        // caller may expect the zeros to be preserved, and so it is better for
-        // us to error. They can proceed by removing the zeros.
-        return Error::new(
-            lit.span(),
-            format!(
-                "leading zeros are not preserved or supported on integer literals in {} form",
-                form,
+    #[test]
+    fn align() {
+        let table: &[(TokenStream2, Result<ExprArray, &str>)] = &[
+            (
+                quote!(0x010203; align = 4),
+                Ok(parse_quote!([1u8, 2u8, 3u8, 0u8])),
             ),
-        )
-        .to_compile_error();
-    } else {
-        0
-    };
+            (
+                quote!(0x01020304; align = 4),
+                Ok(parse_quote!([1u8, 2u8, 3u8, 4u8])),
+            ),
+            (
+                quote!(0x00000003, 0x6162; align = 4),
+                Ok(parse_quote!([0u8, 0u8, 0u8, 3u8, 97u8, 98u8, 0u8, 0u8])),
+            ),
+            (
+                quote!(0x0102030405; range = 0..1, align = 2),
+                Ok(parse_quote!([1u8, 0u8])),
+            ),
+            (
+                quote!(0x01; align = 0),
+                Err("align must be greater than zero"),
+            ),
+            (
+                quote!(0x01; align = 4000000000),
+                Err("align of 4000000000 byte(s) is larger than the maximum of 16777216 bytes"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.0.clone());
+            match t.1.clone() {
+                Ok(expect) => {
+                    let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+                    assert_eq!(parsed, expect, "table entry: {}", i);
+                }
+                Err(message) => {
+                    let expect = Error::new(Span::call_site(), message)
+                        .to_compile_error()
+                        .to_string();
+                    assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+                }
+            }
+        }
+    }
 
-    // Create the final byte slice, which has length of the leading zero bytes,
-    // followed by the big integer bytes.
-    let int_bits: usize = int.bits().try_into().expect("overflow");
-    let int_bytes = int.to_bytes_be();
-    let int_len = int_bytes.len();
-    let total_bits = leading_zero_bits.checked_add(int_bits).expect("overflow");
-    let total_len = (total_bits.checked_add(7).expect("overflow")) / 8;
-    let mut total_bytes: Vec<u8> = vec![0; total_len];
-    total_bytes[total_len - int_len..].copy_from_slice(&int_bytes);
+    #[test]
+    fn truncate() {
+        let table: &[(TokenStream2, Result<ExprArray, &str>)] = &[
+            (
+                quote!(0x0102030405; size = 2, truncate),
+                Ok(parse_quote!([1u8, 2u8])),
+            ),
+            (
+                quote!(0x0102030405; size = 2, truncate = leading),
+                Ok(parse_quote!([1u8, 2u8])),
+            ),
+            (
+                quote!(0x0102030405; size = 2, truncate = trailing),
+                Ok(parse_quote!([4u8, 5u8])),
+            ),
+            (
+                quote!(0x01; size = 2, truncate),
+                Ok(parse_quote!([0u8, 1u8])),
+            ),
+            (
+                quote!(0x0102030405; size = 2),
+                Err("5 byte(s) do not fit in the size of 2 byte(s), use the `truncate` option to keep only 2 byte(s)"),
+            ),
+            (
+                quote!(0x0102030405; truncate),
+                Err("option `truncate` requires the `size` option"),
+            ),
+            (
+                quote!(0x0102030405; size = 2, truncate = middle),
+                Err("option `truncate` requires one of: leading, trailing"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.0.clone());
+            match t.1.clone() {
+                Ok(expect) => {
+                    let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+                    assert_eq!(parsed, expect, "table entry: {}", i);
+                }
+                Err(message) => {
+                    let expect = Error::new(Span::call_site(), message)
+                        .to_compile_error()
+                        .to_string();
+                    assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+                }
+            }
+        }
+    }
 
-    quote! { [#(#total_bytes),*] }
-}
+    #[test]
+    fn pad_right() {
+        let table: &[(TokenStream2, Result<ExprArray, &str>)] = &[
+            (
+                quote!(0x555344, pad_right = 4),
+                Ok(parse_quote!([85u8, 83u8, 68u8, 0u8])),
+            ),
+            (quote!(0x0102, pad_right = 2), Ok(parse_quote!([1u8, 2u8]))),
+            (
+                quote!(0x01, pad_right = 2, append = crc16_xmodem),
+                Ok(parse_quote!([1u8, 0u8, 51u8, 49u8])),
+            ),
+            (
+                quote!(0x010203, pad_right = 2),
+                Err("3 byte(s) do not fit in the pad_right of 2 byte(s)"),
+            ),
+            (
+                quote!(0x01, pad_right = 0),
+                Err("pad_right must be greater than zero"),
+            ),
+            (
+                quote!(0x01, pad_right = 4000000000),
+                Err("pad_right of 4000000000 byte(s) is larger than the maximum of 16777216 bytes"),
+            ),
+            (
+                quote!(0x01, size = 2, pad_right = 2),
+                Err("options `size` and `pad_right` cannot be used together"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.0.clone());
+            match t.1.clone() {
+                Ok(expect) => {
+                    let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+                    assert_eq!(parsed, expect, "table entry: {}", i);
+                }
+                Err(message) => {
+                    let expect = Error::new(Span::call_site(), message)
+                        .to_compile_error()
+                        .to_string();
+                    assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+                }
+            }
+        }
+    }
 
-#[cfg(test)]
-mod test {
-    use super::bytes;
-    use pretty_assertions::assert_eq;
-    use proc_macro2::Span;
-    use quote::quote;
-    use syn::{parse_quote, Error, ExprArray};
+    #[test]
+    fn ascription() {
+        let table: &[(TokenStream2, Result<ExprArray, &str>)] = &[
+            (
+                quote!(0x01020304 => [u8; 4]),
+                Ok(parse_quote!([1u8, 2u8, 3u8, 4u8])),
+            ),
+            (
+                quote!(0x01 => [u8; 4], pad),
+                Ok(parse_quote!([0u8, 0u8, 0u8, 1u8])),
+            ),
+            (
+                quote!(0x010203 => [u8; 2], truncate),
+                Ok(parse_quote!([1u8, 2u8])),
+            ),
+            (
+                quote!(0x010203 => [u8; 2], truncate = trailing),
+                Ok(parse_quote!([2u8, 3u8])),
+            ),
+            (
+                quote!(0x01, 0x02 => [u8; 4], align = 4),
+                Ok(parse_quote!([1u8, 2u8, 0u8, 0u8])),
+            ),
+            (
+                quote!(0x01 => [u8; 4]),
+                Err("1 byte(s) do not match the ascribed type `[u8; 4]`, use the `pad` option to zero pad"),
+            ),
+            (
+                quote!(0x010203 => [u8; 2]),
+                Err("3 byte(s) do not match the ascribed type `[u8; 2]`, use the `truncate` option to keep only 2 byte(s)"),
+            ),
+            (
+                quote!(0x01 => [i8; 1]),
+                Err("type ascription requires an array of bytes, such as `[u8; 4]`"),
+            ),
+            (
+                quote!(0x01 => [u8; N]),
+                Err("type ascription requires an array of bytes, such as `[u8; 4]`"),
+            ),
+            (
+                quote!(0x01, pad),
+                Err("option `pad` requires a type ascription, such as `=> [u8; 4]`"),
+            ),
+            (
+                quote!(0x0102 => [u8; 2], chunk = 1),
+                Err("option `chunk` cannot be used with a type ascription"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.0.clone());
+            match t.1.clone() {
+                Ok(expect) => {
+                    let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+                    assert_eq!(parsed, expect, "table entry: {}", i);
+                }
+                Err(message) => {
+                    let expect = Error::new(Span::call_site(), message)
+                        .to_compile_error()
+                        .to_string();
+                    assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+                }
+            }
+        }
+    }
 
     #[test]
-    fn neg() {
-        let tokens = bytes(quote! {-0x1});
-        let expect = Error::new(Span::call_site(), "negative values unsupported")
-            .to_compile_error()
-            .to_string();
+    fn patterns() {
+        let table: &[TokenStream2] = &[
+            bytes(quote!(0xCAFEBABE)),
+            bytes(quote!(0x0102; chunk = 1)),
+            bytes_pat(quote!(0xCAFEBABE)),
+            bytes_pat(quote!(0x00; 1000)),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let pat = Parser::parse2(Pat::parse_single, t.clone());
+            assert!(pat.is_ok(), "table entry: {}", i);
+        }
+
+        let tokens = bytes_pat(quote!(0x00; 300));
+        let pat = Parser::parse2(Pat::parse_single, tokens).unwrap();
+        assert!(matches!(pat, Pat::Slice(slice) if slice.elems.len() == 300));
+
+        let tokens = bytes_pat(quote!(0xfff));
+        let expect = Error::new(
+            Span::call_site(),
+            "odd number of hex digits, the first byte holds a single digit, add leading zeros to fill the byte or use the `allow_odd` option",
+        )
+        .to_compile_error()
+        .to_string();
         assert_eq!(tokens.to_string(), expect);
+        let tokens = bytes_pat(quote!(0xfff, allow_odd));
+        let expect: ExprArray = parse_quote!([15u8, 255u8]);
+        assert_eq!(syn::parse2::<ExprArray>(tokens).unwrap(), expect);
     }
 
     #[test]
-    fn hex() {
-        let tokens = bytes(quote! {0x1});
-        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
-        let expect = syn::parse_quote!([1u8]);
-        assert_eq!(parsed, expect);
+    fn denied_forms() {
+        let table: &[(TokenStream2, bool, &str)] = &[
+            (
+                quote!(255),
+                cfg!(feature = "deny-decimal"),
+                "integer literals in decimal form are denied by the `deny-decimal` feature, write the value in hex as `0xff` or in binary",
+            ),
+            (
+                quote!(0o377),
+                cfg!(feature = "deny-octal"),
+                "integer literals in octal form are denied by the `deny-octal` feature, write the value in hex as `0xff` or in binary",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.0.clone());
+            if t.1 {
+                let expect = Error::new(Span::call_site(), t.2)
+                    .to_compile_error()
+                    .to_string();
+                assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+            } else {
+                let expect: ExprArray = parse_quote!([255u8]);
+                assert_eq!(array(tokens), expect, "table entry: {}", i);
+            }
+        }
+    }
 
-        let tokens = bytes(quote! {0x928374892abc});
-        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
-        let expect = syn::parse_quote!([146u8, 131u8, 116u8, 137u8, 42u8, 188u8]);
-        assert_eq!(parsed, expect);
+    #[test]
+    fn range_option() {
+        let table: &[(TokenStream2, Result<ExprArray, &str>)] = &[
+            (
+                quote!(0x0001020304; range = 0..2),
+                Ok(parse_quote!([0u8, 1u8])),
+            ),
+            (
+                quote!(0x0001020304; range = 1..=3),
+                Ok(parse_quote!([1u8, 2u8, 3u8])),
+            ),
+            (
+                quote!(0x0001020304; range = 3..),
+                Ok(parse_quote!([3u8, 4u8])),
+            ),
+            (quote!(0x0001020304; range = ..1), Ok(parse_quote!([0u8]))),
+            (
+                quote!(0x0001020304; range = 4..6),
+                Err("range 4..6 is out of bounds of the 5 byte(s)"),
+            ),
+            (
+                quote!(0x0001020304; range = 3..2),
+                Err("range start must not be greater than range end"),
+            ),
+            (
+                quote!(0x0001020304; range = 3),
+                Err("option `range` requires a range of integers, such as `0..4`"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.0.clone());
+            match t.1.clone() {
+                Ok(expect) => {
+                    let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+                    assert_eq!(parsed, expect, "table entry: {}", i);
+                }
+                Err(message) => {
+                    let expect = Error::new(Span::call_site(), message)
+                        .to_compile_error()
+                        .to_string();
+                    assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+                }
+            }
+        }
+    }
 
-        let tokens =
-            bytes(quote! {0xfded3f55dec47250a52a8c0bb7038e72fa6ffaae33562f77cd2b629ef7fd424d});
-        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
-        let expect = syn::parse_quote!([
-            253u8, 237u8, 63u8, 85u8, 222u8, 196u8, 114u8, 80u8, 165u8, 42u8, 140u8, 11u8, 183u8,
-            3u8, 142u8, 114u8, 250u8, 111u8, 250u8, 174u8, 51u8, 86u8, 47u8, 119u8, 205u8, 43u8,
-            98u8, 158u8, 247u8, 253u8, 66u8, 77u8
-        ]);
-        assert_eq!(parsed, expect);
+    #[test]
+    fn ignored_suffix_warning() {
+        let tokens = bytes(quote!(0x01u32));
+        let block = syn::parse2::<ExprBlock>(tokens).unwrap();
+        let expect: Stmt = parse_quote! {
+            const _: () = {
+                #[deprecated(note = "the `u32` suffix is ignored, the literal is 1 byte(s) rather than 4")]
+                struct Warning;
+                let _ = Warning;
+            };
+        };
+        assert_eq!(block.block.stmts[0], expect);
+
+        let tokens = bytes(quote!(0x00000001u32));
+        assert!(syn::parse2::<ExprArray>(tokens).is_ok());
+    }
+
+    #[test]
+    fn suffixes_and_underscores() {
+        let table: &[(TokenStream2, ExprArray)] = &[
+            (quote!(0x00_ff), parse_quote!([0u8, 255u8])),
+            (quote!(0x00ffu16), parse_quote!([0u8, 255u8])),
+            (quote!(0b0000_0001_1), parse_quote!([0u8, 3u8])),
+            (quote!(0b0000_0001_1u32), parse_quote!([0u8, 3u8])),
+            (quote!(0x01, 0x0203u16), parse_quote!([1u8, 2u8, 3u8])),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(array(bytes(t.0.clone())), t.1, "table entry: {}", i);
+        }
+    }
+
+    /// Checks that 8 MB hex literals and hex strings expand to all of their
+    /// bytes. The time to expand them is measured by the expansion benchmark.
+    #[test]
+    fn large_input() {
+        let len = 8 << 20;
+        let hex: String = (0..len * 2)
+            .map(|i| b"0123456789abcdef"[i % 16] as char)
+            .collect();
+        for source in [format!("0x{}", hex), format!("{:?}", hex)] {
+            let input: proc_macro2::TokenStream = source.parse().unwrap();
+            let parsed = syn::parse2::<Expr>(bytes(input)).unwrap();
+            let bytes = byte_string(&parsed);
+            assert_eq!(bytes.len(), len);
+            assert_eq!(bytes[..2], [0x01, 0x23]);
+            assert_eq!(bytes[len - 2..], [0xcd, 0xef]);
+        }
+    }
+
+    #[test]
+    fn hex_literal_input() {
+        // The examples of the hex! macro of the hex-literal crate.
+        let table: &[(_, ExprArray)] = &[
+            (quote!("0a1b2c3d"), parse_quote!([10u8, 27u8, 44u8, 61u8])),
+            (
+                quote!("0A 1B\t2C\r\n3D"),
+                parse_quote!([10u8, 27u8, 44u8, 61u8]),
+            ),
+            (
+                quote!(
+                    "00010203 04050607" // first half
+                    "08090a0b 0c0d0e0f" // second half
+                ),
+                parse_quote!([
+                    0u8, 1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 12u8, 13u8, 14u8,
+                    15u8
+                ]),
+            ),
+            (
+                quote!("00 aa" "bb cc"),
+                parse_quote!([0u8, 170u8, 187u8, 204u8]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = bytes(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
     }
 
     #[test]
@@ -162,12 +2331,19 @@ mod test {
             (quote!(0b0000001), parse_quote!([1u8])),
             (quote!(0b00000001), parse_quote!([1u8])),
             (quote!(0b000000001), parse_quote!([0u8, 1u8])),
+            // Base 8, with 3 bits per leading zero rounded up to whole bytes.
+            (quote!(0o377), parse_quote!([255u8])),
+            (quote!(0o0377), parse_quote!([0u8, 255u8])),
+            (quote!(0o00377), parse_quote!([0u8, 255u8])),
+            (quote!(0o000377), parse_quote!([0u8, 0u8, 255u8])),
+            (quote!(0o017), parse_quote!([15u8])),
+            (quote!(0o0), parse_quote!([0u8])),
+            (quote!(0o000), parse_quote!([0u8, 0u8])),
         ];
         for (i, t) in table.iter().cloned().enumerate() {
             let tokens = bytes(t.0);
-            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
             let expect = t.1;
-            assert_eq!(parsed, expect, "table entry: {}", i);
+            assert_eq!(array(tokens), expect, "table entry: {}", i);
         }
     }
 
@@ -176,13 +2352,11 @@ mod test {
         let table: &[(_, Result<ExprArray, Error>)] = &[
             // Base 8.
             (quote!(0o377), Ok(parse_quote!([255u8]))),
-            (quote!(0o0377), Err(Error::new(Span::call_site(), "leading zeros are not preserved or supported on integer literals in octal form"))),
-            (quote!(0o00377), Err(Error::new(Span::call_site(), "leading zeros are not preserved or supported on integer literals in octal form"))),
             (quote!(0o400), Ok(parse_quote!([1u8, 0u8]))),
             // Base 10.
             (quote!(255), Ok(parse_quote!([255u8]))),
-            (quote!(0255), Err(Error::new(Span::call_site(), "leading zeros are not preserved or supported on integer literals in decimal form"))),
-            (quote!(00255), Err(Error::new(Span::call_site(), "leading zeros are not preserved or supported on integer literals in decimal form"))),
+            (quote!(0255), Err(Error::new(Span::call_site(), "leading zeros are not preserved or supported on integer literals in decimal form, remove the zeros and use the `size` option for a fixed width, such as `255, size = 2`, or write the value in hex as `0xff`, where leading zero digits are preserved"))),
+            (quote!(00255), Err(Error::new(Span::call_site(), "leading zeros are not preserved or supported on integer literals in decimal form, remove the zeros and use the `size` option for a fixed width, such as `255, size = 3`, or write the value in hex as `0xff`, where leading zero digits are preserved"))),
             (quote!(256), Ok(parse_quote!([1u8, 0u8]))),
         ];
         for (i, t) in table.iter().enumerate() {
@@ -203,4 +2377,123 @@ mod test {
             };
         }
     }
+
+    #[test]
+    fn slice_ref() {
+        let tokens = bytes_ref(quote! {0x0102, 0x03});
+        let parsed = syn::parse2::<ExprBlock>(tokens).unwrap();
+        let expect: ExprBlock = parse_quote!({
+            const BYTES: &[u8] = &[1u8, 2u8, 3u8];
+            BYTES
+        });
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn vec() {
+        let tokens = bytesvec(quote! {0x0102});
+        let parsed = syn::parse2::<ExprCall>(tokens).unwrap();
+        let expect: ExprCall = if cfg!(feature = "alloc") {
+            parse_quote!(::alloc::vec::Vec::<u8>::from([1u8, 2u8]))
+        } else {
+            parse_quote!(::std::vec::Vec::<u8>::from([1u8, 2u8]))
+        };
+        assert_eq!(parsed, expect);
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn bytes_crate() {
+        let tokens = super::bytes_buf(quote! {0x0102});
+        let parsed = syn::parse2::<ExprBlock>(tokens).unwrap();
+        let expect: ExprBlock = parse_quote!({
+            static BYTES: [u8; 2usize] = [1u8, 2u8];
+            ::bytes::Bytes::from_static(&BYTES)
+        });
+        assert_eq!(parsed, expect);
+    }
+
+    #[cfg(feature = "xdr")]
+    #[test]
+    fn xdr_types() {
+        let zeros = quote!([
+            0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8,
+            0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 1u8
+        ]);
+        let table: &[(_, ExprCall)] = &[
+            (quote!(0x01; as Hash, size = 32), parse_quote!(Hash(#zeros))),
+            (
+                quote!(0x01, as stellar_xdr::curr::Uint256; size = 32),
+                parse_quote!(stellar_xdr::curr::Uint256(#zeros)),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = bytes(t.0);
+            let parsed = syn::parse2::<ExprCall>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+
+        let table = &[
+            (
+                quote!(0x01; as Hash),
+                "`Hash` wraps 32 bytes, found 1 byte(s), use `size = 32` to zero pad",
+            ),
+            (
+                quote!(0x00; 33; as Uint256),
+                "`Uint256` wraps 32 bytes, found 33 byte(s)",
+            ),
+            (
+                quote!(0x01; as Signature),
+                "unsupported output type, expected one of: Hash, Uint256",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn xdr_type_errors() {
+        let table = &[
+            (
+                quote!(0x01; as Hash, chunk = 1),
+                "option `chunk` cannot be used with an output type",
+            ),
+            (
+                quote!(0x01; as Hash, byte_str),
+                "option `byte_str` cannot be used with an output type",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+
+        let tokens = bytes_ref(quote!(0x01; as Hash));
+        let expect = Error::new(
+            Span::call_site(),
+            "an output type, `as Type`, is only supported by bytes!",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+
+        if !cfg!(feature = "xdr") {
+            let tokens = bytes(quote!(0x01; as Hash, size = 32));
+            let expect = Error::new(
+                Span::call_site(),
+                "output `as` a type requires the `xdr` feature of bytes-lit",
+            )
+            .to_compile_error()
+            .to_string();
+            assert_eq!(tokens.to_string(), expect);
+        }
+    }
 }
@@ -1,26 +1,40 @@
-use std::str::FromStr;
-
-use num_bigint::BigUint;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
 use syn::{Error, LitInt};
 
+use crate::{
+    bytes::{allowed_form, int_error, literal_text, raw_digits},
+    emit,
+};
+
 pub fn bytesmin(input: TokenStream2) -> TokenStream2 {
-    let lit = match syn::parse2::<LitInt>(input) {
-        Ok(lit) => lit,
-        Err(e) => return e.to_compile_error(),
-    };
-    let int = match BigUint::from_str(lit.base10_digits()) {
-        Ok(int) => int,
-        Err(_) => return Error::new(lit.span(), "negative values unsupported").to_compile_error(),
-    };
-    let bytes = int.to_bytes_be();
-    quote! { [#(#bytes),*] }
+    match syn::parse2::<LitInt>(input).and_then(|lit| min_bytes(&lit)) {
+        Ok(bytes) => emit::array(&bytes),
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn bytesmin_le(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<LitInt>(input).and_then(|lit| min_bytes(&lit)) {
+        Ok(mut bytes) => {
+            bytes.reverse();
+            emit::array(&bytes)
+        }
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Converts an integer literal into the fewest bytes that hold its value, most
+/// significant first.
+fn min_bytes(lit: &LitInt) -> Result<Vec<u8>, Error> {
+    let bytes =
+        bytes_lit_core::parse_int_literal_min(&literal_text(lit)).map_err(|e| int_error(lit, e))?;
+    allowed_form(lit, raw_digits(lit).form)?;
+    Ok(bytes)
 }
 
 #[cfg(test)]
 mod test {
-    use super::bytesmin;
+    use super::{bytesmin, bytesmin_le};
     use pretty_assertions::assert_eq;
     use proc_macro2::Span;
     use quote::quote;
@@ -29,9 +43,12 @@ mod test {
     #[test]
     fn neg() {
         let tokens = bytesmin(quote! {-0x1});
-        let expect = Error::new(Span::call_site(), "negative values unsupported")
-            .to_compile_error()
-            .to_string();
+        let expect = Error::new(
+            Span::call_site(),
+            "negative values unsupported, use ibytes! with `size = 1` for two's complement bytes",
+        )
+        .to_compile_error()
+        .to_string();
         assert_eq!(tokens.to_string(), expect);
     }
 
@@ -125,4 +142,19 @@ mod test {
             assert_eq!(parsed, expect, "table entry: {}", i);
         }
     }
+
+    #[test]
+    fn little_endian() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(0x0102), parse_quote!([2u8, 1u8])),
+            (quote!(0x000102), parse_quote!([2u8, 1u8])),
+            (quote!(65536), parse_quote!([0u8, 0u8, 1u8])),
+            (quote!(0), parse_quote!([0u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = bytesmin_le(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
 }
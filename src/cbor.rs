@@ -0,0 +1,84 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::Error;
+
+use crate::{bytes, diagnostic, emit};
+
+pub fn cbor_bytes(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<bytes::Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(input: bytes::Input) -> Result<TokenStream2, Error> {
+    let bytes::Output { bytes, warnings } = bytes::value(input)?;
+    let mut item = header(bytes.len() as u64);
+    item.extend(bytes);
+    Ok(diagnostic::with_warnings(&warnings, emit::array(&item)))
+}
+
+/// Returns the header of a CBOR byte string, major type 2, of the length. A
+/// length below 24 is held in the additional information of the initial byte,
+/// and a longer length follows it in the fewest of 1, 2, 4, or 8 bytes, big
+/// endian, with the additional information 24 to 27.
+pub fn header(len: u64) -> Vec<u8> {
+    const MAJOR: u8 = 2 << 5;
+    let bytes = len.to_be_bytes();
+    let (info, size) = match len {
+        0..=23 => return vec![MAJOR | len as u8],
+        24..=0xff => (24, 1),
+        0x100..=0xffff => (25, 2),
+        0x1_0000..=0xffff_ffff => (26, 4),
+        _ => (27, 8),
+    };
+    let mut header = vec![MAJOR | info];
+    header.extend_from_slice(&bytes[8 - size..]);
+    header
+}
+
+#[cfg(test)]
+mod test {
+    use super::{cbor_bytes, header};
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray};
+
+    #[test]
+    fn headers() {
+        let table: &[(u64, &[u8])] = &[
+            (0, &[0x40]),
+            (4, &[0x44]),
+            (23, &[0x57]),
+            (24, &[0x58, 24]),
+            (255, &[0x58, 0xff]),
+            (256, &[0x59, 0x01, 0x00]),
+            (65535, &[0x59, 0xff, 0xff]),
+            (65536, &[0x5a, 0x00, 0x01, 0x00, 0x00]),
+            (
+                1 << 32,
+                &[0x5b, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00],
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(header(t.0), t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn items() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(b""), parse_quote!([64u8])),
+            (quote!(0x01020304), parse_quote!([68u8, 1u8, 2u8, 3u8, 4u8])),
+            (quote!(0x00; 24), {
+                let zeros = vec![quote!(0u8); 24];
+                parse_quote!([88u8, 24u8, #(#zeros),*])
+            }),
+            (quote!(0x01, size = 2), parse_quote!([66u8, 0u8, 1u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = cbor_bytes(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+}
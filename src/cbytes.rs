@@ -0,0 +1,185 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::LitStr;
+
+use crate::{emit, error::StrError};
+
+pub fn cbytes_src(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<LitStr>(input).and_then(|lit| {
+        let bytes = decode(&lit.value()).map_err(|e| e.into_error(&lit))?;
+        Ok(emit::array(&bytes))
+    }) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Decodes a C array initializer, a brace delimited and comma separated list
+/// of C integer literals each no greater than 255. Hex, octal, binary, and
+/// decimal literals are accepted, with optional integer suffixes, as are a
+/// trailing comma and C comments.
+pub fn decode(s: &str) -> Result<Vec<u8>, StrError> {
+    let mut cursor = Cursor { s, i: 0 };
+    cursor.skip_space()?;
+    if !cursor.eat(b'{') {
+        return Err(StrError::new(cursor.i, "expected `{`"));
+    }
+    let mut bytes = Vec::new();
+    loop {
+        cursor.skip_space()?;
+        if cursor.eat(b'}') {
+            break;
+        }
+        bytes.push(cursor.int()?);
+        cursor.skip_space()?;
+        if cursor.eat(b'}') {
+            break;
+        }
+        if !cursor.eat(b',') {
+            return Err(StrError::new(cursor.i, "expected `,` or `}`"));
+        }
+    }
+    cursor.skip_space()?;
+    if cursor.i < s.len() {
+        return Err(StrError::new(cursor.i, "unexpected text after `}`"));
+    }
+    Ok(bytes)
+}
+
+struct Cursor<'a> {
+    s: &'a str,
+    i: usize,
+}
+
+impl Cursor<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.s.as_bytes().get(self.i).copied()
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.i += 1;
+        }
+        found
+    }
+
+    /// Skips whitespace and comments.
+    fn skip_space(&mut self) -> Result<(), StrError> {
+        loop {
+            let rest = &self.s[self.i..];
+            if let Some(c) = rest.chars().next().filter(|c| c.is_whitespace()) {
+                self.i += c.len_utf8();
+            } else if rest.starts_with("//") {
+                self.i += rest.find('\n').unwrap_or(rest.len());
+            } else if let Some(comment) = rest.strip_prefix("/*") {
+                let end = comment
+                    .find("*/")
+                    .ok_or_else(|| StrError::new(self.i, "unterminated comment"))?;
+                self.i += end + 4;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Parses a C integer literal that fits in a byte.
+    fn int(&mut self) -> Result<u8, StrError> {
+        let start = self.i;
+        let rest = &self.s.as_bytes()[start..];
+        let len = rest
+            .iter()
+            .take_while(|c| c.is_ascii_alphanumeric() || **c == b'\'')
+            .count();
+        if len == 0 {
+            return Err(StrError::new(start, "expected an integer literal"));
+        }
+        self.i += len;
+        let literal = &self.s[start..start + len];
+
+        // Remove any combination of unsigned and long suffixes.
+        let digits = literal.trim_end_matches(['u', 'U', 'l', 'L']);
+        // C23 allows ' digit separators.
+        let digits = digits.replace('\'', "");
+        let (radix, digits) = match digits.as_bytes() {
+            [b'0', b'x' | b'X', ..] => (16, &digits[2..]),
+            [b'0', b'b' | b'B', ..] => (2, &digits[2..]),
+            [b'0', _, ..] => (8, &digits[1..]),
+            _ => (10, &digits[..]),
+        };
+        let value = u64::from_str_radix(digits, radix)
+            .map_err(|_| StrError::new(start, format!("invalid integer literal {:?}", literal)))?;
+        u8::try_from(value)
+            .map_err(|_| StrError::new(start, format!("value {} is greater than 255", value)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{cbytes_src, decode};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn literals() {
+        let table: &[(&str, &[u8])] = &[
+            ("{}", &[]),
+            ("{0xDE, 0xAD, 0xBE}", &[0xde, 0xad, 0xbe]),
+            ("{ 0XffU, 10, 010, 0b11, 0 }", &[0xff, 10, 8, 3, 0]),
+            ("{1u, 2UL, 3llu, 4LL,}", &[1, 2, 3, 4]),
+            (
+                "
+                /* key */
+                {
+                    0x01, 0x02, // first
+                    0x03,       // second
+                }
+                ",
+                &[1, 2, 3],
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(decode(t.0).as_deref(), Ok(t.1), "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(&str, usize, &str)] = &[
+            ("0x01", 0, "expected `{`"),
+            ("{0x01 0x02}", 6, "expected `,` or `}`"),
+            ("{0x100}", 1, "value 256 is greater than 255"),
+            ("{0x01,,}", 6, "expected an integer literal"),
+            ("{09}", 1, "invalid integer literal \"09\""),
+            ("{1} 2", 4, "unexpected text after `}`"),
+            ("{1 /* 2}", 3, "unterminated comment"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                decode(t.0),
+                Err(StrError::new(t.1, t.2)),
+                "table entry: {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn macro_output() {
+        let tokens = cbytes_src(quote! {"{0xDE, 0xAD, 0xBE, 0xEF}"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([222u8, 173u8, 190u8, 239u8]);
+        assert_eq!(parsed, expect);
+
+        let tokens = cbytes_src(quote! {"{0x1FF}"});
+        let expect = Error::new(
+            Span::call_site(),
+            "value 511 is greater than 255 at index 1",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
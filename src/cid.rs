@@ -0,0 +1,193 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitStr,
+};
+
+use crate::{args::Args, base58, emit, multibase, multihash};
+
+struct Input {
+    lit: LitStr,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn cid(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let digest = input.args.flag("digest")?;
+    input.args.finish()?;
+    let cid = decode(&input.lit)?;
+    if digest {
+        Ok(emit::array(&cid.bytes[cid.digest..]))
+    } else {
+        Ok(emit::array(&cid.bytes))
+    }
+}
+
+/// A binary CID, and the index of the digest of its multihash.
+pub struct Cid {
+    pub bytes: Vec<u8>,
+    pub digest: usize,
+}
+
+/// Decodes a CID string. A CIDv0 is the base58btc encoding of a sha2_256
+/// multihash, starting `Qm`, and the binary CID is the multihash. A CIDv1 is a
+/// multibase string of the version, the content codec, and the multihash.
+pub fn decode(lit: &LitStr) -> Result<Cid, Error> {
+    let value = lit.value();
+    if value.len() == 46 && value.starts_with("Qm") {
+        let bytes = base58::decode(&value).map_err(|e| e.into_error(lit))?;
+        let digest = multihash_digest(lit, &bytes, 0)?;
+        return Ok(Cid { bytes, digest });
+    }
+
+    let bytes = multibase::decode(&value).map_err(|e| e.into_error(lit))?;
+    let mut pos = 0;
+    match read_varint(&bytes, &mut pos) {
+        Some(1) => {}
+        Some(version) => {
+            return Err(Error::new(
+                lit.span(),
+                format!("unsupported CID version {}, expected 0 or 1", version),
+            ))
+        }
+        None => return Err(truncated(lit)),
+    }
+    read_varint(&bytes, &mut pos).ok_or_else(|| truncated(lit))?;
+    let digest = multihash_digest(lit, &bytes, pos)?;
+    Ok(Cid { bytes, digest })
+}
+
+/// Validates the multihash at the index, that is the rest of the bytes, and
+/// returns the index of its digest.
+fn multihash_digest(lit: &LitStr, bytes: &[u8], mut pos: usize) -> Result<usize, Error> {
+    let code = read_varint(bytes, &mut pos).ok_or_else(|| truncated(lit))?;
+    let len = read_varint(bytes, &mut pos).ok_or_else(|| truncated(lit))?;
+    let found = bytes.len() - pos;
+    if len != found as u64 {
+        return Err(Error::new(
+            lit.span(),
+            format!(
+                "invalid multihash length, the length is {} bytes but the digest is {} bytes",
+                len, found
+            ),
+        ));
+    }
+    if let Some(code) = multihash::from_code(code) {
+        if found != code.len {
+            return Err(Error::new(
+                lit.span(),
+                format!(
+                    "invalid {} digest length, expected {} bytes, found {}",
+                    code.name, code.len, found
+                ),
+            ));
+        }
+    }
+    Ok(pos)
+}
+
+fn truncated(lit: &LitStr) -> Error {
+    Error::new(lit.span(), "CID is truncated")
+}
+
+/// Reads an unsigned varint of at most 64 bits at the position, advancing the
+/// position past it.
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let b = *bytes.get(*pos)?;
+        *pos += 1;
+        value |= u64::from(b & 0x7f) << shift;
+        if b & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::cid;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{Error, ExprArray};
+
+    const DIGEST: &str = "9d6c2be50f706953479ab9df2ce3edca90b68053c00b3004b7f0accbe1e8eedf";
+
+    fn array(hex: &str) -> ExprArray {
+        let bytes = crate::hex::decode(hex).unwrap();
+        syn::parse2(crate::emit::array(&bytes)).unwrap()
+    }
+
+    #[test]
+    fn versions() {
+        let table = &[
+            (
+                quote!("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+                format!("1220{}", DIGEST),
+            ),
+            (
+                quote!("bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34"),
+                format!("01701220{}", DIGEST),
+            ),
+            (
+                quote!("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG", digest),
+                DIGEST.to_string(),
+            ),
+            (
+                quote!(
+                    "bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34",
+                    digest
+                ),
+                DIGEST.to_string(),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = cid(t.0.clone());
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, array(&t.1), "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table = &[
+            // The multibase hex of version 2.
+            (
+                quote!("f0270122000"),
+                "unsupported CID version 2, expected 0 or 1",
+            ),
+            (quote!("f0170"), "CID is truncated"),
+            (
+                quote!("f017012200000"),
+                "invalid multihash length, the length is 32 bytes but the digest is 2 bytes",
+            ),
+            (
+                quote!("f017012020000"),
+                "invalid sha2_256 digest length, expected 32 bytes, found 2",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = cid(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
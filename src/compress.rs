@@ -0,0 +1,247 @@
+use miniz_oxide::deflate::{compress_to_vec, compress_to_vec_zlib};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    Attribute, Error, Ident, Token, Visibility,
+};
+
+use crate::{args::Args, bytes, diagnostic, emit, file};
+
+/// Input to the compressed bytes macro: attributes, visibility, and the name
+/// of the constant, followed by `=` or `,` and the data, which is the input of
+/// the bytes macro or a `file = "path"` option naming a file to read.
+struct Input {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    data: Data,
+}
+
+enum Data {
+    Bytes(Box<bytes::Input>),
+    File(Args),
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+        } else {
+            input.parse::<Token![,]>()?;
+        }
+        let fork = input.fork();
+        let is_file = fork.parse::<Ident>().map_or(false, |i| i == "file") && fork.peek(Token![=]);
+        let data = if is_file {
+            Data::File(Args::parse_only(input)?)
+        } else {
+            Data::Bytes(Box::new(input.parse()?))
+        };
+        Ok(Self {
+            attrs,
+            vis,
+            name,
+            data,
+        })
+    }
+}
+
+pub fn compressed_bytes(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Expands to a constant of the compressed bytes, and a constant of their
+/// length before compression, named with a `_LEN` suffix. Attributes other
+/// than docs are applied to both, so that a `cfg` removes both.
+fn expand(input: Input) -> Result<TokenStream2, Error> {
+    let Input {
+        attrs,
+        vis,
+        name,
+        data,
+    } = input;
+
+    let (level, zlib, bytes, warnings, tracked) = match data {
+        Data::Bytes(mut input) => {
+            let (level, zlib) = options(&mut input.args)?;
+            let bytes::Output { bytes, warnings } = bytes::value(*input)?;
+            (level, zlib, bytes, warnings, None)
+        }
+        Data::File(mut args) => {
+            let path = args.str("file")?.expect("file option");
+            let (level, zlib) = options(&mut args)?;
+            args.finish()?;
+            let (path, bytes) = file::read_bytes(&path)?;
+            (level, zlib, bytes, Vec::new(), Some(path))
+        }
+    };
+
+    let compressed = if zlib {
+        compress_to_vec_zlib(&bytes, level)
+    } else {
+        compress_to_vec(&bytes, level)
+    };
+    let compressed_len = compressed.len();
+    let array = emit::array(&compressed);
+    let len = bytes.len();
+    let len_name = format_ident!("{}_LEN", name);
+    let len_doc = format!(" The length of [`{}`] when decompressed.", name);
+    let len_attrs = attrs.iter().filter(|a| !a.path().is_ident("doc"));
+    // The file is included in an unused constant, so that the compiler tracks
+    // the file as a dependency, without the file being in the binary.
+    let tracked = tracked.map(|path| {
+        quote! {
+            const _: &[u8] = ::core::include_bytes!(#path);
+        }
+    });
+    let warnings = diagnostic::warnings(&warnings);
+    Ok(quote! {
+        #warnings
+        #tracked
+        #(#attrs)*
+        #vis const #name: [u8; #compressed_len] = #array;
+        #(#len_attrs)*
+        #[doc = #len_doc]
+        #vis const #len_name: usize = #len;
+    })
+}
+
+/// Takes the options of the compression: the level, 0 to 10 and by default
+/// the highest, and whether to wrap the compressed data in the zlib format.
+fn options(args: &mut Args) -> Result<(u8, bool), Error> {
+    let level = args.int::<u8>("level")?.unwrap_or(10);
+    if level > 10 {
+        return Err(Error::new(
+            Span::call_site(),
+            "level must be between 0 and 10",
+        ));
+    }
+    let zlib = args.flag("zlib")?;
+    Ok((level, zlib))
+}
+
+#[cfg(test)]
+mod test {
+    use super::compressed_bytes;
+    use miniz_oxide::inflate::{decompress_to_vec, decompress_to_vec_zlib};
+    use pretty_assertions::assert_eq;
+    use proc_macro2::{Span, TokenStream as TokenStream2};
+    use quote::quote;
+    use std::{env, fs};
+    use syn::{parse_quote, Error, Expr, File, Item, ItemConst, Lit};
+
+    /// Returns the constants emitted, skipping any other items.
+    fn constants(tokens: TokenStream2) -> Vec<ItemConst> {
+        let file = syn::parse2::<File>(tokens).unwrap();
+        file.items
+            .into_iter()
+            .filter_map(|item| match item {
+                Item::Const(item) if item.ident != "_" => Some(item),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the bytes of an array expression of u8 literals.
+    fn bytes(expr: &Expr) -> Vec<u8> {
+        match expr {
+            Expr::Array(array) => array
+                .elems
+                .iter()
+                .map(|elem| match elem {
+                    Expr::Lit(lit) => match &lit.lit {
+                        Lit::Int(int) => int.base10_parse().unwrap(),
+                        _ => panic!("expected an integer literal"),
+                    },
+                    _ => panic!("expected a literal"),
+                })
+                .collect(),
+            _ => panic!("expected an array"),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let consts = constants(compressed_bytes(quote! {
+            /// The blob.
+            #[cfg(test)]
+            pub BLOB = 0xdeadbeef; 1000
+        }));
+        assert_eq!(consts.len(), 2);
+        let compressed = bytes(&consts[0].expr);
+        assert!(compressed.len() < 100);
+        assert_eq!(
+            decompress_to_vec(&compressed).unwrap(),
+            [0xde, 0xad, 0xbe, 0xef].repeat(1000)
+        );
+        let expect: ItemConst = parse_quote! {
+            #[cfg(test)]
+            #[doc = " The length of [`BLOB`] when decompressed."]
+            pub const BLOB_LEN: usize = 4000usize;
+        };
+        assert_eq!(consts[1], expect);
+
+        let consts = constants(compressed_bytes(quote!(
+            BLOB,
+            "dead",
+            size = 4,
+            level = 0,
+            zlib
+        )));
+        assert_eq!(
+            decompress_to_vec_zlib(&bytes(&consts[0].expr)).unwrap(),
+            [0, 0, 0xde, 0xad]
+        );
+    }
+
+    #[test]
+    fn file() {
+        let path = env::temp_dir().join(format!("bytes-lit-{}-blob.bin", std::process::id()));
+        fs::write(&path, [0xff, 0xfe, 0x00, 0x80]).unwrap();
+        let path = path.to_str().unwrap();
+        let tokens = compressed_bytes(quote!(BLOB, file = #path, level = 9));
+        let file = syn::parse2::<File>(tokens.clone()).unwrap();
+        let expect: Item = parse_quote!(
+            const _: &[u8] = ::core::include_bytes!(#path);
+        );
+        assert_eq!(file.items[0], expect);
+        let consts = constants(tokens);
+        assert_eq!(
+            decompress_to_vec(&bytes(&consts[0].expr)).unwrap(),
+            [0xff, 0xfe, 0x00, 0x80]
+        );
+        let expect: Expr = parse_quote!(4usize);
+        assert_eq!(*consts[1].expr, expect);
+    }
+
+    #[test]
+    fn errors() {
+        let table = &[
+            (
+                quote!(BLOB = 0x00, level = 11),
+                "level must be between 0 and 10",
+            ),
+            (
+                quote!(BLOB, file = "blob.bin", size = 4),
+                "unknown option `size`",
+            ),
+            (
+                quote!(BLOB = 0x00, zlib = 1),
+                "option `zlib` is a flag and does not take a value",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = compressed_bytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, Ident, Lit,
+};
+
+use crate::{args::Args, emit, hash::message};
+
+/// A CRC algorithm, parameterized as in the catalogue of parametrised CRC
+/// algorithms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Algorithm {
+    width: u32,
+    poly: u32,
+    init: u32,
+    reflect: bool,
+    xorout: u32,
+}
+
+/// CRC-32/ISO-HDLC, as used by Ethernet, zip, and PNG.
+pub const CRC32: Algorithm = Algorithm {
+    width: 32,
+    poly: 0x04c1_1db7,
+    init: 0xffff_ffff,
+    reflect: true,
+    xorout: 0xffff_ffff,
+};
+
+/// CRC-16/IBM-3740, commonly known as CRC-16/CCITT-FALSE.
+pub const CRC16_CCITT: Algorithm = Algorithm {
+    width: 16,
+    poly: 0x1021,
+    init: 0xffff,
+    reflect: false,
+    xorout: 0,
+};
+
+/// CRC-16/XMODEM, as used by Stellar strkeys.
+pub const CRC16_XMODEM: Algorithm = Algorithm {
+    width: 16,
+    poly: 0x1021,
+    init: 0,
+    reflect: false,
+    xorout: 0,
+};
+
+impl Algorithm {
+    /// Returns the algorithm for the name of a preset: `crc32`, `crc16` (an
+    /// alias of `crc16_ccitt`), `crc16_ccitt`, or `crc16_xmodem`.
+    pub fn from_name(name: &Ident) -> Result<Self, Error> {
+        match name.to_string().as_str() {
+            "crc32" => Ok(CRC32),
+            "crc16" | "crc16_ccitt" => Ok(CRC16_CCITT),
+            "crc16_xmodem" => Ok(CRC16_XMODEM),
+            _ => Err(Error::new(
+                name.span(),
+                "unsupported CRC, expected one of: crc32, crc16, crc16_ccitt, crc16_xmodem",
+            )),
+        }
+    }
+
+    /// Computes the CRC of the data.
+    pub fn checksum(&self, data: &[u8]) -> u32 {
+        let top = 1u32 << (self.width - 1);
+        let mask = if self.width == 32 {
+            u32::MAX
+        } else {
+            (1 << self.width) - 1
+        };
+        let mut crc = self.init;
+        for b in data {
+            let b = if self.reflect { b.reverse_bits() } else { *b };
+            crc ^= u32::from(b) << (self.width - 8);
+            for _ in 0..8 {
+                crc = if crc & top != 0 {
+                    crc << 1 ^ self.poly
+                } else {
+                    crc << 1
+                };
+            }
+            crc &= mask;
+        }
+        if self.reflect {
+            crc = crc.reverse_bits() >> (32 - self.width);
+        }
+        crc ^ self.xorout
+    }
+
+    /// Computes the CRC of the data as big endian bytes.
+    pub fn checksum_bytes(&self, data: &[u8]) -> Vec<u8> {
+        let len = (self.width / 8) as usize;
+        self.checksum(data).to_be_bytes()[4 - len..].to_vec()
+    }
+}
+
+struct Input {
+    lit: Lit,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn crc32(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(|input| expand(input, CRC32, &[])) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn crc16(input: TokenStream2) -> TokenStream2 {
+    let presets = [("ccitt", CRC16_CCITT), ("xmodem", CRC16_XMODEM)];
+    match syn::parse2::<Input>(input).and_then(|input| expand(input, CRC16_CCITT, &presets)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(
+    mut input: Input,
+    default: Algorithm,
+    presets: &[(&str, Algorithm)],
+) -> Result<TokenStream2, Error> {
+    let algorithm = if presets.is_empty() {
+        default
+    } else {
+        let names = presets.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        match input.args.ident("preset", &names)? {
+            Some(preset) => presets
+                .iter()
+                .find(|(name, _)| preset == name)
+                .map(|(_, a)| *a)
+                .unwrap_or(default),
+            None => default,
+        }
+    };
+    let little = input
+        .args
+        .ident("endian", &["big", "little"])?
+        .map_or(false, |e| e == "little");
+    input.args.finish()?;
+
+    let mut crc = algorithm.checksum_bytes(&message(&input.lit)?);
+    if little {
+        crc.reverse();
+    }
+    Ok(emit::array(&crc))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{crc16, crc32, CRC16_CCITT, CRC16_XMODEM, CRC32};
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray};
+
+    #[test]
+    fn check_values() {
+        // The check value of each algorithm is the CRC of "123456789".
+        assert_eq!(CRC32.checksum(b"123456789"), 0xcbf4_3926);
+        assert_eq!(CRC16_CCITT.checksum(b"123456789"), 0x29b1);
+        assert_eq!(CRC16_XMODEM.checksum(b"123456789"), 0x31c3);
+    }
+
+    #[test]
+    fn macros() {
+        let table: &[(_, ExprArray)] = &[
+            (
+                crc32(quote!("123456789")),
+                parse_quote!([203u8, 244u8, 57u8, 38u8]),
+            ),
+            (
+                crc32(quote!("123456789", endian = little)),
+                parse_quote!([38u8, 57u8, 244u8, 203u8]),
+            ),
+            (crc16(quote!("123456789")), parse_quote!([41u8, 177u8])),
+            (
+                crc16(quote!(b"123456789", preset = xmodem)),
+                parse_quote!([49u8, 195u8]),
+            ),
+            (crc16(quote!(0x00)), parse_quote!([225u8, 240u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let parsed = syn::parse2::<ExprArray>(t.0).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+}
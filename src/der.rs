@@ -0,0 +1,272 @@
+use std::str::FromStr;
+
+use num_bigint::{BigInt, BigUint};
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitInt, LitStr,
+};
+
+use crate::{args::Args, emit, error::StrError};
+
+/// The DER tag of an INTEGER.
+const TAG_INTEGER: u8 = 0x02;
+
+/// The DER tag of an OBJECT IDENTIFIER.
+const TAG_OID: u8 = 0x06;
+
+struct OidInput {
+    lit: LitStr,
+    args: Args,
+}
+
+impl Parse for OidInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn der_int(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<LitInt>(input) {
+        Ok(lit) => {
+            // The base10 digits of a literal validated by syn always parse,
+            // with a leading minus sign for negative values.
+            let int = BigInt::from_str(lit.base10_digits()).expect("valid integer");
+            emit::array(&encode_int(&int))
+        }
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn oid(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<OidInput>(input).and_then(expand_oid) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand_oid(mut input: OidInput) -> Result<TokenStream2, Error> {
+    let tag = input.args.flag("tag")?;
+    input.args.finish()?;
+    let content = encode_oid(&input.lit.value()).map_err(|e| e.into_error(&input.lit))?;
+    if tag {
+        Ok(emit::array(&encode(TAG_OID, &content)))
+    } else {
+        Ok(emit::array(&content))
+    }
+}
+
+/// Encodes a dotted OID as the content octets of a DER OBJECT IDENTIFIER. The
+/// first two arcs are packed into one value, 40 times the first plus the
+/// second, and each value is encoded in base 128, most significant group
+/// first, with the high bit set on every byte but the last.
+pub fn encode_oid(s: &str) -> Result<Vec<u8>, StrError> {
+    let mut arcs = Vec::new();
+    let mut start = 0;
+    for part in s.split('.') {
+        if part.is_empty() || !part.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(StrError::new(
+                start,
+                "invalid OID arc, expected decimal digits",
+            ));
+        }
+        if part.len() > 1 && part.starts_with('0') {
+            return Err(StrError::new(start, "invalid OID arc with leading zeros"));
+        }
+        let arc = BigUint::from_str(part).expect("decimal digits");
+        arcs.push((start, arc));
+        start += part.len() + 1;
+    }
+    if arcs.len() < 2 {
+        return Err(StrError::new(
+            s.len(),
+            "invalid OID, expected at least two arcs",
+        ));
+    }
+
+    let (_, first) = &arcs[0];
+    let (second_index, second) = &arcs[1];
+    if *first > BigUint::from(2u8) {
+        return Err(StrError::new(
+            0,
+            "invalid first OID arc, expected 0, 1, or 2",
+        ));
+    }
+    if *first < BigUint::from(2u8) && *second >= BigUint::from(40u8) {
+        return Err(StrError::new(
+            *second_index,
+            "invalid second OID arc, expected 0 to 39 when the first arc is 0 or 1",
+        ));
+    }
+
+    let mut bytes = base128(&(first * 40u8 + second));
+    for (_, arc) in &arcs[2..] {
+        bytes.extend(base128(arc));
+    }
+    Ok(bytes)
+}
+
+/// Encodes a value in base 128, most significant group first, with the high
+/// bit set on every byte but the last.
+fn base128(int: &BigUint) -> Vec<u8> {
+    let mut groups = int.to_radix_be(128);
+    let last = groups.len() - 1;
+    for g in &mut groups[..last] {
+        *g |= 0x80;
+    }
+    groups
+}
+
+/// Encodes an integer as a DER INTEGER, the minimal two's complement bytes of
+/// the value, so a non-negative value whose high bit is set is preceded by a
+/// zero byte. Leading zeros of the literal are not preserved.
+pub fn encode_int(int: &BigInt) -> Vec<u8> {
+    encode(TAG_INTEGER, &int.to_signed_bytes_be())
+}
+
+/// Encodes the tag, the definite length of the content, and the content.
+pub fn encode(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![tag];
+    bytes.extend(encode_length(content.len()));
+    bytes.extend(content);
+    bytes
+}
+
+/// Encodes a definite length, in the short form of one byte for lengths below
+/// 128, otherwise in the long form of the number of length bytes with the high
+/// bit set, followed by the length in big endian order.
+pub fn encode_length(len: usize) -> Vec<u8> {
+    if len < 0x80 {
+        return vec![len as u8];
+    }
+    let len = BigUint::from(len).to_bytes_be();
+    let mut bytes = vec![0x80 | len.len() as u8];
+    bytes.extend(len);
+    bytes
+}
+
+#[cfg(test)]
+mod test {
+    use super::{der_int, encode_length, encode_oid, oid};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray};
+
+    #[test]
+    fn integers() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(0), parse_quote!([2u8, 1u8, 0u8])),
+            (quote!(127), parse_quote!([2u8, 1u8, 127u8])),
+            (quote!(128), parse_quote!([2u8, 2u8, 0u8, 128u8])),
+            (quote!(0x00FF), parse_quote!([2u8, 2u8, 0u8, 255u8])),
+            (quote!(0x0100), parse_quote!([2u8, 2u8, 1u8, 0u8])),
+            (quote!(-1), parse_quote!([2u8, 1u8, 255u8])),
+            (quote!(-128), parse_quote!([2u8, 1u8, 128u8])),
+            (quote!(-129), parse_quote!([2u8, 2u8, 255u8, 127u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = der_int(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn lengths() {
+        let table: &[(usize, &[u8])] = &[
+            (0, &[0x00]),
+            (127, &[0x7f]),
+            (128, &[0x81, 0x80]),
+            (255, &[0x81, 0xff]),
+            (256, &[0x82, 0x01, 0x00]),
+            (65536, &[0x83, 0x01, 0x00, 0x00]),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(encode_length(t.0), t.1, "table entry: {}", i);
+        }
+
+        // A 129 byte value has a long form length.
+        let tokens = der_int(quote!(
+            0x8000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000
+        ));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        assert_eq!(parsed.elems.len(), 132);
+        let prefix: ExprArray = parse_quote!([2u8, 129u8, 129u8, 0u8, 128u8]);
+        assert_eq!(
+            parsed.elems.iter().take(5).collect::<Vec<_>>(),
+            prefix.elems.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn oids() {
+        let table: &[(&str, &[u8])] = &[
+            (
+                "1.2.840.113549.1.1.11",
+                &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b],
+            ),
+            ("2.5.4.3", &[0x55, 0x04, 0x03]),
+            ("0.0", &[0x00]),
+            ("2.999.3", &[0x88, 0x37, 0x03]),
+            (
+                "1.3.6.1.4.1.311.21.20",
+                &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x15, 0x14],
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(encode_oid(t.0).as_deref(), Ok(t.1), "table entry: {}", i);
+        }
+
+        let tokens = oid(quote!("2.5.4.3", tag));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([6u8, 3u8, 85u8, 4u8, 3u8]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn oid_errors() {
+        let table: &[(&str, StrError)] = &[
+            (
+                "1",
+                StrError::new(1, "invalid OID, expected at least two arcs"),
+            ),
+            (
+                "",
+                StrError::new(0, "invalid OID arc, expected decimal digits"),
+            ),
+            (
+                "1.2.",
+                StrError::new(4, "invalid OID arc, expected decimal digits"),
+            ),
+            (
+                "1.2.a",
+                StrError::new(4, "invalid OID arc, expected decimal digits"),
+            ),
+            (
+                "1.2.-1",
+                StrError::new(4, "invalid OID arc, expected decimal digits"),
+            ),
+            (
+                "1.02",
+                StrError::new(2, "invalid OID arc with leading zeros"),
+            ),
+            (
+                "3.1",
+                StrError::new(0, "invalid first OID arc, expected 0, 1, or 2"),
+            ),
+            (
+                "1.40",
+                StrError::new(
+                    2,
+                    "invalid second OID arc, expected 0 to 39 when the first arc is 0 or 1",
+                ),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(encode_oid(t.0).as_ref(), Err(&t.1), "table entry: {}", i);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+
+/// A warning about the input of a macro, that does not stop the macro from
+/// expanding.
+pub struct Warning {
+    pub span: Span,
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+/// Emits the warnings, returning items to place in the output of the macro.
+///
+/// With the nightly feature, inside a macro, the warnings are emitted as
+/// compiler diagnostics and no items are needed. Otherwise each warning is the
+/// deprecation note of an item that is then used, which the compiler reports
+/// as a warning at the span of the warning.
+pub fn warnings(warnings: &[Warning]) -> TokenStream2 {
+    warnings.iter().map(warning).collect()
+}
+
+/// Emits the warnings, returning the expression with any items needed to emit
+/// the warnings.
+pub fn with_warnings(warnings: &[Warning], expr: TokenStream2) -> TokenStream2 {
+    if warnings.is_empty() {
+        return expr;
+    }
+    let items = self::warnings(warnings);
+    quote! {{
+        #items
+        #expr
+    }}
+}
+
+fn warning(warning: &Warning) -> TokenStream2 {
+    #[cfg(feature = "nightly")]
+    if proc_macro::is_available() {
+        warning.span.unwrap().warning(&warning.message).emit();
+        return TokenStream2::new();
+    }
+
+    let message = &warning.message;
+    quote_spanned! {warning.span=>
+        const _: () = {
+            #[deprecated(note = #message)]
+            struct Warning;
+            let _ = Warning;
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{with_warnings, Warning};
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, ExprBlock};
+
+    #[test]
+    fn deprecation_notes() {
+        let expr = quote!([1u8]);
+        assert_eq!(
+            with_warnings(&[], expr.clone()).to_string(),
+            expr.to_string()
+        );
+
+        let warnings = [Warning::new(Span::call_site(), "careful")];
+        let block: ExprBlock = syn::parse2(with_warnings(&warnings, expr)).unwrap();
+        let expect: ExprBlock = parse_quote! {{
+            const _: () = {
+                #[deprecated(note = "careful")]
+                struct Warning;
+                let _ = Warning;
+            };
+            [1u8]
+        }};
+        assert_eq!(block, expect);
+    }
+}
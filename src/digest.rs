@@ -0,0 +1,103 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::LitStr;
+
+use crate::{emit, error::StrError, hex};
+
+pub fn hash20(input: TokenStream2) -> TokenStream2 {
+    fixed(input, 20)
+}
+
+pub fn hash32(input: TokenStream2) -> TokenStream2 {
+    fixed(input, 32)
+}
+
+fn fixed(input: TokenStream2, len: usize) -> TokenStream2 {
+    let lit = match syn::parse2::<LitStr>(input) {
+        Ok(lit) => lit,
+        Err(e) => return e.to_compile_error(),
+    };
+    match decode(&lit.value(), len) {
+        Ok(bytes) => emit::array(&bytes),
+        Err(e) => e.into_error(&lit).to_compile_error(),
+    }
+}
+
+/// Decodes a hash of exactly the length in bytes from its hex digits, so that
+/// a truncated or extended copy of a hash is an error.
+pub fn decode(s: &str, len: usize) -> Result<Vec<u8>, StrError> {
+    let digits = s.len();
+    if digits != len * 2 {
+        return Err(StrError::new(
+            digits.min(len * 2),
+            format!(
+                "invalid hash length, expected {} hex digits for {} bytes, found {}",
+                len * 2,
+                len,
+                digits
+            ),
+        ));
+    }
+    hex::decode(s)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode, hash20, hash32};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{Error, ExprArray};
+
+    #[test]
+    fn lengths() {
+        let tokens = hash20(quote!("da39a3ee5e6b4b0d3255bfef95601890afd80709"));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        assert_eq!(parsed.elems.len(), 20);
+
+        let tokens = hash32(quote!(
+            "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855"
+        ));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        assert_eq!(parsed.elems.len(), 32);
+
+        let tokens = hash20(quote!("da39a3ee5e6b4b0d3255bfef95601890afd807"));
+        let expect = Error::new(
+            Span::call_site(),
+            "invalid hash length, expected 40 hex digits for 20 bytes, found 38 at index 38",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(&str, usize, StrError)] = &[
+            (
+                "da39a3ee5e6b4b0d3255bfef95601890afd807090",
+                20,
+                StrError::new(
+                    40,
+                    "invalid hash length, expected 40 hex digits for 20 bytes, found 41",
+                ),
+            ),
+            (
+                "da39a3ee5e6b4b0d3255bfef95601890afd80709",
+                32,
+                StrError::new(
+                    40,
+                    "invalid hash length, expected 64 hex digits for 32 bytes, found 40",
+                ),
+            ),
+            (
+                "da39a3ee5e6b4b0d3255bfef95601890afd8070g",
+                20,
+                StrError::new(39, "invalid hex character 'g'"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(decode(t.0, t.1).as_ref(), Err(&t.2), "table entry: {}", i);
+        }
+    }
+}
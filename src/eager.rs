@@ -0,0 +1,195 @@
+use syn::{
+    buffer::Cursor, parse::ParseStream, punctuated::Punctuated, Error, Expr, Lit, LitStr, Macro,
+    Token, UnOp,
+};
+
+/// The builtin macros that are expanded eagerly inside the input of a macro.
+const MACROS: &[&str] = &["concat", "env", "stringify"];
+
+/// Returns true if the next tokens are a call of a builtin macro that is
+/// expanded eagerly: `concat!`, `env!`, or `stringify!`.
+pub fn peek(cursor: Cursor) -> bool {
+    match cursor.ident() {
+        Some((ident, rest)) => {
+            MACROS.iter().any(|name| ident == name)
+                && rest
+                    .punct()
+                    .map_or(false, |(punct, _)| punct.as_char() == '!')
+        }
+        None => false,
+    }
+}
+
+/// Parses a call of a builtin macro and expands it to the string literal the
+/// compiler would produce, spanning the call.
+pub fn parse(input: ParseStream) -> syn::Result<LitStr> {
+    let mac: Macro = input.parse()?;
+    let value = expand(&mac)?;
+    Ok(LitStr::new(&value, mac.path.segments[0].ident.span()))
+}
+
+fn expand(mac: &Macro) -> Result<String, Error> {
+    let name = match mac.path.get_ident() {
+        Some(ident) => ident.to_string(),
+        None => String::new(),
+    };
+    match name.as_str() {
+        "concat" => {
+            let args = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+            let mut value = String::new();
+            for arg in &args {
+                value.push_str(&concat_arg(arg)?);
+            }
+            Ok(value)
+        }
+        "env" => {
+            let args = mac.parse_body_with(Punctuated::<LitStr, Token![,]>::parse_terminated)?;
+            let (key, message) = match (args.first(), args.len()) {
+                (Some(key), 1) => (key, None),
+                (Some(key), 2) => (key, Some(&args[1])),
+                _ => {
+                    return Err(Error::new_spanned(
+                        mac,
+                        "`env!` takes one or two string literal arguments",
+                    ))
+                }
+            };
+            let name = key.value();
+            match std::env::var(&name) {
+                Ok(value) => Ok(value),
+                Err(std::env::VarError::NotUnicode(_)) => Err(Error::new(
+                    key.span(),
+                    format!("environment variable `{}` is not valid unicode", name),
+                )),
+                Err(std::env::VarError::NotPresent) => Err(Error::new(
+                    key.span(),
+                    match message {
+                        Some(message) => message.value(),
+                        None => {
+                            format!(
+                                "environment variable `{}` not defined at compile time",
+                                name
+                            )
+                        }
+                    },
+                )),
+            }
+        }
+        "stringify" => Ok(mac.tokens.to_string()),
+        _ => Err(Error::new_spanned(
+            &mac.path,
+            "unsupported macro, only `concat!`, `env!`, and `stringify!` are expanded",
+        )),
+    }
+}
+
+/// Returns the string of an argument of `concat!`: a literal, a negated number,
+/// or a call of another builtin macro.
+fn concat_arg(arg: &Expr) -> Result<String, Error> {
+    match arg {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Str(s) => Ok(s.value()),
+            Lit::Char(c) => Ok(c.value().to_string()),
+            Lit::Int(int) => Ok(int.base10_digits().to_string()),
+            Lit::Float(float) => Ok(float.base10_digits().to_string()),
+            Lit::Bool(b) => Ok(b.value.to_string()),
+            _ => Err(Error::new_spanned(
+                arg,
+                "`concat!` cannot concatenate byte string or byte literals",
+            )),
+        },
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => match &*unary.expr {
+            Expr::Lit(lit) if matches!(lit.lit, Lit::Int(_) | Lit::Float(_)) => {
+                Ok(format!("-{}", concat_arg(&unary.expr)?))
+            }
+            _ => Err(expected_literal(arg)),
+        },
+        Expr::Group(group) => concat_arg(&group.expr),
+        Expr::Macro(mac) => expand(&mac.mac),
+        _ => Err(expected_literal(arg)),
+    }
+}
+
+fn expected_literal(arg: &Expr) -> Error {
+    Error::new_spanned(arg, "expected a literal in `concat!`")
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::TokenStream as TokenStream2;
+    use quote::quote;
+    use syn::parse::Parser;
+
+    fn expand(tokens: TokenStream2) -> Result<String, String> {
+        parse
+            .parse2(tokens)
+            .map(|lit| lit.value())
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn values() {
+        std::env::set_var("BYTES_LIT_TEST_EAGER", "beef");
+        let table: &[(_, &str)] = &[
+            (quote!(concat!("dead", "beef")), "deadbeef"),
+            (
+                quote!(concat!("de", 'a', 0xd, -1, 2.5, true,)),
+                "dea13-12.5true",
+            ),
+            (quote!(concat!()), ""),
+            (
+                quote!(concat!("dead", env!("BYTES_LIT_TEST_EAGER"))),
+                "deadbeef",
+            ),
+            (quote!(concat!(stringify!(de), concat!("ad"))), "dead"),
+            (quote!(env!("BYTES_LIT_TEST_EAGER")), "beef"),
+            (quote!(env!("BYTES_LIT_TEST_EAGER", "message")), "beef"),
+            (quote!(stringify!(dead)), "dead"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                expand(t.0.clone()),
+                Ok(t.1.to_string()),
+                "table entry: {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(_, &str)] = &[
+            (
+                quote!(env!("BYTES_LIT_TEST_EAGER_UNSET")),
+                "environment variable `BYTES_LIT_TEST_EAGER_UNSET` not defined at compile time",
+            ),
+            (
+                quote!(env!("BYTES_LIT_TEST_EAGER_UNSET", "set the key")),
+                "set the key",
+            ),
+            (
+                quote!(env!()),
+                "`env!` takes one or two string literal arguments",
+            ),
+            (quote!(concat!(x)), "expected a literal in `concat!`"),
+            (
+                quote!(concat!(b"de")),
+                "`concat!` cannot concatenate byte string or byte literals",
+            ),
+            (
+                quote!(concat!(include_str!("key"))),
+                "unsupported macro, only `concat!`, `env!`, and `stringify!` are expanded",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                expand(t.0.clone()),
+                Err(t.1.to_string()),
+                "table entry: {}",
+                i
+            );
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::Error;
+
+use crate::{bytes, diagnostic, emit};
+
+/// The schemes of error detection and correction.
+const SCHEMES: &[&str] = &["even_parity", "odd_parity", "hamming74", "secded"];
+
+pub fn ecc_bytes(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<bytes::Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Expands to the bytes protected by the scheme given.
+fn expand(mut input: bytes::Input) -> Result<TokenStream2, Error> {
+    let scheme = input.args.ident("scheme", SCHEMES)?.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            format!(
+                "ecc_bytes! requires the `scheme` option, one of: {}",
+                SCHEMES.join(", ")
+            ),
+        )
+    })?;
+    let bytes::Output { bytes, warnings } = bytes::value(input)?;
+    let encoded = match scheme.to_string().as_str() {
+        "even_parity" => parity(&bytes, false),
+        "odd_parity" => parity(&bytes, true),
+        "hamming74" => nibbles(&bytes, hamming74),
+        _ => nibbles(&bytes, secded),
+    };
+    Ok(diagnostic::with_warnings(&warnings, emit::array(&encoded)))
+}
+
+/// Returns the bytes followed by their parity bits, packed eight to a byte
+/// with the parity of the first byte in the most significant bit. The parity
+/// bit makes the number of one bits of a byte and its parity bit even, or odd.
+fn parity(bytes: &[u8], odd: bool) -> Vec<u8> {
+    let mut encoded = bytes.to_vec();
+    for chunk in bytes.chunks(8) {
+        let mut bits = 0u8;
+        for (i, b) in chunk.iter().enumerate() {
+            if (b.count_ones() % 2 == 1) != odd {
+                bits |= 0x80 >> i;
+            }
+        }
+        encoded.push(bits);
+    }
+    encoded
+}
+
+/// Encodes each nibble of the bytes, the high nibble first, into a byte.
+fn nibbles(bytes: &[u8], encode: fn(u8) -> u8) -> Vec<u8> {
+    bytes
+        .iter()
+        .flat_map(|b| [encode(b >> 4), encode(b & 0x0f)])
+        .collect()
+}
+
+/// Encodes a nibble as a Hamming(7,4) codeword, in the low seven bits of a
+/// byte in the order of the positions of the code, p1 p2 d1 p3 d2 d3 d4, with
+/// d1 the most significant bit of the nibble.
+fn hamming74(nibble: u8) -> u8 {
+    let d = |i: u8| nibble >> (3 - i) & 1;
+    let p1 = d(0) ^ d(1) ^ d(3);
+    let p2 = d(0) ^ d(2) ^ d(3);
+    let p3 = d(1) ^ d(2) ^ d(3);
+    p1 << 6 | p2 << 5 | d(0) << 4 | p3 << 3 | d(1) << 2 | d(2) << 1 | d(3)
+}
+
+/// Encodes a nibble as an extended Hamming(8,4) codeword, which corrects
+/// single bit errors and detects double bit errors: the Hamming(7,4) codeword
+/// with a parity bit of the whole codeword in the most significant bit.
+fn secded(nibble: u8) -> u8 {
+    let code = hamming74(nibble);
+    (code.count_ones() as u8 & 1) << 7 | code
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ecc_bytes, hamming74, secded};
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn codewords() {
+        // The codewords of Hamming(7,4), which differ in at least three bits,
+        // and of Hamming(8,4), which differ in at least four bits.
+        for a in 0..16 {
+            assert_eq!(hamming74(a) & 0x80, 0);
+            assert_eq!(secded(a).count_ones() % 2, 0);
+            for b in 0..a {
+                assert!((hamming74(a) ^ hamming74(b)).count_ones() >= 3);
+                assert!((secded(a) ^ secded(b)).count_ones() >= 4);
+            }
+        }
+    }
+
+    #[test]
+    fn schemes() {
+        let table: &[(_, ExprArray)] = &[
+            (
+                quote!(0x0103; scheme = even_parity),
+                parse_quote!([1u8, 3u8, 128u8]),
+            ),
+            (
+                quote!(0x0103; scheme = odd_parity),
+                parse_quote!([1u8, 3u8, 64u8]),
+            ),
+            (
+                quote!(0x00; 9, scheme = odd_parity),
+                parse_quote!([0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 255u8, 128u8]),
+            ),
+            // The nibble 0b1011 is the codeword 0b0110011.
+            (quote!(0xb0; scheme = hamming74), parse_quote!([51u8, 0u8])),
+            (quote!(0x0f; scheme = hamming74), parse_quote!([0u8, 127u8])),
+            // The nibble 0b1000 is the codeword 0b1110000, with three one bits.
+            (quote!(0x80; scheme = secded), parse_quote!([240u8, 0u8])),
+            (quote!(0x01; scheme = secded), parse_quote!([0u8, 105u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = ecc_bytes(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table = &[
+            (
+                quote!(0x01),
+                "ecc_bytes! requires the `scheme` option, one of: even_parity, odd_parity, hamming74, secded",
+            ),
+            (
+                quote!(0x01; scheme = crc),
+                "option `scheme` requires one of: even_parity, odd_parity, hamming74, secded",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = ecc_bytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
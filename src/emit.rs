@@ -0,0 +1,61 @@
+use num_bigint::{BigInt, Sign};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::LitByteStr;
+
+/// The number of bytes above which arrays are emitted as a dereferenced byte
+/// string literal, which is one token, rather than as one token per byte.
+pub const BYTE_STRING_THRESHOLD: usize = 256;
+
+/// Emits bytes as an array expression of `u8` literals, or as a byte string
+/// literal if there are more bytes than the threshold.
+///
+/// An empty slice is emitted as `[0u8; 0]` so that the element type of the
+/// array can still be inferred.
+pub fn array(bytes: &[u8]) -> TokenStream2 {
+    if bytes.is_empty() {
+        return quote! { [0u8; 0] };
+    }
+    if bytes.len() > BYTE_STRING_THRESHOLD {
+        return byte_string(bytes);
+    }
+    elements(bytes)
+}
+
+/// Emits bytes as an array of `u8` literals regardless of the number of bytes,
+/// which is valid as an expression and as a pattern.
+pub fn elements(bytes: &[u8]) -> TokenStream2 {
+    quote! { [#(#bytes),*] }
+}
+
+/// Emits bytes as a dereferenced byte string literal, `*b"..."`, which has the
+/// same `[u8; N]` type and value as the array of the bytes.
+pub fn byte_string(bytes: &[u8]) -> TokenStream2 {
+    let lit = LitByteStr::new(bytes, Span::call_site());
+    quote! { *#lit }
+}
+
+/// Emits bytes as an array expression of rows, each row an array of the given
+/// size emitted by the row function. The number of bytes must be a multiple of
+/// the size.
+pub fn chunks(bytes: &[u8], size: usize, row: fn(&[u8]) -> TokenStream2) -> TokenStream2 {
+    if bytes.is_empty() {
+        return quote! { [[0u8; #size]; 0] };
+    }
+    let rows = bytes.chunks(size).map(row);
+    quote! { [#(#rows),*] }
+}
+
+/// Returns the two's complement bytes of the integer in the size, the minimal
+/// representation sign extended, or the minimal number of bytes if it does
+/// not fit.
+pub fn signed_bytes(int: &BigInt, size: usize) -> Result<Vec<u8>, usize> {
+    let min = int.to_signed_bytes_be();
+    if min.len() > size {
+        return Err(min.len());
+    }
+    let fill = if int.sign() == Sign::Minus { 0xff } else { 0 };
+    let mut bytes = vec![fill; size - min.len()];
+    bytes.extend_from_slice(&min);
+    Ok(bytes)
+}
@@ -0,0 +1,183 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitStr,
+};
+
+use crate::{args::Args, base64, emit, hex};
+
+struct Input {
+    name: LitStr,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { name, args })
+    }
+}
+
+pub fn bytes_env(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(|input| expand(input, false)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn option_bytes_env(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(|input| expand(input, true)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input, optional: bool) -> Result<TokenStream2, Error> {
+    let is_base64 = input.args.flag("base64")?;
+    let default = if optional {
+        match input.args.str("default")? {
+            Some(default) => Some(default),
+            None => {
+                return Err(Error::new(
+                    input.name.span(),
+                    "missing required option `default`",
+                ))
+            }
+        }
+    } else {
+        None
+    };
+    input.args.finish()?;
+
+    let decode = |s: &str| {
+        if is_base64 {
+            base64::decode(s.trim(), base64::Alphabet::Standard)
+        } else {
+            hex::decode_separated(s)
+        }
+    };
+
+    let name = input.name.value();
+    let bytes = match std::env::var(&name) {
+        Ok(value) => decode(&value).map_err(|e| {
+            Error::new(
+                input.name.span(),
+                format!(
+                    "{} at index {} of environment variable `{}`",
+                    e.message, e.index, name
+                ),
+            )
+        })?,
+        Err(std::env::VarError::NotUnicode(_)) => {
+            return Err(Error::new(
+                input.name.span(),
+                format!("environment variable `{}` is not valid unicode", name),
+            ))
+        }
+        Err(std::env::VarError::NotPresent) => match default {
+            Some(default) => decode(&default.value()).map_err(|e| e.into_error(&default))?,
+            None => {
+                return Err(Error::new(
+                    input.name.span(),
+                    format!("environment variable `{}` not defined", name),
+                ))
+            }
+        },
+    };
+
+    // Reference the variable with option_env so that the compiler tracks it
+    // and expands the macro again when it changes.
+    let array = emit::array(&bytes);
+    Ok(quote! {{
+        const _: ::core::option::Option<&str> = ::core::option_env!(#name);
+        #array
+    }})
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bytes_env, option_bytes_env};
+    use pretty_assertions::assert_eq;
+    use proc_macro2::{Span, TokenStream as TokenStream2};
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray, ExprBlock, Stmt};
+
+    /// Returns the array expression at the end of the block emitted.
+    fn array(tokens: TokenStream2) -> ExprArray {
+        let block = syn::parse2::<ExprBlock>(tokens).unwrap();
+        match block.block.stmts.last() {
+            Some(Stmt::Expr(syn::Expr::Array(array), None)) => array.clone(),
+            _ => panic!("expected an array at the end of the block"),
+        }
+    }
+
+    #[test]
+    fn decoding() {
+        std::env::set_var("BYTES_LIT_TEST_HEX", "de:ad be ef");
+        std::env::set_var("BYTES_LIT_TEST_BASE64", "3q2+7w==\n");
+        let expect: ExprArray = parse_quote!([222u8, 173u8, 190u8, 239u8]);
+
+        let tokens = bytes_env(quote! {"BYTES_LIT_TEST_HEX"});
+        assert_eq!(array(tokens), expect);
+
+        let tokens = bytes_env(quote! {"BYTES_LIT_TEST_BASE64", base64});
+        assert_eq!(array(tokens), expect);
+
+        let tokens = option_bytes_env(quote! {"BYTES_LIT_TEST_HEX", default = "00"});
+        assert_eq!(array(tokens), expect);
+    }
+
+    #[test]
+    fn tracks_variable() {
+        std::env::set_var("BYTES_LIT_TEST_TRACKED", "00");
+        let tokens = bytes_env(quote! {"BYTES_LIT_TEST_TRACKED"});
+        let block = syn::parse2::<ExprBlock>(tokens).unwrap();
+        let expect: Stmt = parse_quote!(
+            const _: ::core::option::Option<&str> = ::core::option_env!("BYTES_LIT_TEST_TRACKED");
+        );
+        assert_eq!(block.block.stmts[0], expect);
+    }
+
+    #[test]
+    fn unset() {
+        let tokens = option_bytes_env(quote! {"BYTES_LIT_TEST_UNSET", default = "0102"});
+        let expect: ExprArray = parse_quote!([1u8, 2u8]);
+        assert_eq!(array(tokens), expect);
+
+        let table: &[(_, &str)] = &[
+            (
+                bytes_env(quote! {"BYTES_LIT_TEST_UNSET"}),
+                "environment variable `BYTES_LIT_TEST_UNSET` not defined",
+            ),
+            (
+                option_bytes_env(quote! {"BYTES_LIT_TEST_UNSET"}),
+                "missing required option `default`",
+            ),
+            (
+                option_bytes_env(quote! {"BYTES_LIT_TEST_UNSET", default = "0g"}),
+                "invalid hex character 'g' at index 1",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(t.0.to_string(), expect, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        std::env::set_var("BYTES_LIT_TEST_INVALID", "abc");
+        let tokens = bytes_env(quote! {"BYTES_LIT_TEST_INVALID"});
+        let expect = Error::new(
+            Span::call_site(),
+            "odd number of hex digits at index 2 of environment variable `BYTES_LIT_TEST_INVALID`",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
@@ -0,0 +1,130 @@
+use std::ops::Range;
+
+use proc_macro2::Span;
+use syn::{Error, LitStr};
+
+/// An error decoding the value of a string literal, at a byte index into the
+/// value.
+#[derive(Debug, PartialEq, Eq)]
+pub struct StrError {
+    pub index: usize,
+    pub message: String,
+}
+
+impl StrError {
+    pub fn new(index: usize, message: impl Into<String>) -> Self {
+        Self {
+            index,
+            message: message.into(),
+        }
+    }
+
+    /// Converts the error into a syn error on the string literal, stating the
+    /// index of the error. The error points at the character at the index
+    /// when the compiler supports spans within literals, and otherwise at the
+    /// whole literal.
+    pub fn into_error(self, lit: &LitStr) -> Error {
+        let span = subspan(lit, self.index).unwrap_or_else(|| lit.span());
+        Error::new(span, format!("{} at index {}", self.message, self.index))
+    }
+}
+
+/// Returns the span of the character at a byte index into the value of a
+/// string literal.
+fn subspan(lit: &LitStr, index: usize) -> Option<Span> {
+    let token = lit.token();
+    let range = source_range(&token.to_string(), index)?;
+    token.subspan(range)
+}
+
+/// Maps a byte index into the value of a string literal to the byte range of
+/// the character, or escape, that produces it in the source of the literal.
+fn source_range(source: &str, index: usize) -> Option<Range<usize>> {
+    // Raw strings have no escapes, so the value is offset by the prefix.
+    if let Some(raw) = source.strip_prefix('r') {
+        let start = 1 + raw.find('"')? + 1;
+        let len = source.get(start + index..)?.chars().next()?.len_utf8();
+        return Some(start + index..start + index + len);
+    }
+
+    let body = source.strip_prefix('"')?;
+    let mut value_index = 0;
+    let mut chars = body.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let (source_len, value_len) = match c {
+            '"' => return None,
+            '\\' => match chars.next()?.1 {
+                'x' => {
+                    chars.nth(1)?;
+                    (4, 1)
+                }
+                'u' => {
+                    let mut len = 2;
+                    let mut digits = String::new();
+                    for (_, c) in chars.by_ref() {
+                        len += c.len_utf8();
+                        match c {
+                            '}' => break,
+                            '{' | '_' => {}
+                            c => digits.push(c),
+                        }
+                    }
+                    let c = char::from_u32(u32::from_str_radix(&digits, 16).ok()?)?;
+                    (len, c.len_utf8())
+                }
+                '\n' => {
+                    // A line continuation skips the newline and any leading
+                    // whitespace on the next line, producing no value.
+                    let mut len = 2;
+                    while let Some((_, c)) = chars.next_if(|(_, c)| c.is_whitespace()) {
+                        len += c.len_utf8();
+                    }
+                    (len, 0)
+                }
+                _ => (2, 1),
+            },
+            c => (c.len_utf8(), c.len_utf8()),
+        };
+        if index < value_index + value_len {
+            let start = 1 + i;
+            return Some(start..start + source_len);
+        }
+        value_index += value_len;
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::{source_range, StrError};
+    use pretty_assertions::assert_eq;
+    use syn::{parse_quote, LitStr};
+
+    #[test]
+    fn into_error() {
+        let lit: LitStr = parse_quote!("GAAZ!");
+        let error = StrError::new(4, "invalid character '!'").into_error(&lit);
+        assert_eq!(error.to_string(), "invalid character '!' at index 4");
+    }
+
+    #[test]
+    fn source_ranges() {
+        let table: &[(&str, usize, Option<std::ops::Range<usize>>)] = &[
+            (r#""abc""#, 0, Some(1..2)),
+            (r#""abc""#, 2, Some(3..4)),
+            (r#""abc""#, 3, None),
+            (r#""a\nb""#, 1, Some(2..4)),
+            (r#""a\nb""#, 2, Some(4..5)),
+            (r#""\x41g""#, 1, Some(5..6)),
+            (r#""\u{e9}g""#, 0, Some(1..7)),
+            (r#""\u{e9}g""#, 1, Some(1..7)),
+            (r#""\u{e9}g""#, 2, Some(7..8)),
+            ("\"a\\\n    b\"", 1, Some(8..9)),
+            (r###"r#"a"b"#"###, 1, Some(4..5)),
+            (r#"r"\x""#, 1, Some(3..4)),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(source_range(t.0, t.1), t.2, "table entry: {}", i);
+        }
+    }
+}
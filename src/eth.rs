@@ -0,0 +1,145 @@
+use proc_macro2::TokenStream as TokenStream2;
+use sha3::{Digest, Keccak256};
+use syn::LitStr;
+
+use crate::{emit, error::StrError, hex};
+
+pub fn eth_address(input: TokenStream2) -> TokenStream2 {
+    let lit = match syn::parse2::<LitStr>(input) {
+        Ok(lit) => lit,
+        Err(e) => return e.to_compile_error(),
+    };
+    match parse_address(&lit.value()) {
+        Ok(bytes) => emit::array(&bytes),
+        Err(e) => e.into_error(&lit).to_compile_error(),
+    }
+}
+
+/// Parses an Ethereum address of 40 hex digits, optionally prefixed with
+/// `0x`. An address in mixed case must have a valid EIP-55 checksum, and an
+/// address in a single case has no checksum.
+pub fn parse_address(s: &str) -> Result<[u8; 20], StrError> {
+    let (offset, digits) = match s.strip_prefix("0x") {
+        Some(digits) => (2, digits),
+        None => (0, s),
+    };
+    if digits.len() != 40 {
+        return Err(StrError::new(
+            offset + digits.len().min(40),
+            format!(
+                "invalid Ethereum address length, expected 40 hex digits, found {}",
+                digits.len()
+            ),
+        ));
+    }
+
+    let mut bytes = [0u8; 20];
+    for (i, c) in digits.bytes().enumerate() {
+        let v = hex::value(c).ok_or_else(|| {
+            StrError::new(offset + i, format!("invalid hex character {:?}", c as char))
+        })?;
+        bytes[i / 2] |= v << (4 * (1 - i % 2));
+    }
+
+    let lower = digits.bytes().any(|c| c.is_ascii_lowercase());
+    let upper = digits.bytes().any(|c| c.is_ascii_uppercase());
+    if lower && upper {
+        let expected = checksum(&bytes);
+        if let Some(i) = digits
+            .bytes()
+            .zip(expected.bytes())
+            .position(|(a, b)| a != b)
+        {
+            return Err(StrError::new(
+                offset + i,
+                format!("invalid EIP-55 checksum, expected 0x{}", expected),
+            ));
+        }
+    }
+    Ok(bytes)
+}
+
+/// Returns the hex digits of an address in the mixed case of its EIP-55
+/// checksum, where a letter is upper case when the corresponding nibble of the
+/// keccak256 hash of the lower case digits is 8 or more.
+fn checksum(bytes: &[u8; 20]) -> String {
+    let digits: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    let hash = Keccak256::digest(digits.as_bytes());
+    digits
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0xf;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{eth_address, parse_address};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray};
+
+    #[test]
+    fn checksums() {
+        // The examples of EIP-55.
+        let table = [
+            "0x52908400098527886E0F7030069857D2E4169EE7",
+            "0x8617E340B3D01FA5F11F306F4090FD50E238070D",
+            "0xde709f2102306220921060314715629080e2fb77",
+            "0x27b1fdb04752bbc536007a920d24acb045561c26",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let bytes = parse_address(t).unwrap();
+            let lower = t[2..].to_ascii_lowercase();
+            let expect = crate::hex::decode(&lower).unwrap();
+            assert_eq!(bytes[..], expect[..], "table entry: {}", i);
+        }
+
+        let tokens = eth_address(quote!("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            90u8, 174u8, 182u8, 5u8, 63u8, 62u8, 148u8, 201u8, 185u8, 160u8, 159u8, 51u8, 102u8,
+            148u8, 53u8, 231u8, 239u8, 27u8, 234u8, 237u8
+        ]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(&str, StrError)] = &[
+            (
+                "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeaED",
+                StrError::new(
+                    39,
+                    "invalid EIP-55 checksum, expected 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+                ),
+            ),
+            (
+                "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA",
+                StrError::new(
+                    40,
+                    "invalid Ethereum address length, expected 40 hex digits, found 38",
+                ),
+            ),
+            (
+                "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg",
+                StrError::new(41, "invalid hex character 'g'"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse_address(t.0).as_ref(), Err(&t.1), "table entry: {}", i);
+        }
+    }
+}
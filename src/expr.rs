@@ -0,0 +1,155 @@
+use std::{convert::TryFrom, str::FromStr};
+
+use num_bigint::BigUint;
+use proc_macro2::Delimiter;
+use syn::{parse::ParseStream, BinOp, Error, Expr, ExprBinary, Lit, UnOp};
+
+/// The maximum number of bits of the result of a left shift, so that a large
+/// shift amount is an error rather than an allocation that exhausts memory.
+const MAX_SHIFT_BITS: u64 = 1 << 20;
+
+/// Returns true if the next tokens start an integer expression rather than a
+/// single literal: a parenthesized group, or a literal followed by an operator.
+/// The tokens are inspected without parsing the literal, which is slow for very
+/// long integer literals.
+pub fn peek(input: ParseStream) -> bool {
+    let cursor = input.cursor();
+    if cursor.group(Delimiter::Parenthesis).is_some() {
+        return true;
+    }
+    match cursor.literal() {
+        Some((_, rest)) => rest
+            .punct()
+            .map_or(false, |(punct, _)| "+-*<>|&^".contains(punct.as_char())),
+        None => false,
+    }
+}
+
+/// Evaluates an expression of unsigned integer literals, combined with the
+/// operators `+ - * << >> | & ^` and parentheses.
+pub fn eval(expr: &Expr) -> Result<BigUint, Error> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => Ok(BigUint::from_str(int.base10_digits()).expect("valid integer")),
+            _ => Err(unsupported(expr)),
+        },
+        Expr::Paren(paren) => eval(&paren.expr),
+        Expr::Group(group) => eval(&group.expr),
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => Err(Error::new_spanned(
+            expr,
+            "negative values unsupported in expressions",
+        )),
+        Expr::Binary(binary) => eval_binary(binary),
+        _ => Err(unsupported(expr)),
+    }
+}
+
+fn eval_binary(binary: &ExprBinary) -> Result<BigUint, Error> {
+    let left = eval(&binary.left)?;
+    let right = eval(&binary.right)?;
+    let shift = |amount: &BigUint| {
+        usize::try_from(amount)
+            .map_err(|_| Error::new_spanned(&binary.right, "shift amount is too large"))
+    };
+    Ok(match binary.op {
+        BinOp::Add(_) => left + right,
+        BinOp::Sub(_) => {
+            if right > left {
+                return Err(Error::new_spanned(
+                    binary,
+                    "subtraction overflows, the result is negative",
+                ));
+            }
+            left - right
+        }
+        BinOp::Mul(_) => left * right,
+        BinOp::Shl(_) => {
+            let amount = shift(&right)?;
+            if left.bits() + amount as u64 > MAX_SHIFT_BITS {
+                return Err(Error::new_spanned(
+                    &binary.right,
+                    "shift amount is too large",
+                ));
+            }
+            left << amount
+        }
+        BinOp::Shr(_) => left >> shift(&right)?,
+        BinOp::BitOr(_) => left | right,
+        BinOp::BitAnd(_) => left & right,
+        BinOp::BitXor(_) => left ^ right,
+        _ => return Err(Error::new_spanned(binary.op, "unsupported operator")),
+    })
+}
+
+fn unsupported(expr: &Expr) -> Error {
+    Error::new_spanned(
+        expr,
+        "unsupported expression, only integer literals, parentheses, and the operators + - * << >> | & ^ are supported",
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::eval;
+    use num_bigint::BigUint;
+    use pretty_assertions::assert_eq;
+    use syn::{parse_quote, Expr};
+
+    #[test]
+    fn operators() {
+        let table: &[(Expr, u64)] = &[
+            (parse_quote!(0x10 + 0x0F), 0x1f),
+            (parse_quote!(10 - 3), 7),
+            (parse_quote!(6 * 7), 42),
+            (parse_quote!(1 << 40), 1 << 40),
+            (parse_quote!(0xff00 >> 8), 0xff),
+            (parse_quote!(0xf0 | 0x0f), 0xff),
+            (parse_quote!(0xf0 & 0x3c), 0x30),
+            (parse_quote!(0xff ^ 0x0f), 0xf0),
+            (parse_quote!((1 + 2) * 3), 9),
+            (parse_quote!(1 + 2 * 3), 7),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let value = eval(&t.0).unwrap();
+            assert_eq!(value, BigUint::from(t.1), "table entry: {}", i);
+        }
+        let value = eval(&parse_quote!(1 << 255)).unwrap();
+        assert_eq!(value.bits(), 256);
+        let value = eval(&parse_quote!(1 << 1048575)).unwrap();
+        assert_eq!(value.bits(), 1048576);
+        let value = eval(&parse_quote!(0xff >> 1000000000000)).unwrap();
+        assert_eq!(value, BigUint::from(0u8));
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(Expr, &str)] = &[
+            (
+                parse_quote!(1 - 2),
+                "subtraction overflows, the result is negative",
+            ),
+            (
+                parse_quote!(1 / 2),
+                "unsupported operator",
+            ),
+            (
+                parse_quote!(-1 + 2),
+                "negative values unsupported in expressions",
+            ),
+            (
+                parse_quote!(1 + "a"),
+                "unsupported expression, only integer literals, parentheses, and the operators + - * << >> | & ^ are supported",
+            ),
+            (
+                parse_quote!(1 << 0x1_0000_0000_0000_0000),
+                "shift amount is too large",
+            ),
+            (parse_quote!(1 << 1000000000000), "shift amount is too large"),
+            (parse_quote!(2 << 1048575), "shift amount is too large"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let message = eval(&t.0).unwrap_err().to_string();
+            assert_eq!(message, t.1, "table entry: {}", i);
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use std::{env, fs, path::Path};
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitStr,
+};
+
+use crate::{args::Args, base64, emit, hex};
+
+struct Input {
+    path: LitStr,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { path, args })
+    }
+}
+
+pub fn bytes_file(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let is_base64 = input.args.flag("base64")?;
+    input.args.finish()?;
+
+    let (path, text) = read(&input.path)?;
+    let (content, offsets) = strip(&text);
+    let decoded = if is_base64 {
+        base64::decode(&content, base64::Alphabet::Standard)
+    } else {
+        hex::decode(&content)
+    };
+    let bytes = decoded.map_err(|e| {
+        let offset = offsets.get(e.index).copied().unwrap_or(text.len());
+        let (line, column) = position(&text, offset);
+        Error::new(
+            input.path.span(),
+            format!(
+                "{} at {}:{}:{}",
+                e.message,
+                input.path.value(),
+                line,
+                column
+            ),
+        )
+    })?;
+
+    Ok(tracked(&path, &bytes))
+}
+
+/// Reads a text file at a path relative to the manifest directory, returning
+/// the full path and the text.
+pub fn read(lit: &LitStr) -> Result<(String, String), Error> {
+    let (path, bytes) = read_bytes(lit)?;
+    let text = String::from_utf8(bytes).map_err(|_| {
+        Error::new(
+            lit.span(),
+            format!(
+                "failed to read {}: stream did not contain valid UTF-8",
+                path
+            ),
+        )
+    })?;
+    Ok((path, text))
+}
+
+/// Reads a file at a path relative to the manifest directory, returning the
+/// full path and the contents.
+pub fn read_bytes(lit: &LitStr) -> Result<(String, Vec<u8>), Error> {
+    let span = lit.span();
+    let root = env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| Error::new(span, "CARGO_MANIFEST_DIR is not set"))?;
+    let path = Path::new(&root).join(lit.value());
+    let bytes = fs::read(&path)
+        .map_err(|e| Error::new(span, format!("failed to read {}: {}", path.display(), e)))?;
+    let path = path
+        .to_str()
+        .ok_or_else(|| Error::new(span, "path is not valid UTF-8"))?;
+    Ok((path.to_string(), bytes))
+}
+
+/// Emits the bytes as an array in a block that includes the file at the path
+/// in an unused constant, so that the compiler tracks the file as a
+/// dependency and expands the macro again when the file changes.
+pub fn tracked(path: &str, bytes: &[u8]) -> TokenStream2 {
+    let array = emit::array(bytes);
+    quote! {{
+        const _: &str = ::core::include_str!(#path);
+        #array
+    }}
+}
+
+/// Removes whitespace and `#` comments running to the end of a line from the
+/// text, returning the remaining content and the byte offset in the text of
+/// each byte of the content.
+fn strip(text: &str) -> (String, Vec<usize>) {
+    let mut content = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len());
+    let mut in_comment = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            '\n' => in_comment = false,
+            '#' => in_comment = true,
+            _ if in_comment || c.is_whitespace() => {}
+            _ => {
+                content.push(c);
+                offsets.extend(std::iter::repeat(i).take(c.len_utf8()));
+            }
+        }
+    }
+    (content, offsets)
+}
+
+/// Returns the one based line and column of a byte offset in the text.
+fn position(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod test {
+    use super::bytes_file;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::{Span, TokenStream as TokenStream2};
+    use quote::quote;
+    use std::{env, fs, path::PathBuf};
+    use syn::{parse_quote, Error, ExprArray, ExprBlock, Stmt};
+
+    /// Writes a file to the temp directory, returning its absolute path, which
+    /// is used as is when joined to the manifest directory.
+    fn write(name: &str, content: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("bytes-lit-{}-{}", std::process::id(), name));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    /// Returns the array expression at the end of the block emitted.
+    fn array(tokens: TokenStream2) -> ExprArray {
+        let block = syn::parse2::<ExprBlock>(tokens).unwrap();
+        match block.block.stmts.last() {
+            Some(Stmt::Expr(syn::Expr::Array(array), None)) => array.clone(),
+            _ => panic!("expected an array at the end of the block"),
+        }
+    }
+
+    #[test]
+    fn hex_file() {
+        let path = write(
+            "signer.hex",
+            "# signer key\nDEAD beef # trailing comment\n\n  01 02\n",
+        );
+        let path = path.to_str().unwrap();
+        let tokens = bytes_file(quote! {#path});
+        let expect: ExprArray = parse_quote!([222u8, 173u8, 190u8, 239u8, 1u8, 2u8]);
+        assert_eq!(array(tokens), expect);
+    }
+
+    #[test]
+    fn base64_file() {
+        let path = write("signer.b64", "3q2+\n7w==\n");
+        let path = path.to_str().unwrap();
+        let tokens = bytes_file(quote! {#path, base64});
+        let expect: ExprArray = parse_quote!([222u8, 173u8, 190u8, 239u8]);
+        assert_eq!(array(tokens), expect);
+    }
+
+    #[test]
+    fn tracks_file() {
+        let path = write("tracked.hex", "00");
+        let path = path.to_str().unwrap();
+        let tokens = bytes_file(quote! {#path});
+        let block = syn::parse2::<ExprBlock>(tokens).unwrap();
+        let expect: Stmt = parse_quote!(
+            const _: &str = ::core::include_str!(#path);
+        );
+        assert_eq!(block.block.stmts[0], expect);
+    }
+
+    #[test]
+    fn errors() {
+        let path = write("invalid.hex", "# key\nab\n cx\n");
+        let path = path.to_str().unwrap();
+        let tokens = bytes_file(quote! {#path});
+        let expect = Error::new(
+            Span::call_site(),
+            format!("invalid hex character 'x' at {}:3:3", path),
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+
+        let path = write("missing.hex", "");
+        fs::remove_file(&path).unwrap();
+        let path = path.to_str().unwrap();
+        let tokens = bytes_file(quote! {#path});
+        assert!(tokens
+            .to_string()
+            .contains(&format!("failed to read {}", path)));
+    }
+}
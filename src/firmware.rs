@@ -0,0 +1,361 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitStr,
+};
+
+use crate::{
+    args::Args,
+    file::{read, tracked},
+    hex,
+};
+
+struct Input {
+    path: LitStr,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { path, args })
+    }
+}
+
+/// The maximum size in bytes of an image with gaps filled, so that data at
+/// distant addresses does not fill gigabytes.
+const MAX_IMAGE_SIZE: u64 = 1 << 24;
+
+/// A parser of the records in a firmware image file, returning the data
+/// segments as the address and the bytes at the address.
+type Parser = fn(&str) -> Result<Vec<Segment>, LineError>;
+
+/// Bytes of data at an address.
+struct Segment {
+    address: u64,
+    data: Vec<u8>,
+}
+
+/// An error at a line of a firmware image file.
+struct LineError {
+    line: usize,
+    message: String,
+}
+
+impl LineError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+pub fn ihex(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(|input| expand(input, parse_ihex)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn srec(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(|input| expand(input, parse_srec)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input, parser: Parser) -> Result<TokenStream2, Error> {
+    let fill = input.args.int::<u8>("fill")?;
+    input.args.finish()?;
+
+    let (path, text) = read(&input.path)?;
+    let segments = parser(&text).map_err(|e| {
+        Error::new(
+            input.path.span(),
+            format!("{} at {}:{}", e.message, input.path.value(), e.line),
+        )
+    })?;
+    let bytes = image(segments, fill).map_err(|e| Error::new(input.path.span(), e))?;
+    Ok(tracked(&path, &bytes))
+}
+
+/// Joins the segments into one contiguous image starting at the lowest
+/// address, filling any gaps with the fill byte, or erroring on gaps if there
+/// is no fill byte or the filled image would exceed the maximum size.
+fn image(mut segments: Vec<Segment>, fill: Option<u8>) -> Result<Vec<u8>, String> {
+    segments.sort_by_key(|s| s.address);
+    let start = match segments.first() {
+        Some(s) => s.address,
+        None => return Ok(Vec::new()),
+    };
+    let mut bytes = Vec::new();
+    for segment in segments {
+        let end = start + bytes.len() as u64;
+        if segment.address < end {
+            return Err(format!(
+                "overlapping data at address {:#x}",
+                segment.address
+            ));
+        }
+        if segment.address > end {
+            match fill {
+                Some(_) if segment.address - start + segment.data.len() as u64 > MAX_IMAGE_SIZE => {
+                    return Err(format!(
+                        "gap from address {:#x} to {:#x} is too large to fill, the image would be larger than the maximum of {} bytes",
+                        end, segment.address, MAX_IMAGE_SIZE
+                    ))
+                }
+                Some(fill) => bytes.resize((segment.address - start) as usize, fill),
+                None => {
+                    return Err(format!(
+                        "non-contiguous data, gap from address {:#x} to {:#x}, use the `fill` option to fill gaps",
+                        end, segment.address
+                    ))
+                }
+            }
+        }
+        bytes.extend(segment.data);
+    }
+    Ok(bytes)
+}
+
+/// Decodes the hex of a record after its start code, verifying that it is
+/// at least the given number of bytes.
+fn record_bytes(line: usize, hex: &str, min_len: usize) -> Result<Vec<u8>, LineError> {
+    let bytes = hex::decode(hex).map_err(|e| LineError::new(line, e.message))?;
+    if bytes.len() < min_len {
+        return Err(LineError::new(line, "record is too short"));
+    }
+    Ok(bytes)
+}
+
+/// Parses the records of an Intel HEX file.
+fn parse_ihex(text: &str) -> Result<Vec<Segment>, LineError> {
+    let mut segments = Vec::new();
+    let mut base = 0u64;
+    let mut last_line = 0;
+    for (i, record) in text.lines().enumerate() {
+        let line = i + 1;
+        last_line = line;
+        let record = record.trim();
+        if record.is_empty() {
+            continue;
+        }
+        let hex = record
+            .strip_prefix(':')
+            .ok_or_else(|| LineError::new(line, "record does not start with ':'"))?;
+        // Length, address, type, and checksum.
+        let bytes = record_bytes(line, hex, 5)?;
+        let len = usize::from(bytes[0]);
+        if bytes.len() != len + 5 {
+            return Err(LineError::new(
+                line,
+                format!(
+                    "record length {} does not match the {} data byte(s)",
+                    len,
+                    bytes.len() - 5
+                ),
+            ));
+        }
+        if bytes.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)) != 0 {
+            return Err(LineError::new(line, "record checksum mismatch"));
+        }
+        let address = u64::from(u16::from_be_bytes([bytes[1], bytes[2]]));
+        let data = &bytes[4..4 + len];
+        match bytes[3] {
+            0x00 => segments.push(Segment {
+                address: base + address,
+                data: data.to_vec(),
+            }),
+            0x01 => return Ok(segments),
+            0x02 if len == 2 => base = u64::from(u16::from_be_bytes([data[0], data[1]])) << 4,
+            0x04 if len == 2 => base = u64::from(u16::from_be_bytes([data[0], data[1]])) << 16,
+            0x03 | 0x05 => {}
+            kind => {
+                return Err(LineError::new(
+                    line,
+                    format!("invalid record type {:02x} of length {}", kind, len),
+                ))
+            }
+        }
+    }
+    Err(LineError::new(last_line, "missing end of file record"))
+}
+
+/// Parses the records of a Motorola S-record file.
+fn parse_srec(text: &str) -> Result<Vec<Segment>, LineError> {
+    let mut segments = Vec::new();
+    for (i, record) in text.lines().enumerate() {
+        let line = i + 1;
+        let record = record.trim();
+        if record.is_empty() {
+            continue;
+        }
+        let mut chars = record.chars();
+        if chars.next() != Some('S') {
+            return Err(LineError::new(line, "record does not start with 'S'"));
+        }
+        let kind = chars.next();
+        // Count and checksum.
+        let bytes = record_bytes(line, chars.as_str(), 2)?;
+        let count = usize::from(bytes[0]);
+        if bytes.len() != count + 1 {
+            return Err(LineError::new(
+                line,
+                format!(
+                    "record count {} does not match the {} byte(s) of the record",
+                    count,
+                    bytes.len() - 1
+                ),
+            ));
+        }
+        let sum = bytes.iter().fold(0u8, |sum, b| sum.wrapping_add(*b));
+        if sum != 0xff {
+            return Err(LineError::new(line, "record checksum mismatch"));
+        }
+        let address_len = match kind {
+            Some('0' | '1' | '5' | '9') => 2,
+            Some('2' | '6' | '8') => 3,
+            Some('3' | '7') => 4,
+            _ => return Err(LineError::new(line, "invalid record type")),
+        };
+        if count < address_len + 1 {
+            return Err(LineError::new(line, "record is too short"));
+        }
+        let address = bytes[1..1 + address_len]
+            .iter()
+            .fold(0u64, |a, b| a << 8 | u64::from(*b));
+        let data = &bytes[1 + address_len..bytes.len() - 1];
+        if let Some('1' | '2' | '3') = kind {
+            segments.push(Segment {
+                address,
+                data: data.to_vec(),
+            });
+        }
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ihex, srec};
+    use pretty_assertions::assert_eq;
+    use proc_macro2::{Span, TokenStream as TokenStream2};
+    use quote::quote;
+    use std::{env, fs};
+    use syn::{parse_quote, Error, ExprArray, ExprBlock, Stmt};
+
+    /// Writes a file to the temp directory, returning its absolute path.
+    fn write(name: &str, content: &str) -> String {
+        let path = env::temp_dir().join(format!("bytes-lit-{}-{}", std::process::id(), name));
+        fs::write(&path, content).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    /// Returns the array expression at the end of the block emitted.
+    fn array(tokens: TokenStream2) -> ExprArray {
+        let block = syn::parse2::<ExprBlock>(tokens).unwrap();
+        match block.block.stmts.last() {
+            Some(Stmt::Expr(syn::Expr::Array(array), None)) => array.clone(),
+            _ => panic!("expected an array at the end of the block"),
+        }
+    }
+
+    fn error(message: String) -> String {
+        Error::new(Span::call_site(), message)
+            .to_compile_error()
+            .to_string()
+    }
+
+    #[test]
+    fn intel_hex() {
+        let path = write(
+            "boot.hex",
+            ":020000040001F9\n:0400000001020304F2\n:02000400AABB95\n:00000001FF\n",
+        );
+        let tokens = ihex(quote! {#path});
+        let expect: ExprArray = parse_quote!([1u8, 2u8, 3u8, 4u8, 170u8, 187u8]);
+        assert_eq!(array(tokens), expect);
+    }
+
+    #[test]
+    fn intel_hex_gaps() {
+        let path = write("gap.hex", ":0100000001FE\n:0100020002FB\n:00000001FF\n");
+        let tokens = ihex(quote! {#path, fill = 0xff});
+        let expect: ExprArray = parse_quote!([1u8, 255u8, 2u8]);
+        assert_eq!(array(tokens), expect);
+
+        let tokens = ihex(quote! {#path});
+        assert_eq!(
+            tokens.to_string(),
+            error(
+                "non-contiguous data, gap from address 0x1 to 0x2, use the `fill` option to fill gaps"
+                    .to_string()
+            )
+        );
+
+        // Data at 16 MiB, after an extended linear address record.
+        let path = write(
+            "far.hex",
+            ":0100000001FE\n:020000040100F9\n:0100000002FD\n:00000001FF\n",
+        );
+        let tokens = ihex(quote! {#path, fill = 0xff});
+        assert_eq!(
+            tokens.to_string(),
+            error(
+                "gap from address 0x1 to 0x1000000 is too large to fill, the image would be larger than the maximum of 16777216 bytes"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn intel_hex_errors() {
+        let table: &[(&str, &str, &str)] = &[
+            (
+                "checksum.hex",
+                ":0100000001FF\n",
+                "record checksum mismatch at {}:1",
+            ),
+            (
+                "eof.hex",
+                ":0100000001FE\n",
+                "missing end of file record at {}:1",
+            ),
+            (
+                "start.hex",
+                "\n0100000001FE\n",
+                "record does not start with ':' at {}:2",
+            ),
+            (
+                "overlap.hex",
+                ":020000000102FB\n:0100010002FC\n:00000001FF\n",
+                "overlapping data at address 0x1",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let path = write(t.0, t.1);
+            let tokens = ihex(quote! {#path});
+            let expect = error(t.2.replace("{}", &path));
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn motorola_srec() {
+        let path = write(
+            "boot.s19",
+            "S00600004844521B\nS107000001020304EE\nS10500040506EB\nS9030000FC\n",
+        );
+        let tokens = srec(quote! {#path});
+        let expect: ExprArray = parse_quote!([1u8, 2u8, 3u8, 4u8, 5u8, 6u8]);
+        assert_eq!(array(tokens), expect);
+
+        let path = write("checksum.s19", "S107000001020304EF\n");
+        let tokens = srec(quote! {#path});
+        let expect = error(format!("record checksum mismatch at {}:1", path));
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
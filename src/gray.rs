@@ -0,0 +1,95 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::Lit;
+
+use crate::{bytes::lit_bytes, emit};
+
+pub fn gray(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Lit>(input).and_then(|lit| lit_bytes(&lit)) {
+        Ok(bytes) => emit::array(&encode(&bytes)),
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn from_gray(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Lit>(input).and_then(|lit| lit_bytes(&lit)) {
+        Ok(bytes) => emit::array(&decode(&bytes)),
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Converts the big endian value of the bytes to its Gray code, the value
+/// xor the value shifted right by one bit. The bytes keep their length.
+pub fn encode(bytes: &[u8]) -> Vec<u8> {
+    let mut carry = 0;
+    bytes
+        .iter()
+        .map(|b| {
+            let shifted = b >> 1 | carry << 7;
+            carry = b & 1;
+            b ^ shifted
+        })
+        .collect()
+}
+
+/// Converts a big endian Gray code back to its value. Each bit of the value,
+/// from the most significant, is the bit of the code xor the previous bit of
+/// the value.
+pub fn decode(bytes: &[u8]) -> Vec<u8> {
+    let mut previous = 0;
+    bytes
+        .iter()
+        .map(|g| {
+            let mut b = 0;
+            for i in (0..8).rev() {
+                previous ^= g >> i & 1;
+                b |= previous << i;
+            }
+            b
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode, encode, from_gray, gray};
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray};
+
+    #[test]
+    fn codes() {
+        // The 4 bit Gray codes of 0 to 15.
+        let codes = [0, 1, 3, 2, 6, 7, 5, 4, 12, 13, 15, 14, 10, 11, 9, 8];
+        for (i, code) in codes.iter().enumerate() {
+            assert_eq!(encode(&[i as u8]), [*code], "value: {}", i);
+            assert_eq!(decode(&[*code]), [i as u8], "code: {}", code);
+        }
+
+        let table: &[(u16, u16)] = &[(0x0100, 0x0180), (0x00ff, 0x0080), (0xffff, 0x8000)];
+        for (i, t) in table.iter().enumerate() {
+            let value = t.0.to_be_bytes();
+            let code = t.1.to_be_bytes();
+            assert_eq!(encode(&value), code, "table entry: {}", i);
+            assert_eq!(decode(&code), value, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn macros() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(0b0111), parse_quote!([4u8])),
+            (quote!(0x0100), parse_quote!([1u8, 128u8])),
+            (quote!("00ff"), parse_quote!([0u8, 128u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = gray(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+
+        let tokens = from_gray(quote!(0b0100));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([7u8]);
+        assert_eq!(parsed, expect);
+    }
+}
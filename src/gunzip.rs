@@ -0,0 +1,223 @@
+use std::convert::TryInto;
+
+use miniz_oxide::inflate::{decompress_to_vec, decompress_to_vec_zlib, TINFLStatus};
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitStr,
+};
+
+use crate::{
+    args::Args,
+    base64::{self, Alphabet},
+    crc::CRC32,
+    emit,
+};
+
+/// The flags of a gzip header, of the optional fields following it.
+const FHCRC: u8 = 0x02;
+const FEXTRA: u8 = 0x04;
+const FNAME: u8 = 0x08;
+const FCOMMENT: u8 = 0x10;
+
+struct Input {
+    lit: LitStr,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn gunzip_bytes(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let alphabet = match input.args.ident("alphabet", &["standard", "url"])? {
+        Some(a) if a == "url" => Alphabet::Url,
+        _ => Alphabet::Standard,
+    };
+    let raw = input.args.flag("raw")?;
+    input.args.finish()?;
+
+    let data =
+        base64::decode(&input.lit.value(), alphabet).map_err(|e| e.into_error(&input.lit))?;
+    let bytes = decompress(&data, raw).map_err(|e| Error::new(input.lit.span(), e))?;
+    Ok(emit::array(&bytes))
+}
+
+/// Decompresses the data, which is a gzip member or a zlib stream, told apart
+/// by their headers, or with raw a DEFLATE stream without a header.
+fn decompress(data: &[u8], raw: bool) -> Result<Vec<u8>, String> {
+    if raw {
+        decompress_to_vec(data).map_err(|e| inflate_error(e.status))
+    } else if data.starts_with(&[0x1f, 0x8b]) {
+        gunzip(data)
+    } else if data.len() >= 2
+        && data[0] & 0x0f == 8
+        && (u16::from(data[0]) << 8 | u16::from(data[1])) % 31 == 0
+    {
+        decompress_to_vec_zlib(data).map_err(|e| inflate_error(e.status))
+    } else {
+        Err(
+            "data is neither gzip nor zlib, use the `raw` option for DEFLATE data without a header"
+                .to_string(),
+        )
+    }
+}
+
+/// Decompresses a gzip member, as defined by RFC 1952, verifying the CRC-32
+/// and length of the data in its trailer.
+fn gunzip(data: &[u8]) -> Result<Vec<u8>, String> {
+    let truncated = || "truncated gzip member".to_string();
+    if data.len() < 18 {
+        return Err(truncated());
+    }
+    if data[2] != 8 {
+        return Err(format!(
+            "unsupported gzip compression method {}, only 8 (DEFLATE) is supported",
+            data[2]
+        ));
+    }
+    let flags = data[3];
+    let (body, trailer) = data.split_at(data.len() - 8);
+
+    // The fixed header of ten bytes is followed by the optional fields that
+    // the flags indicate, in order.
+    let mut start = 10;
+    if flags & FEXTRA != 0 {
+        let len = body.get(start..start + 2).ok_or_else(truncated)?;
+        start += 2 + usize::from(u16::from_le_bytes([len[0], len[1]]));
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            let len = body
+                .get(start..)
+                .and_then(|rest| rest.iter().position(|b| *b == 0))
+                .ok_or_else(truncated)?;
+            start += len + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        start += 2;
+    }
+    let deflated = body.get(start..).ok_or_else(truncated)?;
+
+    let bytes = decompress_to_vec(deflated).map_err(|e| inflate_error(e.status))?;
+    let crc = u32::from_le_bytes(trailer[..4].try_into().expect("4 bytes"));
+    let size = u32::from_le_bytes(trailer[4..].try_into().expect("4 bytes"));
+    let actual = CRC32.checksum(&bytes);
+    if crc != actual {
+        return Err(format!(
+            "gzip crc32 of the data does not match, expected 0x{:08x}, found 0x{:08x}",
+            crc, actual
+        ));
+    }
+    // The trailer holds the length modulo 2^32.
+    if size != bytes.len() as u32 {
+        return Err(format!(
+            "gzip length of the data does not match, expected {}, found {}",
+            size,
+            bytes.len()
+        ));
+    }
+    Ok(bytes)
+}
+
+fn inflate_error(status: TINFLStatus) -> String {
+    match status {
+        TINFLStatus::Adler32Mismatch => "zlib adler32 of the data does not match".to_string(),
+        TINFLStatus::FailedCannotMakeProgress | TINFLStatus::NeedsMoreInput => {
+            "truncated DEFLATE data".to_string()
+        }
+        _ => "invalid DEFLATE data".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::gunzip_bytes;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn values() {
+        let hello: ExprArray = parse_quote!([
+            104u8, 101u8, 108u8, 108u8, 111u8, 32u8, 119u8, 111u8, 114u8, 108u8, 100u8
+        ]);
+        let table: &[(_, ExprArray)] = &[
+            (
+                quote!("H4sIAAAAAAACA8tIzcnJVyjPL8pJAQCFEUoNCwAAAA=="),
+                hello.clone(),
+            ),
+            (quote!("eJzLSM3JyVcozy/KSQEAGgsEXQ=="), hello.clone()),
+            (quote!("y0jNyclXKM8vykkBAA==", raw), hello),
+            // A gzip member with the name of the file in its header.
+            (
+                quote!("H4sICAAAAAAC/2EudHh0ALu3dt/7e3gwAJQ8cz8gAAAA"),
+                parse_quote!([
+                    222u8, 173u8, 190u8, 239u8, 222u8, 173u8, 190u8, 239u8, 222u8, 173u8, 190u8,
+                    239u8, 222u8, 173u8, 190u8, 239u8, 222u8, 173u8, 190u8, 239u8, 222u8, 173u8,
+                    190u8, 239u8, 222u8, 173u8, 190u8, 239u8, 222u8, 173u8, 190u8, 239u8
+                ]),
+            ),
+            (
+                quote!(
+                    "H4sICAAAAAAC_2EudHh0ALu3dt_7e3gwAJQ8cz8gAAAA",
+                    alphabet = url
+                ),
+                parse_quote!([
+                    222u8, 173u8, 190u8, 239u8, 222u8, 173u8, 190u8, 239u8, 222u8, 173u8, 190u8,
+                    239u8, 222u8, 173u8, 190u8, 239u8, 222u8, 173u8, 190u8, 239u8, 222u8, 173u8,
+                    190u8, 239u8, 222u8, 173u8, 190u8, 239u8, 222u8, 173u8, 190u8, 239u8
+                ]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = gunzip_bytes(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table = &[
+            (
+                quote!("H4sIAAAAAAACA8tIzcnJVyjPL8pJAQCFEUoMCwAAAA=="),
+                "gzip crc32 of the data does not match, expected 0x0c4a1185, found 0x0d4a1185",
+            ),
+            (
+                quote!("eJzLSM3JyVcozy/KSQEAGgsEXA=="),
+                "zlib adler32 of the data does not match",
+            ),
+            (
+                quote!("H4sIAAAAAAACA8tIzcnJVyjPL8pJAQ=="),
+                "truncated DEFLATE data",
+            ),
+            (quote!("H4sIAAAA"), "truncated gzip member"),
+            (
+                quote!("y0jNyclXKM8vykkBAA=="),
+                "data is neither gzip nor zlib, use the `raw` option for DEFLATE data without a header",
+            ),
+            (quote!("H4s!"), "invalid base64 character '!' at index 3"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = gunzip_bytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
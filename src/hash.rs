@@ -0,0 +1,274 @@
+use blake2::{
+    digest::{Update, VariableOutput},
+    Blake2bVar, Blake2sVar,
+};
+use hmac::{Hmac, Mac};
+use proc_macro2::TokenStream as TokenStream2;
+use sha2::{Digest, Sha256};
+use sha3::{Keccak256, Sha3_256};
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, Lit, LitInt, Token,
+};
+
+use crate::{bytes::int_bytes, emit};
+
+pub fn sha256(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Lit>(input).and_then(|lit| message(&lit)) {
+        Ok(message) => emit::array(&Sha256::digest(message)),
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn keccak256(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Lit>(input).and_then(|lit| message(&lit)) {
+        Ok(message) => emit::array(&Keccak256::digest(message)),
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn sha3_256(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Lit>(input).and_then(|lit| message(&lit)) {
+        Ok(message) => emit::array(&Sha3_256::digest(message)),
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Input to the blake2 macros: the output length in bytes, and the literal to
+/// hash.
+struct Blake2Input {
+    len: LitInt,
+    lit: Lit,
+}
+
+impl Parse for Blake2Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let len = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let lit = input.parse()?;
+        Ok(Self { len, lit })
+    }
+}
+
+pub fn blake2b(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Blake2Input>(input).and_then(|input| blake2::<Blake2bVar>(&input, 64)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn blake2s(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Blake2Input>(input).and_then(|input| blake2::<Blake2sVar>(&input, 32)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn blake2<H: VariableOutput + Update>(
+    input: &Blake2Input,
+    max_len: usize,
+) -> Result<TokenStream2, Error> {
+    let len = input.len.base10_parse::<usize>()?;
+    if !(1..=max_len).contains(&len) {
+        return Err(Error::new(
+            input.len.span(),
+            format!("output length must be 1 to {} bytes", max_len),
+        ));
+    }
+    let mut hasher = H::new(len).expect("valid output length");
+    hasher.update(&message(&input.lit)?);
+    let mut digest = vec![0; len];
+    hasher
+        .finalize_variable(&mut digest)
+        .expect("valid output length");
+    Ok(emit::array(&digest))
+}
+
+/// Input to the hmac macros: the key literal, and the message literal.
+struct HmacInput {
+    key: Lit,
+    message: Lit,
+}
+
+impl Parse for HmacInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let message = input.parse()?;
+        Ok(Self { key, message })
+    }
+}
+
+pub fn hmac_sha256(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<HmacInput>(input).and_then(|input| {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&message(&input.key)?).expect("any key length");
+        Mac::update(&mut mac, &message(&input.message)?);
+        Ok(mac.finalize().into_bytes())
+    }) {
+        Ok(mac) => emit::array(&mac),
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Converts a literal into the message bytes to hash. Integer literals are
+/// converted as by the bytes macro, string literals are hashed as their UTF-8
+/// bytes, and byte string literals as their bytes.
+pub fn message(lit: &Lit) -> Result<Vec<u8>, Error> {
+    match lit {
+        Lit::Int(lit) => int_bytes(lit),
+        Lit::Str(lit) => Ok(lit.value().into_bytes()),
+        Lit::ByteStr(lit) => Ok(lit.value()),
+        _ => Err(Error::new(
+            lit.span(),
+            "expected an integer, string, or byte string literal",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{blake2b, blake2s, hmac_sha256, keccak256, sha256, sha3_256};
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn sha256_forms() {
+        // The SHA-256 of "abc".
+        let expect: ExprArray = parse_quote!([
+            186u8, 120u8, 22u8, 191u8, 143u8, 1u8, 207u8, 234u8, 65u8, 65u8, 64u8, 222u8, 93u8,
+            174u8, 34u8, 35u8, 176u8, 3u8, 97u8, 163u8, 150u8, 23u8, 122u8, 156u8, 180u8, 16u8,
+            255u8, 97u8, 242u8, 0u8, 21u8, 173u8
+        ]);
+        let table = &[quote!("abc"), quote!(b"abc"), quote!(0x616263)];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = sha256(t.clone());
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, expect, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn sha256_leading_zeros() {
+        // The SHA-256 of the single byte 0x00.
+        let tokens = sha256(quote! {0x00});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            110u8, 52u8, 11u8, 156u8, 255u8, 179u8, 122u8, 152u8, 156u8, 165u8, 68u8, 230u8, 187u8,
+            120u8, 10u8, 44u8, 120u8, 144u8, 29u8, 63u8, 179u8, 55u8, 56u8, 118u8, 133u8, 17u8,
+            163u8, 6u8, 23u8, 175u8, 160u8, 29u8
+        ]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn unsupported_literal() {
+        let tokens = sha256(quote! {1.5});
+        let expect = Error::new(
+            Span::call_site(),
+            "expected an integer, string, or byte string literal",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+
+    #[test]
+    fn keccak256_selector() {
+        let tokens = keccak256(quote! {"Transfer(address,address,uint256)"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            221u8, 242u8, 82u8, 173u8, 27u8, 226u8, 200u8, 155u8, 105u8, 194u8, 176u8, 104u8,
+            252u8, 55u8, 141u8, 170u8, 149u8, 43u8, 167u8, 241u8, 99u8, 196u8, 161u8, 22u8, 40u8,
+            245u8, 90u8, 77u8, 245u8, 35u8, 179u8, 239u8
+        ]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn sha3_256_empty() {
+        let tokens = sha3_256(quote! {""});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            167u8, 255u8, 198u8, 248u8, 191u8, 30u8, 215u8, 102u8, 81u8, 193u8, 71u8, 86u8, 160u8,
+            97u8, 214u8, 98u8, 245u8, 128u8, 255u8, 77u8, 228u8, 59u8, 73u8, 250u8, 130u8, 216u8,
+            10u8, 75u8, 128u8, 248u8, 67u8, 74u8
+        ]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn blake2_lengths() {
+        let tokens = blake2b(quote! {32, "abc"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            189u8, 221u8, 129u8, 60u8, 99u8, 66u8, 57u8, 114u8, 49u8, 113u8, 239u8, 63u8, 238u8,
+            152u8, 87u8, 155u8, 148u8, 150u8, 78u8, 59u8, 177u8, 203u8, 62u8, 66u8, 114u8, 98u8,
+            200u8, 192u8, 104u8, 213u8, 35u8, 25u8
+        ]);
+        assert_eq!(parsed, expect);
+
+        let tokens = blake2b(quote! {16, "abc"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            207u8, 74u8, 183u8, 145u8, 198u8, 43u8, 141u8, 43u8, 33u8, 9u8, 201u8, 2u8, 117u8,
+            40u8, 120u8, 22u8
+        ]);
+        assert_eq!(parsed, expect);
+
+        let tokens = blake2s(quote! {32, "abc"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            80u8, 140u8, 94u8, 140u8, 50u8, 124u8, 20u8, 226u8, 225u8, 167u8, 43u8, 163u8, 78u8,
+            235u8, 69u8, 47u8, 55u8, 69u8, 139u8, 32u8, 158u8, 214u8, 58u8, 41u8, 77u8, 153u8,
+            155u8, 76u8, 134u8, 103u8, 89u8, 130u8
+        ]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn blake2_length_bounds() {
+        let table = &[
+            (
+                blake2b(quote! {0, "abc"}),
+                "output length must be 1 to 64 bytes",
+            ),
+            (
+                blake2b(quote! {65, "abc"}),
+                "output length must be 1 to 64 bytes",
+            ),
+            (
+                blake2s(quote! {33, "abc"}),
+                "output length must be 1 to 32 bytes",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(t.0.to_string(), expect, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn hmac_sha256_forms() {
+        // RFC 4231 test case 2.
+        let expect: ExprArray = parse_quote!([
+            91u8, 220u8, 193u8, 70u8, 191u8, 96u8, 117u8, 78u8, 106u8, 4u8, 36u8, 38u8, 8u8, 149u8,
+            117u8, 199u8, 90u8, 0u8, 63u8, 8u8, 157u8, 39u8, 57u8, 131u8, 157u8, 236u8, 88u8,
+            185u8, 100u8, 236u8, 56u8, 67u8
+        ]);
+        let table = &[
+            quote!("Jefe", "what do ya want for nothing?"),
+            quote!(b"Jefe", "what do ya want for nothing?"),
+            quote!(0x4a656665, b"what do ya want for nothing?"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = hmac_sha256(t.clone());
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, expect, "table entry: {}", i);
+        }
+    }
+}
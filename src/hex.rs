@@ -0,0 +1,202 @@
+use crate::error::StrError;
+
+/// Returns the value of a hex digit.
+pub fn value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Encodes bytes as lower case hex, two digits per byte.
+pub fn encode(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        s.push(DIGITS[usize::from(b >> 4)] as char);
+        s.push(DIGITS[usize::from(b & 0xf)] as char);
+    }
+    s
+}
+
+/// Decodes hex, two digits per byte, accepting upper and lower case digits.
+pub fn decode(s: &str) -> Result<Vec<u8>, StrError> {
+    decode_impl(s, |_| false)
+}
+
+/// Decodes hex like [`decode`], ignoring `:`, `-`, and whitespace separators
+/// between bytes, such as in fingerprints `AB:CD:EF` and dumps `ab cd ef`.
+pub fn decode_separated(s: &str) -> Result<Vec<u8>, StrError> {
+    decode_impl(s, |c| c == b':' || c == b'-' || c.is_ascii_whitespace())
+}
+
+/// Decodes a string of `\xNN` escapes, as printed by Python and C, mixed with
+/// plain ASCII characters that each produce their own byte. The escapes `\\`,
+/// `\'`, `\"`, `\0`, `\t`, `\n`, and `\r` are also accepted.
+pub fn decode_escaped(s: &str) -> Result<Vec<u8>, StrError> {
+    let input = s.as_bytes();
+    let mut bytes = Vec::with_capacity(input.len() / 4);
+    let mut i = 0;
+    while i < input.len() {
+        let c = input[i];
+        if !c.is_ascii() {
+            let c = s[i..].chars().next().expect("char at index");
+            return Err(StrError::new(i, format!("non-ASCII character {:?}", c)));
+        }
+        if c != b'\\' {
+            bytes.push(c);
+            i += 1;
+            continue;
+        }
+        let byte = match input.get(i + 1) {
+            Some(b'x') => {
+                let digit = |j: usize| {
+                    input.get(j).and_then(|d| value(*d)).ok_or_else(|| {
+                        StrError::new(i, "escape `\\x` must be followed by two hex digits")
+                    })
+                };
+                let byte = digit(i + 2)? << 4 | digit(i + 3)?;
+                i += 2;
+                byte
+            }
+            Some(b'\\') => b'\\',
+            Some(b'\'') => b'\'',
+            Some(b'"') => b'"',
+            Some(b'0') => 0,
+            Some(b't') => b'\t',
+            Some(b'n') => b'\n',
+            Some(b'r') => b'\r',
+            _ => return Err(StrError::new(i, "invalid escape")),
+        };
+        bytes.push(byte);
+        i += 2;
+    }
+    Ok(bytes)
+}
+
+fn decode_impl(s: &str, is_separator: impl Fn(u8) -> bool) -> Result<Vec<u8>, StrError> {
+    let input = s.as_bytes();
+    let mut bytes = Vec::with_capacity(input.len() / 2);
+    let mut high = None;
+    for (i, c) in input.iter().enumerate() {
+        if is_separator(*c) {
+            if high.is_some() {
+                return Err(StrError::new(i, "separator between the digits of a byte"));
+            }
+            continue;
+        }
+        let v = value(*c)
+            .ok_or_else(|| StrError::new(i, format!("invalid hex character {:?}", *c as char)))?;
+        match high.take() {
+            Some(h) => bytes.push(h << 4 | v),
+            None => high = Some(v),
+        }
+    }
+    if high.is_some() {
+        return Err(StrError::new(input.len() - 1, "odd number of hex digits"));
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode, decode_escaped, decode_separated, encode};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn encoding() {
+        assert_eq!(encode(&[]), "");
+        assert_eq!(encode(&[0x00, 0x0f, 0xde, 0xad]), "000fdead");
+        assert_eq!(
+            decode(&encode(&[1, 2, 0xff])).as_deref(),
+            Ok(&[1, 2, 0xff][..])
+        );
+    }
+
+    #[test]
+    fn decoding() {
+        assert_eq!(decode("").as_deref(), Ok(&[][..]));
+        assert_eq!(decode("00ff").as_deref(), Ok(&[0x00, 0xff][..]));
+        assert_eq!(
+            decode("DeadBEEF").as_deref(),
+            Ok(&[0xde, 0xad, 0xbe, 0xef][..])
+        );
+        assert_eq!(
+            decode("0g"),
+            Err(StrError::new(1, "invalid hex character 'g'"))
+        );
+        assert_eq!(
+            decode("abc"),
+            Err(StrError::new(2, "odd number of hex digits"))
+        );
+    }
+
+    #[test]
+    fn separators() {
+        let expect = &[0xab, 0xcd, 0xef][..];
+        let table: &[&str] = &[
+            "AB:CD:EF",
+            "ab-cd-ef",
+            "ab cd ef",
+            " ab\tcd\nef ",
+            "abcd:ef",
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                decode_separated(t).as_deref(),
+                Ok(expect),
+                "table entry: {}",
+                i
+            );
+        }
+        assert_eq!(
+            decode("ab:cd"),
+            Err(StrError::new(2, "invalid hex character ':'"))
+        );
+        assert_eq!(
+            decode_separated("a:bcd"),
+            Err(StrError::new(1, "separator between the digits of a byte"))
+        );
+    }
+
+    #[test]
+    fn escaped() {
+        let table: &[(&str, &[u8])] = &[
+            ("", b""),
+            (r"\xDE\xAD\xBE\xEF", &[0xde, 0xad, 0xbe, 0xef]),
+            (r"\x00abc\xff", b"\x00abc\xff"),
+            (r#"GET /\r\n\t\0\\\'\""#, b"GET /\r\n\t\0\\'\""),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                decode_escaped(t.0).as_deref(),
+                Ok(t.1),
+                "table entry: {}",
+                i
+            );
+        }
+
+        let table: &[(&str, usize, &str)] = &[
+            (
+                r"ab\xg0",
+                2,
+                "escape `\\x` must be followed by two hex digits",
+            ),
+            (r"\x0", 0, "escape `\\x` must be followed by two hex digits"),
+            (r"\q", 0, "invalid escape"),
+            (r"a\", 1, "invalid escape"),
+            ("\u{e9}", 0, "non-ASCII character '\u{e9}'"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                decode_escaped(t.0),
+                Err(StrError::new(t.1, t.2)),
+                "table entry: {}",
+                i
+            );
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::LitStr;
+
+use crate::{emit, error::StrError, hex};
+
+pub fn hexdump(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<LitStr>(input).and_then(|lit| {
+        let bytes = decode(&lit.value()).map_err(|e| e.into_error(&lit))?;
+        Ok(emit::array(&bytes))
+    }) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Decodes the output of `xxd` or `hexdump -C`. Each line is an offset,
+/// followed by columns of hex, and an ASCII gutter that is discarded. A line
+/// of `*`, used by hexdump for repeated lines, repeats the previous line up to
+/// the offset of the next line.
+pub fn decode(s: &str) -> Result<Vec<u8>, StrError> {
+    let mut bytes = Vec::new();
+    let mut base = None;
+    let mut previous: Vec<u8> = Vec::new();
+    let mut repeat = false;
+    let mut start = 0;
+    for line in s.split('\n') {
+        let line_start = start;
+        start += line.len() + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        if trimmed == "*" {
+            repeat = true;
+            continue;
+        }
+
+        // The offset ends at a colon for xxd, or whitespace for hexdump.
+        let offset_end = trimmed
+            .find(|c: char| c == ':' || c.is_whitespace())
+            .unwrap_or(trimmed.len());
+        let offset = u64::from_str_radix(&trimmed[..offset_end], 16).map_err(|_| {
+            StrError::new(
+                line_start + indent,
+                "expected a hex offset at the start of the line",
+            )
+        })?;
+        let base = *base.get_or_insert(offset);
+        let expected = base + bytes.len() as u64;
+        if repeat && offset > expected && !previous.is_empty() {
+            let fill = (offset - expected) as usize;
+            bytes.extend(previous.iter().cycle().take(fill));
+        } else if offset != expected {
+            return Err(StrError::new(
+                line_start + indent,
+                format!(
+                    "offset {:x} does not follow the previous line, expected {:x}",
+                    offset, expected
+                ),
+            ));
+        }
+        repeat = false;
+
+        // The hex columns end at the gutter, which xxd separates from the
+        // columns with two spaces, and hexdump starts with a `|`.
+        let xxd = trimmed[offset_end..].starts_with(':');
+        let rest_start = indent + offset_end + usize::from(xxd);
+        let rest = &line[rest_start..];
+        let columns_start = rest_start + (rest.len() - rest.trim_start().len());
+        let columns = &line[columns_start..];
+        let columns = if xxd {
+            columns.split("  ").next().unwrap_or("")
+        } else {
+            columns.split('|').next().unwrap_or("")
+        };
+
+        previous.clear();
+        let mut i = 0;
+        while let Some(group_start) = columns[i..].find(|c: char| !c.is_whitespace()) {
+            let group_start = i + group_start;
+            let group_end = columns[group_start..]
+                .find(char::is_whitespace)
+                .map_or(columns.len(), |end| group_start + end);
+            let decoded = hex::decode(&columns[group_start..group_end]).map_err(|e| {
+                StrError::new(
+                    line_start + columns_start + group_start + e.index,
+                    e.message,
+                )
+            })?;
+            previous.extend(decoded);
+            i = group_end;
+        }
+        bytes.extend(&previous);
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode, hexdump};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray};
+
+    #[test]
+    fn xxd() {
+        let dump = "00000000: 4865 6c6c 6f2c 2077 6f72 6c64 2120 6361  Hello, world! ca
+00000010: 6665                                     fe
+";
+        assert_eq!(decode(dump).as_deref(), Ok(&b"Hello, world! cafe"[..]));
+    }
+
+    #[test]
+    fn hexdump_canonical() {
+        let dump = "
+            00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 20 63 61  |Hello, world! ca|
+            00000010  66 65                                             |fe|
+            00000012
+        ";
+        assert_eq!(decode(dump).as_deref(), Ok(&b"Hello, world! cafe"[..]));
+    }
+
+    #[test]
+    fn repeated_lines() {
+        let dump = "00000000  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |................|
+*
+00000030  01                                                |.|
+00000031
+";
+        let mut expect = vec![0u8; 0x30];
+        expect.push(1);
+        assert_eq!(decode(dump), Ok(expect));
+    }
+
+    #[test]
+    fn gutter_separators() {
+        let dump = "00000000: 4142 7c43  AB|C";
+        assert_eq!(decode(dump).as_deref(), Ok(&b"AB|C"[..]));
+    }
+
+    #[test]
+    fn nonzero_start() {
+        let dump = "00000100: 0102  ..\n00000102: 03  .";
+        assert_eq!(decode(dump).as_deref(), Ok(&[1, 2, 3][..]));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            decode("00000000: 0102  ..\n00000004: 03  ."),
+            Err(StrError::new(
+                19,
+                "offset 4 does not follow the previous line, expected 2"
+            ))
+        );
+        assert_eq!(
+            decode("00000000: 01g2  ..."),
+            Err(StrError::new(12, "invalid hex character 'g'"))
+        );
+        assert_eq!(
+            decode("hello"),
+            Err(StrError::new(
+                0,
+                "expected a hex offset at the start of the line"
+            ))
+        );
+    }
+
+    #[test]
+    fn macro_output() {
+        let tokens = hexdump(quote! {"00000000: dead beef  ...."});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([222u8, 173u8, 190u8, 239u8]);
+        assert_eq!(parsed, expect);
+    }
+}
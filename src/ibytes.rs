@@ -0,0 +1,150 @@
+use std::str::FromStr;
+
+use num_bigint::BigInt;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitInt,
+};
+
+use crate::{args::Args, emit, suggest};
+
+struct Input {
+    lit: LitInt,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn ibytes(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let size = input.args.int::<usize>("size")?;
+    input.args.finish()?;
+    let suffix = input.lit.suffix();
+    let suffix_size = suggest::suffix_size(suffix);
+    let size = match (size, suffix_size) {
+        (Some(0), _) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "size must be greater than zero",
+            ))
+        }
+        (Some(size), Some(suffix_size)) if size != suffix_size => {
+            return Err(Error::new(
+                input.lit.span(),
+                format!(
+                    "the `{}` suffix is {} byte(s) but the size is {} byte(s), use `size = {}` or remove the suffix",
+                    suffix, suffix_size, size, suffix_size
+                ),
+            ))
+        }
+        (Some(size), _) => size,
+        (None, Some(suffix_size)) => {
+            return Err(Error::new(
+                Span::call_site(),
+                format!(
+                    "missing required option `size`, use `size = {}` for the `{}` suffix",
+                    suffix_size, suffix
+                ),
+            ))
+        }
+        (None, None) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "missing required option `size`",
+            ))
+        }
+    };
+
+    // The base10 digits of a literal validated by syn always parse, with a
+    // leading minus sign for negative values.
+    let int = BigInt::from_str(input.lit.base10_digits()).expect("valid integer");
+
+    let bytes = emit::signed_bytes(&int, size).map_err(|min| {
+        Error::new(
+            input.lit.span(),
+            format!(
+                "value does not fit in {} byte(s) as a two's complement integer, {}",
+                size,
+                suggest::size(min)
+            ),
+        )
+    })?;
+
+    Ok(emit::array(&bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::ibytes;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn twos_complement() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(-1, size = 1), parse_quote!([255u8])),
+            (
+                quote!(-1, size = 4),
+                parse_quote!([255u8, 255u8, 255u8, 255u8]),
+            ),
+            (quote!(-128; size = 1), parse_quote!([128u8])),
+            (quote!(-129, size = 2), parse_quote!([255u8, 127u8])),
+            (quote!(-0x100, size = 2), parse_quote!([255u8, 0u8])),
+            (quote!(0, size = 2), parse_quote!([0u8, 0u8])),
+            (quote!(127, size = 1), parse_quote!([127u8])),
+            (quote!(0x7fff, size = 3), parse_quote!([0u8, 127u8, 255u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = ibytes(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(_, &str)] = &[
+            (quote!(-1), "missing required option `size`"),
+            (quote!(-1, size = 0), "size must be greater than zero"),
+            (quote!(-1, size = 1, signed), "unknown option `signed`"),
+            (
+                quote!(-129, size = 1),
+                "value does not fit in 1 byte(s) as a two's complement integer, use `size = 2` or greater",
+            ),
+            (
+                quote!(128, size = 1),
+                "value does not fit in 1 byte(s) as a two's complement integer, use `size = 2` or greater",
+            ),
+            (
+                quote!(-1i16),
+                "missing required option `size`, use `size = 2` for the `i16` suffix",
+            ),
+            (
+                quote!(-1i16, size = 4),
+                "the `i16` suffix is 2 byte(s) but the size is 4 byte(s), use `size = 2` or remove the suffix",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = ibytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::LitStr;
+
+use crate::{emit, error::StrError, hex};
+
+pub fn ip(input: TokenStream2) -> TokenStream2 {
+    let lit = match syn::parse2::<LitStr>(input) {
+        Ok(lit) => lit,
+        Err(e) => return e.to_compile_error(),
+    };
+    let value = lit.value();
+    let result = if value.contains(':') {
+        parse_v6(&value).map(|b| b.to_vec())
+    } else {
+        parse_v4(&value, 0).map(|b| b.to_vec())
+    };
+    match result {
+        Ok(bytes) => emit::array(&bytes),
+        Err(e) => e.into_error(&lit).to_compile_error(),
+    }
+}
+
+/// Parses an IPv4 address in dotted decimal form. The offset is the index of
+/// the address within the string literal, for errors.
+pub fn parse_v4(s: &str, offset: usize) -> Result<[u8; 4], StrError> {
+    let mut bytes = [0u8; 4];
+    let mut pos = offset;
+    let mut count = 0;
+    for part in s.split('.') {
+        if count == 4 {
+            return Err(StrError::new(pos - 1, "IPv4 address has more than 4 parts"));
+        }
+        if part.is_empty() {
+            return Err(StrError::new(pos, "empty IPv4 address part"));
+        }
+        if let Some(i) = part.bytes().position(|c| !c.is_ascii_digit()) {
+            return Err(StrError::new(
+                pos + i,
+                format!("invalid IPv4 character {:?}", part.as_bytes()[i] as char),
+            ));
+        }
+        if part.len() > 1 && part.starts_with('0') {
+            return Err(StrError::new(
+                pos,
+                "leading zeros are not supported in IPv4 address parts",
+            ));
+        }
+        bytes[count] = part
+            .parse()
+            .map_err(|_| StrError::new(pos, "IPv4 address part is greater than 255"))?;
+        pos += part.len() + 1;
+        count += 1;
+    }
+    if count != 4 {
+        return Err(StrError::new(
+            offset + s.len(),
+            format!("IPv4 address has {} parts, expected 4", count),
+        ));
+    }
+    Ok(bytes)
+}
+
+/// Parses an IPv6 address, supporting `::` compression of zero groups, and an
+/// embedded IPv4 address in the last 32 bits.
+pub fn parse_v6(s: &str) -> Result<[u8; 16], StrError> {
+    let (head, tail) = match s.find("::") {
+        Some(i) => {
+            if let Some(j) = s[i + 2..].find("::") {
+                return Err(StrError::new(i + 2 + j, "`::` may only appear once"));
+            }
+            (&s[..i], Some((i + 2, &s[i + 2..])))
+        }
+        None => (s, None),
+    };
+    let head = groups(head, 0, tail.is_none())?;
+    let groups = match tail {
+        Some((offset, tail)) => {
+            let tail = groups(tail, offset, true)?;
+            if head.len() + tail.len() > 7 {
+                return Err(StrError::new(
+                    s.len(),
+                    "IPv6 address with `::` has more than 7 groups",
+                ));
+            }
+            let mut groups = head;
+            groups.resize(8 - tail.len(), 0);
+            groups.extend(tail);
+            groups
+        }
+        None if head.len() != 8 => {
+            return Err(StrError::new(
+                s.len(),
+                format!("IPv6 address has {} groups, expected 8", head.len()),
+            ));
+        }
+        None => head,
+    };
+
+    let mut bytes = [0u8; 16];
+    for (i, g) in groups.iter().enumerate() {
+        bytes[i * 2..i * 2 + 2].copy_from_slice(&g.to_be_bytes());
+    }
+    Ok(bytes)
+}
+
+/// Parses colon separated groups of an IPv6 address, where the last group may
+/// be an IPv4 address if allowed.
+fn groups(s: &str, offset: usize, allow_v4: bool) -> Result<Vec<u16>, StrError> {
+    let mut groups = Vec::new();
+    if s.is_empty() {
+        return Ok(groups);
+    }
+    let parts = s.split(':').collect::<Vec<_>>();
+    let mut pos = offset;
+    for (n, part) in parts.iter().enumerate() {
+        if allow_v4 && n == parts.len() - 1 && part.contains('.') {
+            let v4 = parse_v4(part, pos)?;
+            groups.push(u16::from_be_bytes([v4[0], v4[1]]));
+            groups.push(u16::from_be_bytes([v4[2], v4[3]]));
+            break;
+        }
+        if part.is_empty() {
+            return Err(StrError::new(pos, "empty IPv6 address group"));
+        }
+        let mut group = 0u16;
+        for (i, c) in part.bytes().enumerate() {
+            if i == 4 {
+                return Err(StrError::new(
+                    pos + i,
+                    "IPv6 address group has more than 4 hex digits",
+                ));
+            }
+            let v = hex::value(c).ok_or_else(|| {
+                StrError::new(pos + i, format!("invalid IPv6 character {:?}", c as char))
+            })?;
+            group = group << 4 | u16::from(v);
+        }
+        groups.push(group);
+        pos += part.len() + 1;
+    }
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ip, parse_v4, parse_v6};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn v4() {
+        assert_eq!(parse_v4("192.168.1.1", 0), Ok([192, 168, 1, 1]));
+        assert_eq!(parse_v4("0.0.0.0", 0), Ok([0, 0, 0, 0]));
+        assert_eq!(parse_v4("255.255.255.255", 0), Ok([255, 255, 255, 255]));
+
+        let table: &[(&str, StrError)] = &[
+            (
+                "192.168.1",
+                StrError::new(9, "IPv4 address has 3 parts, expected 4"),
+            ),
+            (
+                "192.168.1.1.1",
+                StrError::new(11, "IPv4 address has more than 4 parts"),
+            ),
+            ("192.168..1", StrError::new(8, "empty IPv4 address part")),
+            (
+                "192.168.256.1",
+                StrError::new(8, "IPv4 address part is greater than 255"),
+            ),
+            (
+                "192.168.01.1",
+                StrError::new(8, "leading zeros are not supported in IPv4 address parts"),
+            ),
+            (
+                "192.16a.1.1",
+                StrError::new(6, "invalid IPv4 character 'a'"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse_v4(t.0, 0).as_ref(), Err(&t.1), "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn v6() {
+        let table: &[(&str, [u8; 16])] = &[
+            ("::", [0; 16]),
+            ("::1", [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+            ("1::", [0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            (
+                "2001:db8::1",
+                [0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            ),
+            (
+                "2001:DB8:0:0:8:800:200C:417A",
+                [
+                    0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 8, 8, 0, 0x20, 0x0c, 0x41, 0x7a,
+                ],
+            ),
+            (
+                "::ffff:192.0.2.128",
+                [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 192, 0, 2, 128],
+            ),
+            (
+                "64:ff9b:0:0:0:0:192.0.2.33",
+                [0, 0x64, 0xff, 0x9b, 0, 0, 0, 0, 0, 0, 0, 0, 192, 0, 2, 33],
+            ),
+            (
+                "1:2:3:4:5:6:7::",
+                [0, 1, 0, 2, 0, 3, 0, 4, 0, 5, 0, 6, 0, 7, 0, 0],
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse_v6(t.0), Ok(t.1), "table entry: {}", i);
+        }
+
+        let table: &[(&str, StrError)] = &[
+            ("1::2::3", StrError::new(4, "`::` may only appear once")),
+            (
+                "1:2:3:4:5:6:7",
+                StrError::new(13, "IPv6 address has 7 groups, expected 8"),
+            ),
+            (
+                "1:2:3:4:5:6:7::8",
+                StrError::new(16, "IPv6 address with `::` has more than 7 groups"),
+            ),
+            (
+                "1:2:3:4:5:6:7:8:9",
+                StrError::new(17, "IPv6 address has 9 groups, expected 8"),
+            ),
+            (
+                "2001:db8:::1",
+                StrError::new(10, "empty IPv6 address group"),
+            ),
+            (
+                "2001:db8::12345",
+                StrError::new(14, "IPv6 address group has more than 4 hex digits"),
+            ),
+            (
+                "2001:dg8::1",
+                StrError::new(6, "invalid IPv6 character 'g'"),
+            ),
+            (
+                "::ffff:192.0.2.256",
+                StrError::new(15, "IPv4 address part is greater than 255"),
+            ),
+            ("1.2.3.4::", StrError::new(1, "invalid IPv6 character '.'")),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse_v6(t.0).as_ref(), Err(&t.1), "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn macro_output() {
+        let tokens = ip(quote! {"192.168.1.1"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([192u8, 168u8, 1u8, 1u8]);
+        assert_eq!(parsed, expect);
+
+        let tokens = ip(quote! {"::1"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 1u8
+        ]);
+        assert_eq!(parsed, expect);
+
+        let tokens = ip(quote! {"10.0.0.x"});
+        let expect = Error::new(Span::call_site(), "invalid IPv4 character 'x' at index 7")
+            .to_compile_error()
+            .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
@@ -0,0 +1,316 @@
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote, Attribute, Error, Expr, Ident, Item, Token, Type, Visibility,
+};
+
+use crate::{bytes, diagnostic, emit};
+
+/// Input to the item macros: attributes, visibility, and the name of the
+/// item, followed by `=` or `,` and the input of the bytes macro.
+struct Input {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    bytes: bytes::Input,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+        } else {
+            input.parse::<Token![,]>()?;
+        }
+        let bytes = input.parse()?;
+        Ok(Self {
+            attrs,
+            vis,
+            name,
+            bytes,
+        })
+    }
+}
+
+pub fn const_bytes(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(|input| expand(input, quote!(const))) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn static_bytes(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(|input| expand(input, quote!(static))) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Expands to an item of the kind given, `const` or `static`.
+fn expand(input: Input, kind: TokenStream2) -> Result<TokenStream2, Error> {
+    let Input {
+        attrs,
+        vis,
+        name,
+        bytes,
+    } = input;
+    let bytes::Output { bytes, warnings } = bytes::value(bytes)?;
+    let len = bytes.len();
+    let array = emit::array(&bytes);
+    let warnings = diagnostic::warnings(&warnings);
+    Ok(quote! {
+        #warnings
+        #(#attrs)*
+        #vis #kind #name: [u8; #len] = #array;
+    })
+}
+
+pub fn bytes_table(input: TokenStream2) -> TokenStream2 {
+    let mut tokens = TokenStream2::new();
+    let mut error: Option<Error> = None;
+    for entry in entries(input) {
+        match syn::parse2::<Input>(entry).and_then(|input| expand(input, quote!(const))) {
+            Ok(item) => tokens.extend(item),
+            Err(e) => match &mut error {
+                Some(error) => error.combine(e),
+                None => error = Some(e),
+            },
+        }
+    }
+    // The items of valid entries are emitted with the errors of the others,
+    // so uses of the valid constants do not also error.
+    if let Some(error) = error {
+        tokens.extend(error.to_compile_error());
+    }
+    tokens
+}
+
+/// Splits the input of the table macro into entries, which are separated by
+/// semicolons. A semicolon followed by a literal is a repeat count, and is
+/// part of the entry.
+fn entries(input: TokenStream2) -> Vec<TokenStream2> {
+    let mut entries = Vec::new();
+    let mut entry = Vec::new();
+    let mut trees = input.into_iter().peekable();
+    while let Some(tree) = trees.next() {
+        match &tree {
+            TokenTree::Punct(punct)
+                if punct.as_char() == ';'
+                    && !matches!(trees.peek(), Some(TokenTree::Literal(_))) =>
+            {
+                entries.push(entry.drain(..).collect());
+            }
+            _ => entry.push(tree),
+        }
+    }
+    if !entry.is_empty() {
+        entries.push(entry.into_iter().collect());
+    }
+    entries
+}
+
+pub fn bytes_item(attr: TokenStream2, item: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Item>(item).and_then(|item| expand_item(attr, item)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Expands a const or static item whose value is the input of the bytes
+/// macro, with any options given in the attribute, replacing the value with
+/// the array. A type of `_` is replaced with the array type, and any other
+/// type is kept so the compiler checks the length.
+fn expand_item(attr: TokenStream2, mut item: Item) -> Result<TokenStream2, Error> {
+    let (ty, expr) = match &mut item {
+        Item::Const(item) => (&mut item.ty, &mut item.expr),
+        Item::Static(item) => (&mut item.ty, &mut item.expr),
+        _ => return Err(Error::new_spanned(item, "expected a const or static item")),
+    };
+    let input = if attr.is_empty() {
+        syn::parse2::<bytes::Input>(quote!(#expr))?
+    } else {
+        syn::parse2::<bytes::Input>(quote!(#expr; #attr))?
+    };
+    let bytes::Output { bytes, warnings } = bytes::value(input)?;
+    if let Type::Infer(_) = **ty {
+        let len = bytes.len();
+        *ty = parse_quote!([u8; #len]);
+    }
+    **expr = syn::parse2::<Expr>(emit::array(&bytes))?;
+    let warnings = diagnostic::warnings(&warnings);
+    Ok(quote! {
+        #warnings
+        #item
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bytes_item, bytes_table, const_bytes, static_bytes};
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, File, Item, ItemConst, ItemStatic};
+
+    #[test]
+    fn items() {
+        let table: &[(_, ItemConst)] = &[
+            (
+                quote!(FOO, 0x0102),
+                parse_quote!(
+                    const FOO: [u8; 2usize] = [1u8, 2u8];
+                ),
+            ),
+            (
+                quote!(
+                    /// The key.
+                    #[allow(dead_code)]
+                    pub(crate) KEY = "dead", 0xbeef
+                ),
+                parse_quote!(
+                    /// The key.
+                    #[allow(dead_code)]
+                    pub(crate) const KEY: [u8; 4usize] = [222u8, 173u8, 190u8, 239u8];
+                ),
+            ),
+            (
+                quote!(pub ZEROS = 0x00; 3),
+                parse_quote!(
+                    pub const ZEROS: [u8; 3usize] = [0u8, 0u8, 0u8];
+                ),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = const_bytes(t.0);
+            let parsed = syn::parse2::<ItemConst>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn statics() {
+        let tokens = static_bytes(quote! {
+            #[link_section = ".keys"]
+            #[used]
+            pub KEY = 0xdeadbeef
+        });
+        let parsed = syn::parse2::<ItemStatic>(tokens).unwrap();
+        let expect: ItemStatic = parse_quote!(
+            #[link_section = ".keys"]
+            #[used]
+            pub static KEY: [u8; 4usize] = [222u8, 173u8, 190u8, 239u8];
+        );
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn table() {
+        let tokens = bytes_table(quote! {
+            /// The magic number.
+            pub MAGIC = 0xCAFEBABE;
+            pub(crate) SALT = 0x00112233;
+            TAG = b"v1";
+            ZEROS = 0x00; 3, size = 4;
+        });
+        let parsed = syn::parse2::<File>(tokens).unwrap();
+        let expect: File = parse_quote! {
+            /// The magic number.
+            pub const MAGIC: [u8; 4usize] = [202u8, 254u8, 186u8, 190u8];
+            pub(crate) const SALT: [u8; 4usize] = [0u8, 17u8, 34u8, 51u8];
+            const TAG: [u8; 2usize] = [118u8, 49u8];
+            const ZEROS: [u8; 4usize] = [0u8, 0u8, 0u8, 0u8];
+        };
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn table_errors() {
+        let tokens = bytes_table(quote! {
+            A = -1;
+            B = 0x01;
+            C = 01;
+        });
+        let expect = quote! {
+            const B: [u8; 1usize] = [1u8];
+        };
+        let mut error = Error::new(
+            Span::call_site(),
+            "negative values unsupported, use ibytes! with `size = 1` for two's complement bytes",
+        );
+        error.combine(Error::new(
+            Span::call_site(),
+            "leading zeros are not preserved or supported on integer literals in decimal form, remove the zeros and use the `size` option for a fixed width, such as `1, size = 1`, or write the value in hex as `0x01`, where leading zero digits are preserved",
+        ));
+        assert_eq!(
+            tokens.to_string(),
+            quote!(#expect).to_string() + " " + &error.to_compile_error().to_string()
+        );
+    }
+
+    #[test]
+    fn attribute() {
+        let table: &[(_, _, Item)] = &[
+            (
+                quote!(),
+                quote!(
+                    /// The key.
+                    pub const KEY: _ = 0x00ff;
+                ),
+                parse_quote!(
+                    /// The key.
+                    pub const KEY: [u8; 2usize] = [0u8, 255u8];
+                ),
+            ),
+            (
+                quote!(),
+                quote!(
+                    const KEY: [u8; 2] = "dead";
+                ),
+                parse_quote!(
+                    const KEY: [u8; 2] = [222u8, 173u8];
+                ),
+            ),
+            (
+                quote!(size = 4),
+                quote!(
+                    static mut KEY: _ = 0xff;
+                ),
+                parse_quote!(
+                    static mut KEY: [u8; 4usize] = [0u8, 0u8, 0u8, 255u8];
+                ),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = bytes_item(t.0, t.1);
+            let parsed = syn::parse2::<Item>(tokens).unwrap();
+            assert_eq!(parsed, t.2, "table entry: {}", i);
+        }
+
+        let tokens = bytes_item(
+            quote!(),
+            quote!(
+                fn key() {}
+            ),
+        );
+        let expect = Error::new(Span::call_site(), "expected a const or static item")
+            .to_compile_error()
+            .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+
+    #[test]
+    fn errors() {
+        let tokens = const_bytes(quote! {FOO = -1});
+        let expect = Error::new(
+            Span::call_site(),
+            "negative values unsupported, use ibytes! with `size = 1` for two's complement bytes",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
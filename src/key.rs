@@ -0,0 +1,115 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitInt, Token,
+};
+
+use crate::{bytes, emit};
+
+/// Input to the key_bytes macro: the length of the key in bits, followed by
+/// `,` and the input of the bytes macro.
+struct Input {
+    bits: LitInt,
+    key: bytes::Input,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let bits = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let key = input.parse()?;
+        Ok(Self { bits, key })
+    }
+}
+
+pub fn key_bytes(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(input: Input) -> Result<TokenStream2, Error> {
+    let bits = input.bits.base10_parse::<usize>()?;
+    if bits == 0 || bits % 8 != 0 {
+        return Err(Error::new(
+            input.bits.span(),
+            "key length must be a positive multiple of 8 bits",
+        ));
+    }
+    let bytes::Output { bytes, warnings } = bytes::value(input.key)?;
+
+    // A key whose first byte is partially filled is likely a truncated copy,
+    // so the warnings are errors.
+    let mut errors = warnings.iter().map(|w| Error::new(w.span, &w.message));
+    if let Some(mut error) = errors.next() {
+        error.extend(errors);
+        return Err(error);
+    }
+
+    if bytes.len() * 8 != bits {
+        return Err(Error::new(
+            input.bits.span(),
+            format!(
+                "key is {} bits ({} bytes), expected {} bits ({} bytes)",
+                bytes.len() * 8,
+                bytes.len(),
+                bits,
+                bits / 8
+            ),
+        ));
+    }
+    Ok(emit::array(&bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::key_bytes;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn lengths() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(32, 0x00000001), parse_quote!([0u8, 0u8, 0u8, 1u8])),
+            (quote!(16, "beef"), parse_quote!([190u8, 239u8])),
+            (quote!(8, 0x00), parse_quote!([0u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = key_bytes(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table = &[
+            (
+                quote!(32, 0x000001),
+                "key is 24 bits (3 bytes), expected 32 bits (4 bytes)",
+            ),
+            (
+                quote!(16, 0x000001),
+                "key is 24 bits (3 bytes), expected 16 bits (2 bytes)",
+            ),
+            (
+                quote!(12, 0x0001),
+                "key length must be a positive multiple of 8 bits",
+            ),
+            (
+                quote!(16, 0xfff),
+                "odd number of hex digits, the first byte holds a single digit, add leading zeros to fill the byte or use the `allow_odd` option",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = key_bytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
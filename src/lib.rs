@@ -1,9 +1,88 @@
 //! Bytes converts literals into an array of bytes.
 //!
-//! Currently supports only integer literals of unbounded size.
+//! Currently supports integer literals of unbounded size, hex string literals,
+//! and a variety of string encodings.
+//!
+//! Macros that take options share one grammar. The options follow the input,
+//! separated from it by a comma or semicolon and from each other by commas,
+//! and each is a flag or a `name = value` pair:
+//!
+//! ```
+//! let bytes = bytes_lit::bytes!(0x1234; size = 4, reverse);
+//! assert_eq!(bytes, [0x34, 0x12, 0x00, 0x00]);
+//! ```
+//!
+//! Unknown and duplicate options are errors, as are options that cannot be
+//! used together, such as `size` and `pad_right`, and options given without
+//! an option they require, such as `truncate` without `size`.
+
+#![cfg_attr(feature = "nightly", feature(proc_macro_diagnostic))]
 
+mod args;
+mod asset_code;
+mod base32;
+mod base45;
+mod base58;
+mod base64;
+mod base85;
+mod bcd;
+mod bech32;
+mod bitfields;
+mod bits;
+mod bitwise;
+mod borsh;
 mod bytes;
 mod bytesmin;
+mod cbor;
+mod cbytes;
+mod cid;
+mod compress;
+mod crc;
+mod der;
+mod diagnostic;
+mod digest;
+mod eager;
+mod ecc;
+mod emit;
+mod env;
+mod error;
+mod eth;
+mod expr;
+mod file;
+mod firmware;
+mod gray;
+mod gunzip;
+mod hash;
+mod hex;
+mod hexdump;
+mod ibytes;
+mod ip;
+mod item;
+mod key;
+mod mac;
+mod msgpack;
+mod multibase;
+mod multihash;
+mod network_id;
+mod nibbles;
+mod pem;
+mod radix;
+mod rand;
+mod rlp;
+mod scale;
+mod scaled;
+mod signmag;
+mod strkey;
+mod suggest;
+mod timestamp;
+mod typed;
+mod ulid;
+mod urldecode;
+mod uuid;
+mod varint;
+mod words;
+mod xdr;
+mod zeros;
 
 use proc_macro::TokenStream;
 
@@ -11,7 +90,7 @@ extern crate proc_macro;
 
 /// Bytes converts literals into an array of bytes.
 ///
-/// Currently supports only integer literals of unbounded size.
+/// Currently supports integer literals of unbounded size, and hex string literals.
 ///
 /// The following integer literal forms are supported and preserve leading
 /// zeros. The final byte representation always returns a consistent number of
@@ -23,15 +102,134 @@ extern crate proc_macro;
 /// the number are preserved as zeros in the final bytes. For example: `0x0001`
 /// will produce `[0, 1]`.
 ///
-/// The following integer literal forms are supported and prohibit leading
-/// zeros. The number of bytes returned is not based off the number of digits
-/// entered.
-/// - Base 10 (decimal)
-/// - Base 8 (octal)
+/// A hex literal with an odd number of digits, or a binary literal with a
+/// number of digits that is not a multiple of 8, has a partially filled first
+/// byte. For example: `0xFFF` will produce `[0x0F, 0xFF]`. A warning is emitted
+/// for these literals, unless the `allow_odd` option is given, and with the
+/// `strict` option they are an error.
+///
+/// Base 8 (octal) literals preserve leading zeros as 3 zero bits per zero
+/// digit, which precede the bits of the value, and the total is rounded up to
+/// whole bytes. For example: `0o377` will produce `[0xFF]`, `0o0377` is 11 bits
+/// and will produce `[0x00, 0xFF]`, and `0o000377` is 17 bits and will produce
+/// `[0x00, 0x00, 0xFF]`. Octal digits do not divide bytes, so no warning is
+/// emitted for a partially filled first byte.
+///
+/// Base 10 (decimal) literals prohibit leading zeros. The number of bytes
+/// returned is not based off the number of digits entered, and is the minimum
+/// number of bytes required to represent the integer. Use the `size` option
+/// for a fixed width, such as `bytes!(255, size = 4)`, which the error for
+/// leading zeros suggests. A decimal zero, which has no width of its own, is
+/// only accepted with `size`.
+///
+/// The `deny-decimal` and `deny-octal` features of this crate make integer
+/// literals in those forms an error, for codebases that only allow hex and
+/// binary.
+///
+/// A type suffix on an integer literal, such as `u32`, does not change the
+/// bytes produced. A warning is emitted when the suffix is a different size to
+/// the bytes.
+///
+/// A hex string literal produces one byte per two hex digits, preserving all
+/// leading zeros. The separators `:`, `-`, and whitespace may appear between
+/// bytes and are ignored, so fingerprints such as `"AB:CD:EF"` can be used
+/// verbatim.
+///
+/// Since whitespace is ignored, long hex strings can span multiple lines, and
+/// several adjacent string literals are concatenated, so that large constants
+/// can be formatted readably. Each string literal must contain whole bytes.
+/// This is the input of the `hex!` macro of the hex-literal crate, so its call
+/// sites can be changed to `bytes!` without other changes.
+///
+/// A string literal containing `\x` escapes, such as `r"\xDE\xAD"` copied
+/// from Python or C, instead produces one byte per `\xNN` escape and one byte
+/// per plain ASCII character. The escapes `\\`, `\'`, `\"`, `\0`, `\t`, `\n`,
+/// and `\r` are also accepted.
+///
+/// A call of the builtin macros `concat!`, `env!`, or `stringify!` is
+/// expanded by the macro and its result read as a string literal, so values
+/// can be composed from pieces known at build time, such as
+/// `concat!("dead", env!("KEY"))`. The environment variable is read when the
+/// macro expands, and changes to it are not tracked by the compiler, use
+/// [`bytes_env!`] when they must be.
+///
+/// A byte string literal, such as `b"v1"`, produces its bytes as they are.
+///
+/// A range of byte values, `start..end` or `start..=end`, produces the
+/// sequence of bytes in the range. Range bounds must fit in a byte.
+///
+/// An integer expression, of integer literals combined with the operators
+/// `+ - * << >> | & ^` and parentheses, is evaluated and produces the minimum
+/// number of bytes required to represent the result, as for decimal literals.
+/// The result of a left shift is limited to 2^20 bits.
 ///
-/// For integer literal forms that do not have consistent digit to byte lengths,
-/// the number of bytes returned is the minimum number of bytes required to
-/// represent the integer.
+/// The literal may be followed by a semicolon and a count, `lit; count`, to
+/// repeat the bytes of the literal `count` times in one flat array, of at most
+/// 16 MiB.
+///
+/// Multiple literals, separated by commas, are concatenated. Each may be
+/// preceded by doc comments or `#[doc = "..."]` attributes that label it and
+/// are otherwise ignored, as are regular comments.
+///
+/// The literals may be followed by a type ascription, `=> [u8; N]`, stating
+/// the length of the bytes after all options are applied. It is an error for
+/// the bytes to be another length, unless the `pad` flag is given to zero pad
+/// them on the left, or the `truncate` option to keep N bytes, so a change in
+/// the length of a value is caught where it is written.
+///
+/// With the `xdr` feature of this crate, the literals may be followed by an
+/// output type, `; as Hash` or `; as Uint256`, of the types of the stellar-xdr
+/// crate that wrap an array of 32 bytes. The constructor of the type is called
+/// with the array, so no runtime conversion is needed, and it is an error for
+/// the bytes to be another length. The type is referred to by the path given,
+/// such as `as stellar_xdr::curr::Hash`, and options follow it.
+///
+/// The array is also valid as a pattern, such as in a match arm, when there
+/// are no warnings and no more than 256 bytes. Use [`bytes_pat!`] for patterns
+/// that are always valid.
+///
+/// Options follow the literals, separated by a comma or semicolon:
+/// - `size = N` zero pads the bytes on the left to N bytes. It is an error for
+///   the bytes to be longer than N, unless the `truncate` flag is given to keep
+///   the leading N bytes, or `truncate = trailing` to keep the trailing N bytes.
+///   N is at most 16 MiB.
+/// - `append = crc32`, `append = crc16`, or `append = crc16_xmodem` appends
+///   the big endian CRC of the bytes. See [`crc32!`] and [`crc16!`] for the
+///   algorithms.
+/// - `chunk = N` splits the bytes into rows of N bytes, producing an array of
+///   arrays, `[[u8; N]; M]`. It is an error if the number of bytes is not a
+///   multiple of N, unless the `chunk_pad` flag is given to zero pad the last
+///   row.
+/// - `pad_right = N` zero pads the bytes on the right to N bytes, before any
+///   CRC is appended, for fixed size text fields such as asset codes. It is an
+///   error if the bytes do not fit, or if `size` is also given. N is at most
+///   16 MiB.
+/// - `strict` errors on hex and binary literals that do not fill whole bytes,
+///   and `allow_odd` allows them without a warning.
+/// - `reverse` reverses the order of the bytes, after any CRC is appended, such
+///   as to write a little endian value most significant byte first.
+/// - `swap = N` reverses the order of the bytes within each group of N bytes,
+///   after `reverse`, such as `swap = 4` to write the little endian 32-bit
+///   words of a register dump as big endian hex. It is an error if the number
+///   of bytes is not a multiple of N.
+/// - `bitreflect` reverses the order of the bits within each byte, for the
+///   reflected bit order of CRC tables and LSB first serial peripherals.
+/// - `range = a..b` keeps only the bytes in the range, such as `range = 0..4`
+///   for the first four bytes of a digest, after the options above are
+///   applied. It is an error for the range to be out of bounds.
+/// - `align = N` zero pads the bytes on the right to the next multiple of N
+///   bytes, after all other options are applied, such as for the 4 byte
+///   alignment of XDR. N is at most 16 MiB.
+/// - `pad` zero pads the bytes on the left to the length of the type
+///   ascription, which it requires.
+/// - `sha256 = <digest>` verifies that the SHA-256 digest of the bytes, after
+///   all other options are applied, is the digest given as a hex integer or
+///   string literal, so a corrupted or replaced blob fails to compile with
+///   both digests in the error.
+/// - `byte_str` emits the bytes as a byte string literal, `*b"..."`, which
+///   has the same type and value as the array but is a single token. Arrays
+///   of more than 256 bytes are always emitted this way, which is faster to
+///   compile for large constants.
 ///
 /// ### Examples
 ///
@@ -60,11 +258,260 @@ extern crate proc_macro;
 ///     250, 111, 250, 174, 51, 86, 47, 119, 205, 43, 98, 158, 247, 253, 66, 77,
 /// ]);
 /// ```
+///
+/// ```
+/// let bytes = bytes_lit::bytes!(0xff; 4);
+/// assert_eq!(bytes, [255, 255, 255, 255]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::bytes!(0x00..=0x03);
+/// assert_eq!(bytes, [0, 1, 2, 3]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::bytes!("DE:AD:BE:EF");
+/// assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::bytes!(r"\xDE\xADOK");
+/// assert_eq!(bytes, [0xde, 0xad, b'O', b'K']);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::bytes!(
+///     "00010203"
+///     "04050607"
+/// );
+/// assert_eq!(bytes, [0, 1, 2, 3, 4, 5, 6, 7]);
+/// ```
+///
+/// ```
+/// let header = bytes_lit::bytes!(
+///     /// Magic.
+///     0xCAFE,
+///     0x01 /* version */,
+///     0x00; 2,
+/// );
+/// assert_eq!(header, [0xca, 0xfe, 0x01, 0x00, 0x00]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::bytes!(255, size = 4);
+/// assert_eq!(bytes, [0, 0, 0, 255]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::bytes!(0x10 + 0x0F, size = 4);
+/// assert_eq!(bytes, [0, 0, 0, 0x1f]);
+/// ```
+///
+/// ```
+/// let id = bytes_lit::bytes!(
+///     0xfded3f55dec47250a52a8c0bb7038e72fa6ffaae33562f77cd2b629ef7fd424d;
+///     size = 4,
+///     truncate,
+/// );
+/// assert_eq!(id, [0xfd, 0xed, 0x3f, 0x55]);
+/// ```
+///
+/// ```
+/// // The asset code USDC.
+/// let code = bytes_lit::bytes!(0x55534443, pad_right = 12);
+/// assert_eq!(code, *b"USDC\0\0\0\0\0\0\0\0");
+/// ```
+///
+/// ```
+/// let words = bytes_lit::bytes!(0xdeadbeef_00000001; swap = 4);
+/// assert_eq!(words, [0xef, 0xbe, 0xad, 0xde, 0x01, 0, 0, 0]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::bytes!(0x0001020304050607, range = 2..4);
+/// assert_eq!(bytes, [2, 3]);
+/// ```
+///
+/// ```
+/// // An XDR string of length 3, padded to a multiple of 4 bytes.
+/// let record = bytes_lit::bytes!(0x00000003, 0x616263; align = 4);
+/// assert_eq!(record, [0, 0, 0, 3, b'a', b'b', b'c', 0]);
+/// ```
+///
+/// ```ignore
+/// use stellar_xdr::curr::Hash;
+/// const WASM_HASH: Hash = bytes_lit::bytes!(0x01; as Hash, size = 32);
+/// ```
+///
+/// ```
+/// let rows = bytes_lit::bytes!(0x000102030405; chunk = 2);
+/// assert_eq!(rows, [[0, 1], [2, 3], [4, 5]]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::bytes!(concat!("dead", "beef"));
+/// assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::bytes!(
+///     b"abc";
+///     sha256 = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+/// );
+/// assert_eq!(bytes, *b"abc");
+/// ```
+///
+/// ```
+/// let word = bytes_lit::bytes!(0x0102 => [u8; 4], pad);
+/// assert_eq!(word, [0, 0, 1, 2]);
+/// ```
+///
+/// ```
+/// // Each register value must be written as whole bytes.
+/// let registers = bytes_lit::bytes!(0b0000_1111, 0b1010_0101; strict);
+/// assert_eq!(registers, [0x0f, 0xa5]);
+/// ```
 #[proc_macro]
 pub fn bytes(input: TokenStream) -> TokenStream {
     bytes::bytes(input.into()).into()
 }
 
+/// Bytes_pat converts literals into an array of bytes for use as a pattern.
+///
+/// Accepts the same input as [`bytes!`], except for the `chunk` and `byte_str`
+/// options. The output of [`bytes!`] is also a valid pattern, unless it has
+/// warnings or is more than 256 bytes and emitted as a byte string. The output
+/// of bytes_pat is always an array of `u8` literals, and any warnings are
+/// errors.
+///
+/// ### Examples
+///
+/// ```
+/// let header = [0xca, 0xfe, 0xba, 0xbe, 0x00];
+/// let kind = match header[..4] {
+///     bytes_lit::bytes_pat!(0xCAFEBABE) => "class",
+///     bytes_lit::bytes_pat!(0x7F454C46) => "elf",
+///     _ => "unknown",
+/// };
+/// assert_eq!(kind, "class");
+/// ```
+///
+/// ```
+/// let header = [0x7f, 0x45, 0x4c, 0x46];
+/// if let bytes_lit::bytes_pat!(0x7F454C46) = header {
+/// } else {
+///     panic!("not elf");
+/// }
+/// ```
+#[proc_macro]
+pub fn bytes_pat(input: TokenStream) -> TokenStream {
+    bytes::bytes_pat(input.into()).into()
+}
+
+/// Bytes_ref converts literals into a `&'static [u8]` slice of bytes.
+///
+/// Accepts the same input as [`bytes!`], for call sites that take a slice and
+/// don't need the fixed size array type.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes: &'static [u8] = bytes_lit::bytes_ref!(0xdeadbeef);
+/// assert_eq!(bytes, &[0xde, 0xad, 0xbe, 0xef]);
+/// ```
+#[proc_macro]
+pub fn bytes_ref(input: TokenStream) -> TokenStream {
+    bytes::bytes_ref(input.into()).into()
+}
+
+/// Bytesvec converts literals into a `Vec<u8>` of bytes.
+///
+/// Accepts the same input as [`bytes!`]. The `Vec` is referenced through
+/// `std`, or through `alloc` when the `alloc` feature of this crate is enabled
+/// for use in `no_std` crates that declare `extern crate alloc`.
+///
+/// ### Examples
+///
+/// ```
+/// let mut bytes = bytes_lit::bytesvec!(0xdead);
+/// bytes.push(0xbe);
+/// assert_eq!(bytes, vec![0xde, 0xad, 0xbe]);
+/// ```
+#[proc_macro]
+pub fn bytesvec(input: TokenStream) -> TokenStream {
+    bytes::bytesvec(input.into()).into()
+}
+
+/// Bytes_buf converts literals into a `bytes::Bytes` that references a static
+/// array, without copying.
+///
+/// Accepts the same input as [`bytes!`]. Requires the `bytes` feature of this
+/// crate, and a dependency on the `bytes` crate by the crate using it.
+///
+/// ### Examples
+///
+/// ```ignore
+/// let bytes: bytes::Bytes = bytes_lit::bytes_buf!(0xdeadbeef);
+/// assert_eq!(&bytes[..], &[0xde, 0xad, 0xbe, 0xef]);
+/// ```
+#[cfg(feature = "bytes")]
+#[proc_macro]
+pub fn bytes_buf(input: TokenStream) -> TokenStream {
+    bytes::bytes_buf(input.into()).into()
+}
+
+/// Bits converts literals into an array of bools, one per bit, most
+/// significant bit first.
+///
+/// Currently supports only integer literals of unbounded size.
+///
+/// Leading zeros are preserved for the same forms as [`bytes!`]. Each leading
+/// zero digit of a base 16 (hex) literal produces four `false` bits, of a base
+/// 8 (octal) literal three `false` bits, and of a base 2 (binary) literal one
+/// `false` bit. The bits of the value that follow start at its most
+/// significant set bit.
+///
+/// Options:
+/// - `len = N` zero pads the bits to exactly N bits, for fields that are not
+///   byte aligned. It is an error for the value to need more than N bits.
+/// - `pad = left` or `pad = right` sets the side the bits are zero padded on,
+///   which requires `len`. The default is left, as for leading zeros.
+/// - `packed` emits the bits packed into an array of bytes, most significant
+///   first, with the first byte partially filled when the number of bits is
+///   not a multiple of 8.
+///
+/// ### Examples
+///
+/// ```
+/// let bits = bytes_lit::bits!(0b1011);
+/// assert_eq!(bits, [true, false, true, true]);
+/// ```
+///
+/// ```
+/// let bits = bytes_lit::bits!(0b0011);
+/// assert_eq!(bits, [false, false, true, true]);
+/// ```
+///
+/// ```
+/// let bits = bytes_lit::bits!(0x0A);
+/// assert_eq!(bits, [false, false, false, false, true, false, true, false]);
+/// ```
+///
+/// ```
+/// let field = bytes_lit::bits!(0b101, len = 12, pad = right);
+/// assert_eq!(field.len(), 12);
+/// assert_eq!(field[..4], [true, false, true, false]);
+/// ```
+///
+/// ```
+/// let field = bytes_lit::bits!(0b101, len = 12, packed);
+/// assert_eq!(field, [0x00, 0x05]);
+/// ```
+#[proc_macro]
+pub fn bits(input: TokenStream) -> TokenStream {
+    bits::bits(input.into()).into()
+}
+
 /// Bytesmin converts literals into an array of bytes of minimal size to capture
 /// the value.
 ///
@@ -107,3 +554,1933 @@ pub fn bytes(input: TokenStream) -> TokenStream {
 pub fn bytesmin(input: TokenStream) -> TokenStream {
     bytesmin::bytesmin(input.into()).into()
 }
+
+/// Ibytes converts signed integer literals into an array of bytes in two's
+/// complement form.
+///
+/// The `size` option is required and sets the number of bytes in the array.
+/// It is an error for the value to not fit in that many bytes as a two's
+/// complement integer. A type suffix on the literal, such as `i16`, must
+/// match the size.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::ibytes!(-1, size = 4);
+/// assert_eq!(bytes, [255, 255, 255, 255]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::ibytes!(-129, size = 2);
+/// assert_eq!(bytes, [255, 127]);
+/// ```
+#[proc_macro]
+pub fn ibytes(input: TokenStream) -> TokenStream {
+    ibytes::ibytes(input.into()).into()
+}
+
+/// Zeros creates an array of zero bytes of the given length, as `[0u8; N]`.
+///
+/// The length must be an integer literal.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::zeros!(4);
+/// assert_eq!(bytes, [0, 0, 0, 0]);
+/// ```
+#[proc_macro]
+pub fn zeros(input: TokenStream) -> TokenStream {
+    zeros::zeros(input.into()).into()
+}
+
+/// Nibbles converts literals into an array of nibbles, one `u8` per four
+/// bits, most significant nibble first.
+///
+/// Currently supports only integer literals of unbounded size.
+///
+/// Leading zeros are preserved for the same forms as [`bytes!`], so each digit
+/// of a base 16 (hex) literal produces exactly one nibble.
+///
+/// ### Examples
+///
+/// ```
+/// let nibbles = bytes_lit::nibbles!(0xA1F);
+/// assert_eq!(nibbles, [0xA, 0x1, 0xF]);
+/// ```
+///
+/// ```
+/// let nibbles = bytes_lit::nibbles!(0x00A1F);
+/// assert_eq!(nibbles, [0x0, 0x0, 0xA, 0x1, 0xF]);
+/// ```
+#[proc_macro]
+pub fn nibbles(input: TokenStream) -> TokenStream {
+    nibbles::nibbles(input.into()).into()
+}
+
+/// Words16 converts literals into an array of `u16` words.
+///
+/// Supports the same literal forms as [`bytes!`], with the same leading zero
+/// semantics. The bytes are split into 2 byte words.
+///
+/// Options:
+/// - `endian = big` or `endian = little` sets the byte order of each word,
+///   defaulting to big.
+/// - `pad` zero pads the front of the bytes to a multiple of the word size.
+///   Without it, a literal that is not a multiple of the word size is an error.
+///
+/// ### Examples
+///
+/// ```
+/// let words = bytes_lit::words16!(0x0001abcd, endian = little);
+/// assert_eq!(words, [0x0100, 0xcdab]);
+/// ```
+#[proc_macro]
+pub fn words16(input: TokenStream) -> TokenStream {
+    words::words(input.into(), 2).into()
+}
+
+/// Words32 converts literals into an array of `u32` words.
+///
+/// Supports the same literal forms as [`bytes!`], with the same leading zero
+/// semantics. The bytes are split into 4 byte words.
+///
+/// Options:
+/// - `endian = big` or `endian = little` sets the byte order of each word,
+///   defaulting to big.
+/// - `pad` zero pads the front of the bytes to a multiple of the word size.
+///   Without it, a literal that is not a multiple of the word size is an error.
+///
+/// ### Examples
+///
+/// ```
+/// let words = bytes_lit::words32!(0x0001abcd);
+/// assert_eq!(words, [0x0001abcd]);
+/// ```
+#[proc_macro]
+pub fn words32(input: TokenStream) -> TokenStream {
+    words::words(input.into(), 4).into()
+}
+
+/// Words64 converts literals into an array of `u64` words.
+///
+/// Supports the same literal forms as [`bytes!`], with the same leading zero
+/// semantics. The bytes are split into 8 byte words.
+///
+/// Options:
+/// - `endian = big` or `endian = little` sets the byte order of each word,
+///   defaulting to big.
+/// - `pad` zero pads the front of the bytes to a multiple of the word size.
+///   Without it, a literal that is not a multiple of the word size is an error.
+///
+/// ### Examples
+///
+/// ```
+/// let words = bytes_lit::words64!(0x123456, pad);
+/// assert_eq!(words, [0x123456]);
+/// ```
+#[proc_macro]
+pub fn words64(input: TokenStream) -> TokenStream {
+    words::words(input.into(), 8).into()
+}
+
+/// Base64 decodes a base64 string literal into an array of bytes.
+///
+/// Padding is optional, but if present must be complete.
+///
+/// Options:
+/// - `alphabet = standard` or `alphabet = url` selects the standard or URL and
+///   filename safe alphabet of RFC 4648, defaulting to standard.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::base64!("3q2+7w==");
+/// assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::base64!("3q2-7w", alphabet = url);
+/// assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef]);
+/// ```
+#[proc_macro]
+pub fn base64(input: TokenStream) -> TokenStream {
+    base64::base64(input.into()).into()
+}
+
+/// Base58 decodes a base58 string literal, using the Bitcoin alphabet, into an
+/// array of bytes.
+///
+/// Each leading `1` character decodes to a leading zero byte.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::base58!("StV1DL6CwTryKyV");
+/// assert_eq!(&bytes, b"hello world");
+/// ```
+#[proc_macro]
+pub fn base58(input: TokenStream) -> TokenStream {
+    base58::base58(input.into()).into()
+}
+
+/// Base58check decodes a base58check string literal into an array of bytes.
+///
+/// The last four decoded bytes are a checksum, the first four bytes of the
+/// double SHA-256 of the preceding bytes. The checksum is verified and it is a
+/// compile error if it does not match. The array contains the bytes preceding
+/// the checksum, including any version byte.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::base58check!("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa");
+/// assert_eq!(bytes, [
+///     0x00, 0x62, 0xe9, 0x07, 0xb1, 0x5c, 0xbf, 0x27, 0xd5, 0x42, 0x53, 0x99,
+///     0xeb, 0xf6, 0xf0, 0xfb, 0x50, 0xeb, 0xb8, 0x8f, 0x18,
+/// ]);
+/// ```
+#[proc_macro]
+pub fn base58check(input: TokenStream) -> TokenStream {
+    base58::base58check(input.into()).into()
+}
+
+/// Base32 decodes a base32 string literal into an array of bytes.
+///
+/// Options:
+/// - `alphabet = standard` or `alphabet = hex` selects the standard or
+///   extended hex alphabet of RFC 4648, defaulting to standard.
+///   `alphabet = crockford` selects the alphabet of Douglas Crockford, which
+///   is case insensitive, reads I and L as 1 and O as 0, ignores hyphens, and
+///   has no padding.
+/// - `pad = optional`, `pad = required`, or `pad = none` sets whether padding
+///   may, must, or must not be present, defaulting to optional. Padding, if
+///   present, must be complete.
+/// - `check` verifies the last symbol of a crockford string as its check
+///   symbol, the value of the other symbols modulo 37, and drops it.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::base32!("MZXW6===");
+/// assert_eq!(&bytes, b"foo");
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::base32!("CPNMU", alphabet = hex, pad = none);
+/// assert_eq!(&bytes, b"foo");
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::base32!("csq-py-q", alphabet = crockford, check);
+/// assert_eq!(&bytes, b"foo");
+/// ```
+#[proc_macro]
+pub fn base32(input: TokenStream) -> TokenStream {
+    base32::base32(input.into()).into()
+}
+
+/// Bech32 decodes a bech32 or bech32m string literal into an array of bytes.
+///
+/// The checksum is verified, and the 5-bit groups of the data part are
+/// converted into bytes.
+///
+/// Options:
+/// - `hrp = "..."` requires the human-readable part to match.
+/// - `segwit` treats the data part as a segwit address, verifying that the
+///   witness version uses the correct encoding, and emitting the witness
+///   program without the version.
+///
+/// ### Examples
+///
+/// ```
+/// let program = bytes_lit::bech32!(
+///     "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+///     hrp = "bc",
+///     segwit,
+/// );
+/// assert_eq!(program, [
+///     0x75, 0x1e, 0x76, 0xe8, 0x19, 0x91, 0x96, 0xd4, 0x54, 0x94,
+///     0x1c, 0x45, 0xd1, 0xb3, 0xa3, 0x23, 0xf1, 0x43, 0x3b, 0xd6,
+/// ]);
+/// ```
+#[proc_macro]
+pub fn bech32(input: TokenStream) -> TokenStream {
+    bech32::bech32(input.into()).into()
+}
+
+/// Multibase decodes a multibase string literal into an array of bytes.
+///
+/// The first character of the string is the multibase prefix that selects the
+/// base of the remainder of the string. Supported prefixes are:
+/// - `f`, `F`: base16 lower and upper case.
+/// - `b`, `B`: base32 lower and upper case, without padding.
+/// - `c`, `C`: base32 lower and upper case, with padding.
+/// - `v`, `V`: base32hex lower and upper case, without padding.
+/// - `t`, `T`: base32hex lower and upper case, with padding.
+/// - `z`: base58btc.
+/// - `m`, `M`: base64 without and with padding.
+/// - `u`, `U`: base64url without and with padding.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::multibase!("z7paNL19xttacUY");
+/// assert_eq!(&bytes, b"yes mani !");
+/// ```
+#[proc_macro]
+pub fn multibase(input: TokenStream) -> TokenStream {
+    multibase::multibase(input.into()).into()
+}
+
+/// Strkey decodes a Stellar strkey string literal into an array of bytes.
+///
+/// The version byte and CRC16 checksum of the strkey are verified, and it is a
+/// compile error if either are invalid. The following strkeys are supported:
+/// - `G...`: ed25519 public key, decoded to the 32 byte key.
+/// - `S...`: ed25519 secret seed, decoded to the 32 byte seed. Secret seeds
+///   are an error unless the `allow_secret` option is given, so that secrets
+///   are not accidentally committed and compiled into binaries.
+/// - `M...`: muxed account, decoded to the 32 byte key followed by the 8 byte
+///   big endian ID.
+/// - `T...`: pre-authorized transaction, decoded to the 32 byte hash.
+/// - `X...`: sha256 hash signer, decoded to the 32 byte hash.
+/// - `C...`: contract, decoded to the 32 byte contract ID.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::strkey!("GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ");
+/// assert_eq!(bytes, [
+///     0x3f, 0x0c, 0x34, 0xbf, 0x93, 0xad, 0x0d, 0x99, 0x71, 0xd0, 0x4c, 0xcc, 0x90, 0xf7, 0x05, 0x51,
+///     0x1c, 0x83, 0x8a, 0xad, 0x97, 0x34, 0xa4, 0xa2, 0xfb, 0x0d, 0x7a, 0x03, 0xfc, 0x7f, 0xe8, 0x9a,
+/// ]);
+/// ```
+#[proc_macro]
+pub fn strkey(input: TokenStream) -> TokenStream {
+    strkey::strkey(input.into()).into()
+}
+
+/// Asset code converts a Stellar asset code string literal into its zero
+/// padded array of bytes.
+///
+/// Asset codes are 1 to 12 characters of a-z, A-Z, and 0-9. Codes of up to 4
+/// characters are padded to 4 bytes, and longer codes to 12 bytes.
+///
+/// Options:
+/// - `size = 4` or `size = 12` forces the padded size.
+///
+/// ### Examples
+///
+/// ```
+/// let code = bytes_lit::asset_code!("USD");
+/// assert_eq!(&code, b"USD\0");
+/// ```
+///
+/// ```
+/// let code = bytes_lit::asset_code!("USD", size = 12);
+/// assert_eq!(&code, b"USD\0\0\0\0\0\0\0\0\0");
+/// ```
+#[proc_macro]
+pub fn asset_code(input: TokenStream) -> TokenStream {
+    asset_code::asset_code(input.into()).into()
+}
+
+/// Network ID computes the Stellar network ID of a network passphrase string
+/// literal, being the SHA-256 of the passphrase, as an array of 32 bytes.
+///
+/// ### Examples
+///
+/// ```
+/// let id = bytes_lit::network_id!("Test SDF Network ; September 2015");
+/// assert_eq!(id, [
+///     0xce, 0xe0, 0x30, 0x2d, 0x59, 0x84, 0x4d, 0x32, 0xbd, 0xca, 0x91, 0x5c, 0x82, 0x03, 0xdd, 0x44,
+///     0xb3, 0x3f, 0xbb, 0x7e, 0xdc, 0x19, 0x05, 0x1e, 0xa3, 0x7a, 0xbe, 0xdf, 0x28, 0xec, 0xd4, 0x72,
+/// ]);
+/// ```
+#[proc_macro]
+pub fn network_id(input: TokenStream) -> TokenStream {
+    network_id::network_id(input.into()).into()
+}
+
+/// UUID converts a UUID string literal into its 16 bytes in big endian order.
+///
+/// Accepts the hyphenated form, the simple form without hyphens, and either
+/// form surrounded by braces. Hex digits may be upper or lower case.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::uuid!("550e8400-e29b-41d4-a716-446655440000");
+/// assert_eq!(bytes, [
+///     0x55, 0x0e, 0x84, 0x00, 0xe2, 0x9b, 0x41, 0xd4,
+///     0xa7, 0x16, 0x44, 0x66, 0x55, 0x44, 0x00, 0x00,
+/// ]);
+/// ```
+#[proc_macro]
+pub fn uuid(input: TokenStream) -> TokenStream {
+    uuid::uuid(input.into()).into()
+}
+
+/// GUID converts a UUID string literal into its 16 bytes in the Microsoft GUID
+/// binary layout.
+///
+/// The first three fields of the GUID are little endian, and the remaining
+/// bytes are in the same order as [`uuid!`]. Accepts the same forms as
+/// [`uuid!`].
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::guid!("{00112233-4455-6677-8899-aabbccddeeff}");
+/// assert_eq!(bytes, [
+///     0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66,
+///     0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+/// ]);
+/// ```
+#[proc_macro]
+pub fn guid(input: TokenStream) -> TokenStream {
+    uuid::guid(input.into()).into()
+}
+
+/// IP converts an IPv4 or IPv6 address string literal into an array of bytes
+/// in network byte order.
+///
+/// IPv4 addresses are in dotted decimal form, and produce 4 bytes. IPv6
+/// addresses support `::` compression of zero groups and an embedded IPv4
+/// address in the last 32 bits, and produce 16 bytes.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::ip!("192.168.1.1");
+/// assert_eq!(bytes, [192, 168, 1, 1]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::ip!("2001:db8::1");
+/// assert_eq!(bytes, [0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+/// ```
+#[proc_macro]
+pub fn ip(input: TokenStream) -> TokenStream {
+    ip::ip(input.into()).into()
+}
+
+/// MAC converts a MAC address string literal into an array of 6 bytes.
+///
+/// Accepts six two digit hex groups separated by `:` or `-`, and the Cisco
+/// form of three four digit hex groups separated by `.`.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::mac!("de:ad:be:ef:00:01");
+/// assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef, 0x00, 0x01]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::mac!("dead.beef.0001");
+/// assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef, 0x00, 0x01]);
+/// ```
+#[proc_macro]
+pub fn mac(input: TokenStream) -> TokenStream {
+    mac::mac(input.into()).into()
+}
+
+/// PEM converts a PEM string literal into an array of the DER bytes it
+/// contains.
+///
+/// The string must contain a single PEM block. The BEGIN and END labels must
+/// match, and the base64 body is decoded. Whitespace around the block and
+/// indentation of its lines is ignored.
+///
+/// Options:
+/// - `label = "..."` requires the label of the block to match.
+///
+/// ### Examples
+///
+/// ```
+/// let der = bytes_lit::pem!(
+///     "
+///     -----BEGIN PUBLIC KEY-----
+///     MCowBQYDK2VwAyEAGb9ECWmEzf6FQbrBZ9w7lshQhqowtrbLDFw4rXAxZuE=
+///     -----END PUBLIC KEY-----
+///     ",
+///     label = "PUBLIC KEY",
+/// );
+/// assert_eq!(der.len(), 44);
+/// ```
+#[proc_macro]
+pub fn pem(input: TokenStream) -> TokenStream {
+    pem::pem(input.into()).into()
+}
+
+/// Sha256 computes the SHA-256 digest of a literal as an array of 32 bytes.
+///
+/// Integer literals are converted to bytes as by [`bytes!`], preserving
+/// leading zeros. String literals are hashed as their UTF-8 bytes, and byte
+/// string literals as their bytes.
+///
+/// ### Examples
+///
+/// ```
+/// let digest = bytes_lit::sha256!("abc");
+/// assert_eq!(digest, [
+///     0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae, 0x22, 0x23,
+///     0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00, 0x15, 0xad,
+/// ]);
+/// ```
+///
+/// ```
+/// let digest = bytes_lit::sha256!(0x616263);
+/// assert_eq!(digest, bytes_lit::sha256!(b"abc"));
+/// ```
+#[proc_macro]
+pub fn sha256(input: TokenStream) -> TokenStream {
+    hash::sha256(input.into()).into()
+}
+
+/// Keccak256 computes the Keccak-256 digest of a literal, as used by Ethereum,
+/// as an array of 32 bytes.
+///
+/// Accepts the same literal forms as [`sha256!`].
+///
+/// ### Examples
+///
+/// ```
+/// let topic = bytes_lit::keccak256!("Transfer(address,address,uint256)");
+/// assert_eq!(topic, [
+///     0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa,
+///     0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3, 0xef,
+/// ]);
+/// ```
+#[proc_macro]
+pub fn keccak256(input: TokenStream) -> TokenStream {
+    hash::keccak256(input.into()).into()
+}
+
+/// Sha3_256 computes the SHA3-256 digest of a literal as an array of 32 bytes.
+///
+/// Accepts the same literal forms as [`sha256!`].
+///
+/// ### Examples
+///
+/// ```
+/// let digest = bytes_lit::sha3_256!("");
+/// assert_eq!(digest, [
+///     0xa7, 0xff, 0xc6, 0xf8, 0xbf, 0x1e, 0xd7, 0x66, 0x51, 0xc1, 0x47, 0x56, 0xa0, 0x61, 0xd6, 0x62,
+///     0xf5, 0x80, 0xff, 0x4d, 0xe4, 0x3b, 0x49, 0xfa, 0x82, 0xd8, 0x0a, 0x4b, 0x80, 0xf8, 0x43, 0x4a,
+/// ]);
+/// ```
+#[proc_macro]
+pub fn sha3_256(input: TokenStream) -> TokenStream {
+    hash::sha3_256(input.into()).into()
+}
+
+/// Blake2b computes the BLAKE2b digest of a literal, with the given output
+/// length in bytes, as an array of bytes.
+///
+/// The first argument is the output length, from 1 to 64 bytes. The second
+/// argument is the literal to hash, accepting the same literal forms as
+/// [`sha256!`].
+///
+/// ### Examples
+///
+/// ```
+/// let digest = bytes_lit::blake2b!(32, "storage:key");
+/// assert_eq!(digest.len(), 32);
+/// ```
+#[proc_macro]
+pub fn blake2b(input: TokenStream) -> TokenStream {
+    hash::blake2b(input.into()).into()
+}
+
+/// Blake2s computes the BLAKE2s digest of a literal, with the given output
+/// length in bytes, as an array of bytes.
+///
+/// The first argument is the output length, from 1 to 32 bytes. The second
+/// argument is the literal to hash, accepting the same literal forms as
+/// [`sha256!`].
+///
+/// ### Examples
+///
+/// ```
+/// let digest = bytes_lit::blake2s!(16, "storage:key");
+/// assert_eq!(digest.len(), 16);
+/// ```
+#[proc_macro]
+pub fn blake2s(input: TokenStream) -> TokenStream {
+    hash::blake2s(input.into()).into()
+}
+
+/// Hmac_sha256 computes the HMAC-SHA-256 of a message literal with a key
+/// literal, as an array of 32 bytes.
+///
+/// The key and message each accept the same literal forms as [`sha256!`].
+///
+/// ### Examples
+///
+/// ```
+/// let mac = bytes_lit::hmac_sha256!("key", "The quick brown fox jumps over the lazy dog");
+/// assert_eq!(mac[..4], [0xf7, 0xbc, 0x83, 0xf4]);
+/// ```
+#[proc_macro]
+pub fn hmac_sha256(input: TokenStream) -> TokenStream {
+    hash::hmac_sha256(input.into()).into()
+}
+
+/// Crc32 computes the CRC-32 of a literal as an array of 4 bytes.
+///
+/// The algorithm is CRC-32/ISO-HDLC, the CRC-32 used by Ethernet, zip, and
+/// PNG. Accepts the same literal forms as [`sha256!`].
+///
+/// Options:
+/// - `endian = big` or `endian = little` sets the byte order of the CRC,
+///   defaulting to big.
+///
+/// ### Examples
+///
+/// ```
+/// let crc = bytes_lit::crc32!("123456789");
+/// assert_eq!(crc, [0xcb, 0xf4, 0x39, 0x26]);
+/// ```
+#[proc_macro]
+pub fn crc32(input: TokenStream) -> TokenStream {
+    crc::crc32(input.into()).into()
+}
+
+/// Crc16 computes a CRC-16 of a literal as an array of 2 bytes.
+///
+/// Accepts the same literal forms as [`sha256!`].
+///
+/// Options:
+/// - `preset = ccitt` or `preset = xmodem` selects the algorithm,
+///   CRC-16/CCITT-FALSE or CRC-16/XMODEM, defaulting to ccitt.
+/// - `endian = big` or `endian = little` sets the byte order of the CRC,
+///   defaulting to big.
+///
+/// ### Examples
+///
+/// ```
+/// let crc = bytes_lit::crc16!("123456789");
+/// assert_eq!(crc, [0x29, 0xb1]);
+/// ```
+///
+/// ```
+/// let crc = bytes_lit::crc16!("123456789", preset = xmodem, endian = little);
+/// assert_eq!(crc, [0xc3, 0x31]);
+/// ```
+#[proc_macro]
+pub fn crc16(input: TokenStream) -> TokenStream {
+    crc::crc16(input.into()).into()
+}
+
+/// Varint encodes an integer literal as an unsigned LEB128 varint, as used by
+/// protobuf, WASM, and DWARF.
+///
+/// Each byte holds seven bits of the value, least significant group first,
+/// with the high bit set on every byte but the last. Integers of any size are
+/// supported.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::varint!(300);
+/// assert_eq!(bytes, [0xac, 0x02]);
+/// ```
+#[proc_macro]
+pub fn varint(input: TokenStream) -> TokenStream {
+    varint::varint(input.into()).into()
+}
+
+/// Sleb128 encodes an integer literal, which may be negative, as a signed
+/// LEB128 varint, as used by WASM and DWARF.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::sleb128!(-123456);
+/// assert_eq!(bytes, [0xc0, 0xbb, 0x78]);
+/// ```
+#[proc_macro]
+pub fn sleb128(input: TokenStream) -> TokenStream {
+    varint::sleb128(input.into()).into()
+}
+
+/// Zigzag encodes an integer literal, which may be negative, as a zigzag
+/// varint, as used by protobuf `sint32` and `sint64` fields.
+///
+/// The value is mapped so that 0, -1, 1, -2, 2 become 0, 1, 2, 3, 4, and the
+/// result is encoded as by [`varint!`].
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::zigzag!(-3);
+/// assert_eq!(bytes, [0x05]);
+/// ```
+#[proc_macro]
+pub fn zigzag(input: TokenStream) -> TokenStream {
+    varint::zigzag(input.into()).into()
+}
+
+/// Scale_compact encodes an integer literal in the SCALE compact form used by
+/// Substrate.
+///
+/// Values up to 2^6 - 1 are encoded in one byte, up to 2^14 - 1 in two bytes,
+/// up to 2^30 - 1 in four bytes, and larger values up to 2^536 - 1 as a length
+/// prefix byte followed by the little endian bytes of the value.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::scale_compact!(69);
+/// assert_eq!(bytes, [0x15, 0x01]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::scale_compact!(1073741824);
+/// assert_eq!(bytes, [0x03, 0x00, 0x00, 0x00, 0x40]);
+/// ```
+#[proc_macro]
+pub fn scale_compact(input: TokenStream) -> TokenStream {
+    scale::scale_compact(input.into()).into()
+}
+
+/// Bcd encodes the digits of a decimal integer literal as binary-coded
+/// decimal.
+///
+/// The digits are taken as written, so leading zeros are preserved. By
+/// default digits are packed two per byte, and an odd number of digits is
+/// zero padded on the left.
+///
+/// Options:
+/// - `pad = left` or `pad = right` sets the side the zero digit is added to
+///   when there is an odd number of digits.
+/// - `unpacked` emits one digit per byte.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::bcd!(1234);
+/// assert_eq!(bytes, [0x12, 0x34]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::bcd!(123, pad = right);
+/// assert_eq!(bytes, [0x12, 0x30]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::bcd!(0123, unpacked);
+/// assert_eq!(bytes, [0, 1, 2, 3]);
+/// ```
+#[proc_macro]
+pub fn bcd(input: TokenStream) -> TokenStream {
+    bcd::bcd(input.into()).into()
+}
+
+/// Xdr_opaque encodes a literal as XDR variable length opaque data, as used
+/// by Stellar XDR.
+///
+/// The data is prefixed with its length as a 4 byte big endian integer, and
+/// zero padded to a multiple of 4 bytes. Accepts the same literal forms as
+/// [`sha256!`].
+///
+/// Options:
+/// - `fixed` encodes fixed length opaque data, which is padded but has no
+///   length prefix.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::xdr_opaque!("abc");
+/// assert_eq!(bytes, [0, 0, 0, 3, b'a', b'b', b'c', 0]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::xdr_opaque!(0x0102, fixed);
+/// assert_eq!(bytes, [1, 2, 0, 0]);
+/// ```
+#[proc_macro]
+pub fn xdr_opaque(input: TokenStream) -> TokenStream {
+    xdr::xdr_opaque(input.into()).into()
+}
+
+/// Bytes_file reads a text file of hex at compile time, and decodes it into an
+/// array of bytes.
+///
+/// The path is relative to the directory containing the crate's Cargo.toml.
+/// Whitespace, and comments starting with `#` and running to the end of a
+/// line, are ignored. The file is tracked so that changes to it cause the
+/// macro to be expanded again. The macro expands to a block expression that
+/// evaluates to the array.
+///
+/// Options:
+/// - `base64` decodes the file as standard base64 instead of hex.
+///
+/// ### Examples
+///
+/// ```ignore
+/// let key = bytes_lit::bytes_file!("keys/signer.hex");
+/// ```
+///
+/// ```ignore
+/// let key = bytes_lit::bytes_file!("keys/signer.b64", base64);
+/// ```
+#[proc_macro]
+pub fn bytes_file(input: TokenStream) -> TokenStream {
+    file::bytes_file(input.into()).into()
+}
+
+/// Ihex reads an Intel HEX firmware image at compile time, and emits the data
+/// as one contiguous array of bytes.
+///
+/// The path is relative to the directory containing the crate's Cargo.toml,
+/// and the file is tracked as by [`bytes_file!`]. Record checksums are
+/// verified, and the file must end with an end of file record. The array
+/// starts at the lowest address of the data.
+///
+/// Options:
+/// - `fill = 0xff` fills gaps between data with the byte. Without it gaps are
+///   an error. A filled image is at most 16 MiB.
+///
+/// ### Examples
+///
+/// ```ignore
+/// let image = bytes_lit::ihex!("firmware/boot.hex", fill = 0xff);
+/// ```
+#[proc_macro]
+pub fn ihex(input: TokenStream) -> TokenStream {
+    firmware::ihex(input.into()).into()
+}
+
+/// Srec reads a Motorola S-record firmware image at compile time, and emits
+/// the data as one contiguous array of bytes.
+///
+/// Behaves as [`ihex!`], and has the same options.
+///
+/// ### Examples
+///
+/// ```ignore
+/// let image = bytes_lit::srec!("firmware/boot.s19");
+/// ```
+#[proc_macro]
+pub fn srec(input: TokenStream) -> TokenStream {
+    firmware::srec(input.into()).into()
+}
+
+/// Hexdump converts the output of `xxd` or `hexdump -C`, pasted into a string
+/// literal, into an array of bytes.
+///
+/// The offset at the start of each line and the ASCII gutter are discarded.
+/// Offsets must follow on from the previous line, except after a `*` line,
+/// which hexdump uses in place of repeated lines and which is expanded.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::hexdump!("
+///     00000000: 4865 6c6c 6f2c 2077 6f72 6c64 210a       Hello, world!.
+/// ");
+/// assert_eq!(&bytes, b"Hello, world!\n");
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::hexdump!("
+///     00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a        |Hello, world!.|
+///     0000000e
+/// ");
+/// assert_eq!(&bytes, b"Hello, world!\n");
+/// ```
+#[proc_macro]
+pub fn hexdump(input: TokenStream) -> TokenStream {
+    hexdump::hexdump(input.into()).into()
+}
+
+/// Cbytes_src converts a C array initializer, in a string literal, into an
+/// array of bytes.
+///
+/// The initializer is a brace delimited, comma separated list of C integer
+/// literals, in hex, octal, binary, or decimal, with optional integer
+/// suffixes. Comments and a trailing comma are allowed. Values greater than
+/// 255 are an error.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::cbytes_src!("{0xDE, 0xAD, 0xBE}");
+/// assert_eq!(bytes, [0xde, 0xad, 0xbe]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::cbytes_src!("
+///     {
+///         0x01u, 002, /* octal */
+///         3,
+///     }
+/// ");
+/// assert_eq!(bytes, [1, 2, 3]);
+/// ```
+#[proc_macro]
+pub fn cbytes_src(input: TokenStream) -> TokenStream {
+    cbytes::cbytes_src(input.into()).into()
+}
+
+/// Bytes_env reads an environment variable at compile time, and decodes its
+/// hex value into an array of bytes.
+///
+/// The hex may contain separators as accepted by [`bytes!`]. The variable is
+/// tracked so that changes to it cause the macro to be expanded again. It is an
+/// error if the variable is not set. The macro expands to a block expression
+/// that evaluates to the array.
+///
+/// Options:
+/// - `base64` decodes the value as standard base64 instead of hex.
+///
+/// ### Examples
+///
+/// ```ignore
+/// let key = bytes_lit::bytes_env!("SIGNER_PUBKEY_HEX");
+/// ```
+#[proc_macro]
+pub fn bytes_env(input: TokenStream) -> TokenStream {
+    env::bytes_env(input.into()).into()
+}
+
+/// Option_bytes_env reads an environment variable at compile time as
+/// [`bytes_env!`] does, decoding a default value instead if the variable is not
+/// set.
+///
+/// Options:
+/// - `default = "..."` is the value to decode if the variable is not set, and
+///   is required.
+/// - `base64` decodes the value, and the default, as standard base64 instead
+///   of hex.
+///
+/// ### Examples
+///
+/// ```
+/// let id = bytes_lit::option_bytes_env!("BYTES_LIT_UNSET_NETWORK_ID", default = "00000000");
+/// assert_eq!(id, [0, 0, 0, 0]);
+/// ```
+#[proc_macro]
+pub fn option_bytes_env(input: TokenStream) -> TokenStream {
+    env::option_bytes_env(input.into()).into()
+}
+
+/// Const_bytes declares a constant byte array, without the length of the array
+/// needing to be stated.
+///
+/// The name of the constant, optionally preceded by attributes and a
+/// visibility, is followed by `=` or `,` and then any input accepted by
+/// [`bytes!`].
+///
+/// ### Examples
+///
+/// ```
+/// bytes_lit::const_bytes!(
+///     /// The magic number.
+///     pub MAGIC = 0xCAFEBABE
+/// );
+/// assert_eq!(MAGIC, [0xca, 0xfe, 0xba, 0xbe]);
+/// ```
+///
+/// ```
+/// bytes_lit::const_bytes!(HEADER, 0xCAFE, 0x01, 0x00; 2);
+/// assert_eq!(HEADER.len(), 5);
+/// ```
+#[proc_macro]
+pub fn const_bytes(input: TokenStream) -> TokenStream {
+    item::const_bytes(input.into()).into()
+}
+
+/// Static_bytes declares a static byte array, without the length of the array
+/// needing to be stated.
+///
+/// Accepts the same input as [`const_bytes!`]. Attributes are passed through
+/// to the static, so it can be placed in a specific section with
+/// `#[link_section]`, and kept with `#[used]`.
+///
+/// ### Examples
+///
+/// ```
+/// bytes_lit::static_bytes!(
+///     #[link_section = ".rodata.keys"]
+///     #[used]
+///     pub KEY = 0xdeadbeef
+/// );
+/// assert_eq!(KEY, [0xde, 0xad, 0xbe, 0xef]);
+/// ```
+#[proc_macro]
+pub fn static_bytes(input: TokenStream) -> TokenStream {
+    item::static_bytes(input.into()).into()
+}
+
+/// Bytes_table declares many constant byte arrays at once.
+///
+/// Each entry is the input of [`const_bytes!`] with `=`, and entries are
+/// separated by semicolons. Options of an entry are separated from the
+/// literals by a comma. Valid entries are declared even when others have
+/// errors.
+///
+/// ### Examples
+///
+/// ```
+/// bytes_lit::bytes_table! {
+///     /// The magic number.
+///     pub MAGIC = 0xCAFEBABE;
+///     pub SALT = 0x00112233;
+///     pub TAG = b"v1";
+///     pub PADDING = 0x00; 3, size = 4;
+/// }
+/// assert_eq!(MAGIC, [0xca, 0xfe, 0xba, 0xbe]);
+/// assert_eq!(TAG, *b"v1");
+/// assert_eq!(PADDING, [0; 4]);
+/// ```
+#[proc_macro]
+pub fn bytes_table(input: TokenStream) -> TokenStream {
+    item::bytes_table(input.into()).into()
+}
+
+/// Bytes_item is an attribute for a const or static item, that replaces the
+/// value of the item with the array of bytes for it.
+///
+/// The value of the item is any input accepted by [`bytes!`] without options,
+/// and the options are given in the attribute. A type of `_` is replaced with
+/// the type of the array, and any other type is kept so that its length is
+/// checked by the compiler.
+///
+/// The attribute cannot be named `bytes` since an attribute shares its name
+/// with the [`bytes!`] macro.
+///
+/// ### Examples
+///
+/// ```
+/// /// The key.
+/// #[bytes_lit::bytes_item]
+/// pub const KEY: _ = "fded3f55dec47250a52a8c0bb7038e72fa6ffaae33562f77cd2b629ef7fd424d";
+/// assert_eq!(KEY.len(), 32);
+/// ```
+///
+/// ```
+/// #[bytes_lit::bytes_item(size = 4)]
+/// static VERSION: [u8; 4] = 0x0102;
+/// assert_eq!(VERSION, [0, 0, 1, 2]);
+/// ```
+#[proc_macro_attribute]
+pub fn bytes_item(attr: TokenStream, item: TokenStream) -> TokenStream {
+    item::bytes_item(attr.into(), item.into()).into()
+}
+
+/// Xor converts two literals into bytes and combines them with exclusive or,
+/// as an array of bytes.
+///
+/// Each literal accepts the integer and hex string forms of [`bytes!`]. The
+/// literals must be the same number of bytes, unless the `pad` option is
+/// given.
+///
+/// Options:
+/// - `pad = left` or `pad = right` zero pads the shorter literal on the left
+///   or right to the length of the longer.
+///
+/// ### Examples
+///
+/// ```
+/// let key = bytes_lit::xor!(0xdeadbeef, "0f0f0f0f");
+/// assert_eq!(key, [0xd1, 0xa2, 0xb1, 0xe0]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::xor!(0xff00, 0x01, pad = left);
+/// assert_eq!(bytes, [0xff, 0x01]);
+/// ```
+#[proc_macro]
+pub fn xor(input: TokenStream) -> TokenStream {
+    bitwise::xor(input.into()).into()
+}
+
+/// And converts two literals into bytes and combines them with bitwise and, as
+/// an array of bytes.
+///
+/// Accepts the same input and options as [`xor!`].
+///
+/// ### Examples
+///
+/// ```
+/// let masked = bytes_lit::and!(0xdeadbeef, 0xffff0000);
+/// assert_eq!(masked, [0xde, 0xad, 0x00, 0x00]);
+/// ```
+#[proc_macro]
+pub fn and(input: TokenStream) -> TokenStream {
+    bitwise::and(input.into()).into()
+}
+
+/// Or converts two literals into bytes and combines them with bitwise or, as
+/// an array of bytes.
+///
+/// Accepts the same input and options as [`xor!`].
+///
+/// ### Examples
+///
+/// ```
+/// let mask = bytes_lit::or!(0xff00, 0x00f0);
+/// assert_eq!(mask, [0xff, 0xf0]);
+/// ```
+#[proc_macro]
+pub fn or(input: TokenStream) -> TokenStream {
+    bitwise::or(input.into()).into()
+}
+
+/// Not converts a literal into bytes and inverts every bit, as an array of
+/// bytes.
+///
+/// Accepts the integer and hex string forms of [`bytes!`].
+///
+/// ### Examples
+///
+/// ```
+/// let inverted = bytes_lit::not!(0xff00);
+/// assert_eq!(inverted, [0x00, 0xff]);
+/// ```
+#[proc_macro]
+pub fn not(input: TokenStream) -> TokenStream {
+    bitwise::not(input.into()).into()
+}
+
+/// Typed_bytes converts literals into bytes, as an instance of a type that
+/// wraps an array of bytes.
+///
+/// The type, which must derive [`FromBytesLit`], is followed by `,` and then
+/// any input accepted by [`bytes!`]. The compiler checks that the number of
+/// bytes matches the length of the wrapped array.
+///
+/// ### Examples
+///
+/// ```
+/// #[derive(bytes_lit::FromBytesLit)]
+/// pub struct ContractId([u8; 4]);
+///
+/// const ID: ContractId = bytes_lit::typed_bytes!(ContractId, 0xdeadbeef);
+/// assert_eq!(ID.0, [0xde, 0xad, 0xbe, 0xef]);
+/// ```
+#[proc_macro]
+pub fn typed_bytes(input: TokenStream) -> TokenStream {
+    typed::typed_bytes(input.into()).into()
+}
+
+/// Derives a const constructor from an array of bytes, for a struct with a
+/// single field of type `[u8; N]`, so it can be created with [`typed_bytes!`].
+#[proc_macro_derive(FromBytesLit)]
+pub fn derive_from_bytes_lit(input: TokenStream) -> TokenStream {
+    typed::derive_from_bytes_lit(input.into()).into()
+}
+
+/// Rand_bytes generates an array of pseudo-random bytes from a seed, for test
+/// fixtures that are arbitrary but stable.
+///
+/// Options:
+/// - `seed = N` is the seed of the generator, a `u64`. Required.
+/// - `len = N` is the number of bytes to generate. Required.
+///
+/// The bytes are generated by xoshiro256**, with its state seeded by
+/// SplitMix64, and each output taken as 8 little endian bytes. The bytes for a
+/// seed do not change between versions of this crate. The bytes are not
+/// suitable for cryptographic use.
+///
+/// ### Examples
+///
+/// ```
+/// let fixture = bytes_lit::rand_bytes!(seed = 42, len = 1024);
+/// assert_eq!(fixture.len(), 1024);
+/// assert_eq!(fixture[..4], bytes_lit::rand_bytes!(seed = 42, len = 4));
+/// ```
+#[proc_macro]
+pub fn rand_bytes(input: TokenStream) -> TokenStream {
+    rand::rand_bytes(input.into()).into()
+}
+
+/// Bytesmin_le converts literals into the fewest bytes that hold the value, as
+/// an array of bytes in little endian order, least significant byte first.
+///
+/// Accepts the same input as [`bytesmin!`].
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::bytesmin_le!(0x000102);
+/// assert_eq!(bytes, [2, 1]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::bytesmin_le!(65536);
+/// assert_eq!(bytes, [0, 0, 1]);
+/// ```
+#[proc_macro]
+pub fn bytesmin_le(input: TokenStream) -> TokenStream {
+    bytesmin::bytesmin_le(input.into()).into()
+}
+
+/// Eth_address decodes an Ethereum address string literal into an array of 20
+/// bytes.
+///
+/// The address is 40 hex digits, optionally prefixed with `0x`. An address in
+/// mixed case must have a valid EIP-55 checksum, and it is a compile error if
+/// it does not, catching mistyped and transposed characters. An address that
+/// is all lower case or all upper case has no checksum to verify.
+///
+/// ### Examples
+///
+/// ```
+/// let address = bytes_lit::eth_address!("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+/// assert_eq!(address, [
+///     0x5a, 0xae, 0xb6, 0x05, 0x3f, 0x3e, 0x94, 0xc9, 0xb9, 0xa0,
+///     0x9f, 0x33, 0x66, 0x94, 0x35, 0xe7, 0xef, 0x1b, 0xea, 0xed,
+/// ]);
+/// ```
+#[proc_macro]
+pub fn eth_address(input: TokenStream) -> TokenStream {
+    eth::eth_address(input.into()).into()
+}
+
+/// Multihash converts a digest into a multihash, as an array of bytes.
+///
+/// The name of the hash function is followed by `,` and then any input
+/// accepted by [`bytes!`] for the digest. The multihash is the code of the
+/// function and the length of the digest as unsigned varints, followed by the
+/// digest. It is a compile error if the digest is not the length of the
+/// function's digest.
+///
+/// The supported functions are: `sha1`, `sha2_256`, `sha2_512`, `sha3_512`,
+/// `sha3_384`, `sha3_256`, `sha3_224`, `keccak_256`, `blake3`, `blake2b_256`,
+/// and `blake2s_256`.
+///
+/// ### Examples
+///
+/// ```
+/// let hash = bytes_lit::multihash!(
+///     sha2_256,
+///     0x2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824,
+/// );
+/// assert_eq!(hash[..2], [0x12, 0x20]);
+/// assert_eq!(hash.len(), 34);
+/// ```
+#[proc_macro]
+pub fn multihash(input: TokenStream) -> TokenStream {
+    multihash::multihash(input.into()).into()
+}
+
+/// Cid decodes an IPFS content identifier string literal into the binary CID,
+/// as an array of bytes.
+///
+/// A CIDv0, the base58btc encoding of a sha2_256 multihash starting `Qm`,
+/// decodes to the multihash. A CIDv1, a multibase string, decodes to the
+/// version, the content codec, and the multihash. The length of the
+/// multihash is verified.
+///
+/// Options:
+/// - `digest` emits just the digest of the multihash.
+///
+/// ### Examples
+///
+/// ```
+/// let cid = bytes_lit::cid!("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+/// assert_eq!(cid[..2], [0x12, 0x20]);
+///
+/// let cid = bytes_lit::cid!("bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34");
+/// assert_eq!(cid[..4], [0x01, 0x70, 0x12, 0x20]);
+/// ```
+///
+/// ```
+/// let digest = bytes_lit::cid!("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG", digest);
+/// assert_eq!(digest.len(), 32);
+/// ```
+#[proc_macro]
+pub fn cid(input: TokenStream) -> TokenStream {
+    cid::cid(input.into()).into()
+}
+
+/// Ulid converts a ULID string literal into its 16 bytes in big endian order.
+///
+/// A ULID is 26 characters of Crockford base32, in upper or lower case, which
+/// excludes the letters I, L, O and U. The first character must be 0 to 7 so
+/// that the value fits in 128 bits.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::ulid!("01ARZ3NDEKTSV4RRFFQ69G5FAV");
+/// assert_eq!(bytes, [
+///     0x01, 0x56, 0x3e, 0x3a, 0xb5, 0xd3, 0xd6, 0x76,
+///     0x4c, 0x61, 0xef, 0xb9, 0x93, 0x02, 0xbd, 0x5b,
+/// ]);
+/// ```
+#[proc_macro]
+pub fn ulid(input: TokenStream) -> TokenStream {
+    ulid::ulid(input.into()).into()
+}
+
+/// Ascii85 decodes an Ascii85 string literal into an array of bytes.
+///
+/// The string may be framed by `<~` and `~>`, as in PostScript and PDF
+/// streams. Whitespace is ignored, `z` is four zero bytes, and a final group of
+/// fewer than five characters decodes to one byte less than its characters.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::ascii85!("<~87cURD_*#4DfTZ)~>");
+/// assert_eq!(bytes, *b"Hello, World");
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::ascii85!("z9jqo");
+/// assert_eq!(bytes, [0, 0, 0, 0, b'M', b'a', b'n']);
+/// ```
+#[proc_macro]
+pub fn ascii85(input: TokenStream) -> TokenStream {
+    base85::ascii85(input.into()).into()
+}
+
+/// Base85 decodes an Ascii85 string literal into an array of bytes, and is the
+/// same as [`ascii85!`].
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::base85!("9jqo^");
+/// assert_eq!(bytes, *b"Man ");
+/// ```
+#[proc_macro]
+pub fn base85(input: TokenStream) -> TokenStream {
+    base85::ascii85(input.into()).into()
+}
+
+/// Z85 decodes a Z85 string literal, the base85 encoding of ZeroMQ, into an
+/// array of bytes.
+///
+/// Every five characters decode to four bytes, so the length of the string
+/// must be a multiple of five. CurveZMQ keys are 40 characters and 32 bytes.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::z85!("HelloWorld");
+/// assert_eq!(bytes, [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b]);
+/// ```
+///
+/// ```
+/// let key = bytes_lit::z85!("Yne@$w-vo<fVvi]a<NY6T1ed:M$fCG*[IaLV{hID");
+/// assert_eq!(key.len(), 32);
+/// ```
+#[proc_macro]
+pub fn z85(input: TokenStream) -> TokenStream {
+    base85::z85(input.into()).into()
+}
+
+/// Urldecode decodes a percent encoded string literal into an array of bytes.
+///
+/// Each `%XX` escape of two hex digits decodes to its byte, and any other
+/// character decodes to its UTF-8 bytes.
+///
+/// Options:
+/// - `plus` decodes `+` as a space, as in form encoded query strings.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::urldecode!("%DE%AD%BE%EF");
+/// assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::urldecode!("a+b%3D", plus);
+/// assert_eq!(bytes, *b"a b=");
+/// ```
+#[proc_macro]
+pub fn urldecode(input: TokenStream) -> TokenStream {
+    urldecode::urldecode(input.into()).into()
+}
+
+/// Der int encodes an integer literal, which may be negative, as a complete
+/// ASN.1 DER INTEGER: the tag `0x02`, the definite length, and the value.
+///
+/// The value is the minimal two's complement bytes of the integer, so a
+/// non-negative value whose high bit is set is preceded by a zero byte, and
+/// leading zeros of the literal are not preserved. Lengths of 128 bytes or
+/// more use the long form.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::der_int!(0x00FF);
+/// assert_eq!(bytes, [0x02, 0x02, 0x00, 0xff]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::der_int!(-129);
+/// assert_eq!(bytes, [0x02, 0x02, 0xff, 0x7f]);
+/// ```
+#[proc_macro]
+pub fn der_int(input: TokenStream) -> TokenStream {
+    der::der_int(input.into()).into()
+}
+
+/// Oid encodes a dotted object identifier string literal as the content octets
+/// of an ASN.1 DER OBJECT IDENTIFIER.
+///
+/// The first two arcs are packed into one value, 40 times the first plus the
+/// second, and each value is encoded in base 128 with the high bit set on
+/// every byte but the last. Arcs may be of any size.
+///
+/// Options:
+/// - `tag` emits the complete OBJECT IDENTIFIER, preceded by the tag `0x06`
+///   and the length.
+///
+/// ### Examples
+///
+/// ```
+/// let sha256_with_rsa = bytes_lit::oid!("1.2.840.113549.1.1.11");
+/// assert_eq!(sha256_with_rsa, [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b]);
+/// ```
+///
+/// ```
+/// let common_name = bytes_lit::oid!("2.5.4.3", tag);
+/// assert_eq!(common_name, [0x06, 0x03, 0x55, 0x04, 0x03]);
+/// ```
+#[proc_macro]
+pub fn oid(input: TokenStream) -> TokenStream {
+    der::oid(input.into()).into()
+}
+
+/// Scaled bytes converts a decimal literal into the bytes of a fixed-point
+/// integer, the value multiplied by 10^scale, as a big endian two's complement
+/// integer of the size.
+///
+/// The literal is parsed exactly, without floating point, and a value with
+/// more decimal places than the scale is an error rather than rounded.
+///
+/// Options:
+/// - `scale = N` is the number of decimal places, required.
+/// - `size = N` is the number of bytes, required.
+///
+/// ### Examples
+///
+/// ```
+/// // A Stellar amount of 1.2345678, in stroops.
+/// let amount = bytes_lit::scaled_bytes!(1.2345678, scale = 7, size = 8);
+/// assert_eq!(amount, 12345678i64.to_be_bytes());
+/// ```
+///
+/// ```
+/// let price = bytes_lit::scaled_bytes!(-0.01, scale = 2, size = 2);
+/// assert_eq!(price, (-1i16).to_be_bytes());
+/// ```
+#[proc_macro]
+pub fn scaled_bytes(input: TokenStream) -> TokenStream {
+    scaled::scaled_bytes(input.into()).into()
+}
+
+/// Timestamp converts an RFC 3339 timestamp string literal into the time since
+/// the Unix epoch, as a big endian two's complement integer.
+///
+/// The date and time are validated, including the days of each month and leap
+/// years, and an offset such as `+02:00` is applied. Fractional seconds finer
+/// than the unit must be zero. A leap second, such as `2016-12-31T23:59:60Z`,
+/// is the same time as the second after it, as Unix time has no leap seconds.
+///
+/// Options:
+/// - `unit = seconds`, `unit = millis`, `unit = micros`, or `unit = nanos`
+///   sets the unit, defaulting to seconds.
+/// - `size = N` sets the number of bytes, defaulting to 8.
+///
+/// ### Examples
+///
+/// ```
+/// let time = bytes_lit::timestamp!("2024-06-01T12:00:00Z");
+/// assert_eq!(time, 1717243200i64.to_be_bytes());
+/// ```
+///
+/// ```
+/// let time = bytes_lit::timestamp!("2024-06-01T14:00:00.250+02:00", unit = millis);
+/// assert_eq!(time, 1717243200250i64.to_be_bytes());
+///
+/// let time = bytes_lit::timestamp!("2024-06-01T12:00:00Z", size = 4);
+/// assert_eq!(time, 1717243200u32.to_be_bytes());
+/// ```
+#[proc_macro]
+pub fn timestamp(input: TokenStream) -> TokenStream {
+    timestamp::timestamp(input.into()).into()
+}
+
+/// Hash20 decodes a string literal of exactly 40 hex digits into a 20 byte
+/// hash, such as a git SHA-1 object ID.
+///
+/// A hash of any other length is an error, so that a truncated copy of a hash
+/// fails to compile.
+///
+/// ### Examples
+///
+/// ```
+/// let commit = bytes_lit::hash20!("da39a3ee5e6b4b0d3255bfef95601890afd80709");
+/// assert_eq!(commit.len(), 20);
+/// ```
+#[proc_macro]
+pub fn hash20(input: TokenStream) -> TokenStream {
+    digest::hash20(input.into()).into()
+}
+
+/// Hash32 decodes a string literal of exactly 64 hex digits into a 32 byte
+/// hash, such as a SHA-256 object ID or content hash.
+///
+/// A hash of any other length is an error, so that a truncated copy of a hash
+/// fails to compile.
+///
+/// ### Examples
+///
+/// ```
+/// let hash = bytes_lit::hash32!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+/// assert_eq!(hash, bytes_lit::sha256!(""));
+/// ```
+#[proc_macro]
+pub fn hash32(input: TokenStream) -> TokenStream {
+    digest::hash32(input.into()).into()
+}
+
+/// Bytes radix converts a string literal of digits in a radix from 2 to 62
+/// into the minimum number of big endian bytes that represent the value.
+///
+/// Up to radix 36 the digits are `0-9` and the letters in either case. Above
+/// radix 36 upper case letters are 10 to 35 and lower case letters are 36 to
+/// 61, as in GMP.
+///
+/// Options:
+/// - `size = N` zero pads the bytes on the left to N bytes. It is an error for
+///   the bytes to be longer than N.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::bytes_radix!(36, "zik0zj");
+/// assert_eq!(bytes, [0x7f, 0xff, 0xff, 0xff]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::bytes_radix!(62, "2LKcb1", size = 8);
+/// assert_eq!(bytes, 2147483647u64.to_be_bytes());
+/// ```
+#[proc_macro]
+pub fn bytes_radix(input: TokenStream) -> TokenStream {
+    radix::bytes_radix(input.into()).into()
+}
+
+/// Key bytes converts the input of [`bytes!`] into an array of bytes that must
+/// be exactly the given number of bits, for keys, seeds, and chain codes.
+///
+/// Leading zeros of hex and binary literals are counted, and a literal whose
+/// first byte is partially filled is an error, so a key that was copied short
+/// or long fails to compile. The options of [`bytes!`] are accepted.
+///
+/// ### Examples
+///
+/// ```
+/// let seed = bytes_lit::key_bytes!(
+///     256,
+///     0x00000000dec47250a52a8c0bb7038e72fa6ffaae33562f77cd2b629ef7fd424d
+/// );
+/// assert_eq!(seed.len(), 32);
+/// ```
+///
+/// ```
+/// let key = bytes_lit::key_bytes!(128, "000102030405060708090a0b0c0d0e0f");
+/// assert_eq!(key[15], 0x0f);
+/// ```
+#[proc_macro]
+pub fn key_bytes(input: TokenStream) -> TokenStream {
+    key::key_bytes(input.into()).into()
+}
+
+/// Hex_str converts literals into their lower case hex string, as a
+/// `&'static str`.
+///
+/// Accepts the same input as [`bytes!`], except for the `chunk` and `byte_str`
+/// options, so that a constant and its display form, such as in logs, are
+/// generated from the same literal.
+///
+/// Options, in addition to those of [`bytes!`]:
+/// - `prefix` starts the string with `0x`.
+/// - `group = N` separates each group of N bytes with a space.
+/// - `separator = "..."` separates the groups with the string given instead of
+///   a space, which requires `group`.
+///
+/// ### Examples
+///
+/// ```
+/// let id = bytes_lit::hex_str!(0xDEADBEEF);
+/// assert_eq!(id, "deadbeef");
+/// ```
+///
+/// ```
+/// let id = bytes_lit::hex_str!("DE:AD:BE:EF", prefix);
+/// assert_eq!(id, "0xdeadbeef");
+/// ```
+///
+/// ```
+/// let fingerprint = bytes_lit::hex_str!(0xdeadbeef, group = 1, separator = ":");
+/// assert_eq!(fingerprint, "de:ad:be:ef");
+/// ```
+#[proc_macro]
+pub fn hex_str(input: TokenStream) -> TokenStream {
+    bytes::hex_str(input.into()).into()
+}
+
+/// Bytes_len evaluates to the number of bytes that literals convert into, as a
+/// `usize` literal.
+///
+/// Accepts the same input as [`bytes!`], except for the `chunk` and `byte_str`
+/// options, and computes the length the same way, including preserved leading
+/// zeros and options such as `size`, so buffers can be sized without
+/// instantiating the array.
+///
+/// ### Examples
+///
+/// ```
+/// const LEN: usize = bytes_lit::bytes_len!(0x00010203);
+/// assert_eq!(LEN, 4);
+/// ```
+///
+/// ```
+/// const LEN: usize = bytes_lit::bytes_len!(0x01, size = 32);
+/// let buf = [0u8; LEN];
+/// assert_eq!(buf.len(), 32);
+/// ```
+#[proc_macro]
+pub fn bytes_len(input: TokenStream) -> TokenStream {
+    bytes::bytes_len(input.into()).into()
+}
+
+/// Signmag_bytes converts signed integer literals into an array of bytes in
+/// sign-magnitude form: a sign byte followed by the magnitude, most
+/// significant byte first.
+///
+/// Options:
+/// - `size = N` sets the number of bytes in the array, including the sign
+///   byte, zero padding the magnitude on the left. It is an error for the
+///   magnitude to not fit. Without it the magnitude is the fewest bytes that
+///   hold it.
+/// - `positive = B` and `negative = B` set the sign bytes, which default to
+///   `0x00` and `0x01`. Zero is positive.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::signmag_bytes!(-255, size = 4);
+/// assert_eq!(bytes, [0x01, 0x00, 0x00, 0xff]);
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::signmag_bytes!(-1, size = 2, negative = 0x80);
+/// assert_eq!(bytes, [0x80, 0x01]);
+/// ```
+#[proc_macro]
+pub fn signmag_bytes(input: TokenStream) -> TokenStream {
+    signmag::signmag_bytes(input.into()).into()
+}
+
+/// Gray converts a literal into the Gray code of its value, as an array of
+/// bytes, for rotary encoders and ADC interfaces that count in Gray code.
+///
+/// Accepts an integer or hex string literal, with the same number of bytes as
+/// [`bytes!`], which the Gray code keeps. See [`from_gray!`] for the inverse.
+///
+/// ### Examples
+///
+/// ```
+/// let code = bytes_lit::gray!(0b0111);
+/// assert_eq!(code, [0b0100]);
+/// ```
+///
+/// ```
+/// let code = bytes_lit::gray!(0x0100);
+/// assert_eq!(code, [0x01, 0x80]);
+/// ```
+#[proc_macro]
+pub fn gray(input: TokenStream) -> TokenStream {
+    gray::gray(input.into()).into()
+}
+
+/// From_gray converts a literal of a Gray code into its value, as an array of
+/// bytes, the inverse of [`gray!`].
+///
+/// Accepts an integer or hex string literal, with the same number of bytes as
+/// [`bytes!`], which the value keeps.
+///
+/// ### Examples
+///
+/// ```
+/// let value = bytes_lit::from_gray!(0b0100);
+/// assert_eq!(value, [0b0111]);
+/// ```
+#[proc_macro]
+pub fn from_gray(input: TokenStream) -> TokenStream {
+    gray::from_gray(input.into()).into()
+}
+
+/// Base45 decodes a Base45 string literal, as specified by RFC 9285, into an
+/// array of bytes.
+///
+/// Base45 is used by QR code payloads, such as the EU Digital COVID
+/// Certificate. The characters must be in the Base45 alphabet, each group of
+/// three characters must not exceed 16 bits, and a final group of two
+/// characters must not exceed 8 bits.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::base45!("%69 VD92EX0");
+/// assert_eq!(bytes, *b"Hello!!");
+/// ```
+#[proc_macro]
+pub fn base45(input: TokenStream) -> TokenStream {
+    base45::base45(input.into()).into()
+}
+
+/// Rlp encodes a literal as a single RLP item, as used by Ethereum, into an
+/// array of bytes.
+///
+/// An integer literal is encoded as the minimal big endian bytes of its value,
+/// without leading zeros, so zero is the empty string. A byte string literal is
+/// encoded as its bytes, and a string literal as the bytes of its hex digits,
+/// as for [`bytes!`]. The bytes are then encoded with the RLP string rules: a
+/// single byte below `0x80` is itself, up to 55 bytes are prefixed by `0x80`
+/// plus the length, and longer strings by `0xb7` plus the length of the length,
+/// followed by the length.
+///
+/// ### Examples
+///
+/// ```
+/// let item = bytes_lit::rlp!(0x0400);
+/// assert_eq!(item, [0x82, 0x04, 0x00]);
+/// ```
+///
+/// ```
+/// let item = bytes_lit::rlp!(b"dog");
+/// assert_eq!(item, [0x83, b'd', b'o', b'g']);
+/// ```
+///
+/// ```
+/// let item = bytes_lit::rlp!(0);
+/// assert_eq!(item, [0x80]);
+/// ```
+#[proc_macro]
+pub fn rlp(input: TokenStream) -> TokenStream {
+    rlp::rlp(input.into()).into()
+}
+
+/// Cbor_bytes converts literals into a CBOR byte string item, major type 2,
+/// as an array of bytes, such as for COSE and WebAuthn test vectors.
+///
+/// Accepts the same input as [`bytes!`], except for the `chunk` and `byte_str`
+/// options. The bytes are prefixed by the CBOR header of their length, with
+/// the length in the initial byte below 24 bytes, and otherwise in the fewest
+/// of 1, 2, 4, or 8 bytes that follow it.
+///
+/// ### Examples
+///
+/// ```
+/// let item = bytes_lit::cbor_bytes!(0x01020304);
+/// assert_eq!(item, [0x44, 0x01, 0x02, 0x03, 0x04]);
+/// ```
+///
+/// ```
+/// let item = bytes_lit::cbor_bytes!(0x00; 32);
+/// assert_eq!(item[..2], [0x58, 32]);
+/// assert_eq!(item.len(), 34);
+/// ```
+#[proc_macro]
+pub fn cbor_bytes(input: TokenStream) -> TokenStream {
+    cbor::cbor_bytes(input.into()).into()
+}
+
+/// Msgpack_bin converts literals into a MessagePack bin item, as an array of
+/// bytes, such as for RPC fixtures.
+///
+/// Accepts the same input as [`bytes!`], except for the `chunk` and `byte_str`
+/// options. The bytes are prefixed by the smallest bin header that holds their
+/// length: bin 8, bin 16, or bin 32.
+///
+/// ### Examples
+///
+/// ```
+/// let item = bytes_lit::msgpack_bin!(0xdeadbeef);
+/// assert_eq!(item, [0xc4, 0x04, 0xde, 0xad, 0xbe, 0xef]);
+/// ```
+///
+/// ```
+/// let item = bytes_lit::msgpack_bin!(0x00; 300);
+/// assert_eq!(item[..3], [0xc5, 0x01, 0x2c]);
+/// ```
+#[proc_macro]
+pub fn msgpack_bin(input: TokenStream) -> TokenStream {
+    msgpack::msgpack_bin(input.into()).into()
+}
+
+/// Borsh_bytes converts literals into their Borsh encoding, as an array of
+/// bytes, such as for serialized fixtures of NEAR and Solana programs.
+///
+/// Accepts the same input as [`bytes!`], except for the `chunk` and `byte_str`
+/// options. The bytes are encoded as a `Vec<u8>`, prefixed by their length as a
+/// little endian `u32`.
+///
+/// Options, in addition to those of [`bytes!`]:
+/// - `fixed` encodes the bytes as a `[u8; N]` field, which has no length
+///   prefix.
+///
+/// ### Examples
+///
+/// ```
+/// let encoded = bytes_lit::borsh_bytes!(0xdeadbeef);
+/// assert_eq!(encoded, [4, 0, 0, 0, 0xde, 0xad, 0xbe, 0xef]);
+/// ```
+///
+/// ```
+/// let encoded = bytes_lit::borsh_bytes!(0xdeadbeef, fixed);
+/// assert_eq!(encoded, [0xde, 0xad, 0xbe, 0xef]);
+/// ```
+#[proc_macro]
+pub fn borsh_bytes(input: TokenStream) -> TokenStream {
+    borsh::borsh_bytes(input.into()).into()
+}
+
+/// Compressed_bytes declares a constant of bytes compressed with DEFLATE when
+/// the macro is expanded, and a constant of their length before compression,
+/// for large data that is decompressed at runtime, such as with the
+/// `miniz_oxide` crate.
+///
+/// Accepts the input of [`const_bytes!`], declaring a `[u8; N]` constant of the
+/// compressed bytes and a `usize` constant of the original length, named with
+/// a `_LEN` suffix. Attributes other than docs apply to both constants.
+/// Instead of literals the data may be read from a file with the `file`
+/// option, at a path relative to the manifest directory, and the file is
+/// tracked as by [`bytes_file!`].
+///
+/// Options, in addition to those of [`bytes!`]:
+/// - `file = "path"` reads the data from the file at the path, instead of
+///   literals, and is followed only by the options below.
+/// - `level = N` is the compression level, from 0 for no compression to 10, the
+///   default, for the best compression.
+/// - `zlib` wraps the compressed data in the zlib format, with a header and an
+///   Adler-32 checksum, instead of raw DEFLATE.
+///
+/// ### Examples
+///
+/// ```ignore
+/// bytes_lit::compressed_bytes!(pub TABLE = 0x00; 65536);
+/// let table = miniz_oxide::inflate::decompress_to_vec(&TABLE).unwrap();
+/// assert_eq!(table.len(), TABLE_LEN);
+/// ```
+///
+/// ```ignore
+/// bytes_lit::compressed_bytes!(FIRMWARE, file = "firmware.bin", zlib);
+/// ```
+#[proc_macro]
+pub fn compressed_bytes(input: TokenStream) -> TokenStream {
+    compress::compressed_bytes(input.into()).into()
+}
+
+/// Gunzip_bytes decodes a base64 string of gzip or zlib compressed data, and
+/// decompresses it when the macro is expanded, into an array of the
+/// uncompressed bytes, so that no decompression is needed at runtime.
+///
+/// The format is told apart by its header. The CRC-32 and length in the
+/// trailer of gzip data, and the Adler-32 checksum of zlib data, are verified.
+/// Only a single gzip member is supported.
+///
+/// Options:
+/// - `alphabet = standard|url` selects the base64 alphabet, as with
+///   [`base64!`], and defaults to `standard`.
+/// - `raw` decompresses DEFLATE data without a gzip or zlib header.
+///
+/// ### Examples
+///
+/// ```
+/// let bytes = bytes_lit::gunzip_bytes!("H4sIAAAAAAACA8tIzcnJVyjPL8pJAQCFEUoNCwAAAA==");
+/// assert_eq!(&bytes, b"hello world");
+/// ```
+///
+/// ```
+/// let bytes = bytes_lit::gunzip_bytes!("eJzLSM3JyVcozy/KSQEAGgsEXQ==");
+/// assert_eq!(&bytes, b"hello world");
+/// ```
+#[proc_macro]
+pub fn gunzip_bytes(input: TokenStream) -> TokenStream {
+    gunzip::gunzip_bytes(input.into()).into()
+}
+
+/// Bitfields packs fields of any width in bits into an array of bytes, such as
+/// for register values and packet headers.
+///
+/// Each field is `width => value`, of an integer literal in the number of bits
+/// of the width, and fields are separated by commas. The fields are packed in
+/// order, each with its most significant bit first, starting at the most
+/// significant bit of the first byte. It is an error for a value to need more
+/// bits than its width, or for the fields to total a number of bits that is
+/// not a multiple of 8.
+///
+/// Options:
+/// - `lsb_first` packs the fields starting at the least significant bit of the
+///   first byte, each with its least significant bit first, as for the bit
+///   fields of C structs on little endian targets.
+/// - `pad` zero pads the last byte when the fields do not fill it.
+///
+/// ### Examples
+///
+/// ```
+/// let header = bytes_lit::bitfields!(4 => 0b1010, 6 => 0x2A, 6 => 0);
+/// assert_eq!(header, [0b1010_1010, 0b1000_0000]);
+/// ```
+///
+/// ```
+/// let flags = bytes_lit::bitfields!(1 => 1, 2 => 0b10; lsb_first, pad);
+/// assert_eq!(flags, [0b0000_0101]);
+/// ```
+#[proc_macro]
+pub fn bitfields(input: TokenStream) -> TokenStream {
+    bitfields::bitfields(input.into()).into()
+}
+
+/// Ecc_bytes converts literals into bytes protected by parity or a Hamming
+/// code, as an array of bytes, for constants of firmware that checks or
+/// corrects errors in its memory.
+///
+/// Accepts the same input as [`bytes!`], except for the `chunk` and `byte_str`
+/// options, and requires the `scheme` option.
+///
+/// Options, in addition to those of [`bytes!`]:
+/// - `scheme = even_parity` or `scheme = odd_parity` is the bytes followed by
+///   a parity bit per byte, packed eight to a byte with the parity bit of the
+///   first byte in the most significant bit. The parity bit makes the number
+///   of one bits of the byte and the parity bit even, or odd.
+/// - `scheme = hamming74` encodes each nibble, the high nibble first, as a
+///   Hamming(7,4) codeword in the low seven bits of a byte, in the order
+///   `p1 p2 d1 p3 d2 d3 d4` with `d1` the most significant bit of the nibble,
+///   which corrects single bit errors.
+/// - `scheme = secded` encodes each nibble as for `hamming74`, with a parity
+///   bit of the codeword in the most significant bit, an extended Hamming(8,4)
+///   code that also detects double bit errors.
+///
+/// ### Examples
+///
+/// ```
+/// let protected = bytes_lit::ecc_bytes!(0x0103; scheme = even_parity);
+/// assert_eq!(protected, [0x01, 0x03, 0b1000_0000]);
+/// ```
+///
+/// ```
+/// let protected = bytes_lit::ecc_bytes!(0xb0; scheme = hamming74);
+/// assert_eq!(protected, [0b0011_0011, 0]);
+/// ```
+#[proc_macro]
+pub fn ecc_bytes(input: TokenStream) -> TokenStream {
+    ecc::ecc_bytes(input.into()).into()
+}
@@ -0,0 +1,116 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::LitStr;
+
+use crate::{emit, error::StrError, hex};
+
+pub fn mac(input: TokenStream2) -> TokenStream2 {
+    let lit = match syn::parse2::<LitStr>(input) {
+        Ok(lit) => lit,
+        Err(e) => return e.to_compile_error(),
+    };
+    match parse(&lit.value()) {
+        Ok(bytes) => emit::array(&bytes),
+        Err(e) => e.into_error(&lit).to_compile_error(),
+    }
+}
+
+/// Parses a MAC address of six two digit hex groups separated by `:` or `-`,
+/// or of three four digit hex groups separated by `.`.
+pub fn parse(s: &str) -> Result<[u8; 6], StrError> {
+    let (digits_per_group, len, sep) = if s.contains('.') {
+        (4, 14, b'.')
+    } else {
+        let sep = s.bytes().find(|c| *c == b':' || *c == b'-').unwrap_or(b':');
+        (2, 17, sep)
+    };
+    if s.len() != len {
+        return Err(StrError::new(
+            s.len().min(len),
+            format!(
+                "invalid MAC address length, expected {} characters, found {}",
+                len,
+                s.len()
+            ),
+        ));
+    }
+
+    let mut bytes = [0u8; 6];
+    let mut digits = 0;
+    for (i, c) in s.bytes().enumerate() {
+        if i % (digits_per_group + 1) == digits_per_group {
+            if c != sep {
+                return Err(StrError::new(
+                    i,
+                    format!("expected {:?}, found {:?}", sep as char, c as char),
+                ));
+            }
+            continue;
+        }
+        let v = hex::value(c)
+            .ok_or_else(|| StrError::new(i, format!("invalid hex character {:?}", c as char)))?;
+        bytes[digits / 2] |= v << (4 * (1 - digits % 2));
+        digits += 1;
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{mac, parse};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray};
+
+    #[test]
+    fn forms() {
+        let expect = [0xde, 0xad, 0xbe, 0xef, 0x00, 0x01];
+        let table: &[&str] = &["de:ad:be:ef:00:01", "DE-AD-BE-EF-00-01", "dead.beef.0001"];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse(t), Ok(expect), "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(&str, StrError)] = &[
+            (
+                "de:ad:be:ef:00",
+                StrError::new(
+                    14,
+                    "invalid MAC address length, expected 17 characters, found 14",
+                ),
+            ),
+            (
+                "de:ad:be:ef:00:01:02",
+                StrError::new(
+                    17,
+                    "invalid MAC address length, expected 17 characters, found 20",
+                ),
+            ),
+            (
+                "de:ad-be:ef:00:01",
+                StrError::new(5, "expected ':', found '-'"),
+            ),
+            (
+                "de:ad:be:eg:00:01",
+                StrError::new(10, "invalid hex character 'g'"),
+            ),
+            (
+                "dead.beef:0001",
+                StrError::new(9, "expected '.', found ':'"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse(t.0).as_ref(), Err(&t.1), "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn macro_output() {
+        let tokens = mac(quote! {"de:ad:be:ef:00:01"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([222u8, 173u8, 190u8, 239u8, 0u8, 1u8]);
+        assert_eq!(parsed, expect);
+    }
+}
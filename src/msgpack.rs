@@ -0,0 +1,89 @@
+use std::convert::TryFrom;
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::Error;
+
+use crate::{bytes, diagnostic, emit};
+
+pub fn msgpack_bin(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<bytes::Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(input: bytes::Input) -> Result<TokenStream2, Error> {
+    let bytes::Output { bytes, warnings } = bytes::value(input)?;
+    let len = u32::try_from(bytes.len()).map_err(|_| {
+        Error::new(
+            Span::call_site(),
+            format!(
+                "{} bytes exceed the maximum length of the bin 32 format",
+                bytes.len()
+            ),
+        )
+    })?;
+    let mut item = header(len);
+    item.extend(bytes);
+    Ok(diagnostic::with_warnings(&warnings, emit::array(&item)))
+}
+
+/// Returns the header of a MessagePack bin of the length, in the smallest
+/// format that holds it: bin 8, bin 16, or bin 32, with the length big endian.
+pub fn header(len: u32) -> Vec<u8> {
+    match len {
+        0..=0xff => vec![0xc4, len as u8],
+        0x100..=0xffff => {
+            let mut header = vec![0xc5];
+            header.extend_from_slice(&(len as u16).to_be_bytes());
+            header
+        }
+        _ => {
+            let mut header = vec![0xc6];
+            header.extend_from_slice(&len.to_be_bytes());
+            header
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{header, msgpack_bin};
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray};
+
+    #[test]
+    fn headers() {
+        let table: &[(u32, &[u8])] = &[
+            (0, &[0xc4, 0x00]),
+            (255, &[0xc4, 0xff]),
+            (256, &[0xc5, 0x01, 0x00]),
+            (65535, &[0xc5, 0xff, 0xff]),
+            (65536, &[0xc6, 0x00, 0x01, 0x00, 0x00]),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(header(t.0), t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn items() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(b""), parse_quote!([196u8, 0u8])),
+            (
+                quote!(0xdeadbeef),
+                parse_quote!([196u8, 4u8, 222u8, 173u8, 190u8, 239u8]),
+            ),
+            (
+                quote!("01:02", size = 3),
+                parse_quote!([196u8, 3u8, 0u8, 1u8, 2u8]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = msgpack_bin(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::LitStr;
+
+use crate::{base32, base58, base64, emit, error::StrError, hex};
+
+pub fn multibase(input: TokenStream2) -> TokenStream2 {
+    let lit = match syn::parse2::<LitStr>(input) {
+        Ok(lit) => lit,
+        Err(e) => return e.to_compile_error(),
+    };
+    match decode(&lit.value()) {
+        Ok(bytes) => emit::array(&bytes),
+        Err(e) => e.into_error(&lit).to_compile_error(),
+    }
+}
+
+/// Decodes a multibase string, dispatching on the prefix character to the
+/// decoder for the base.
+pub fn decode(s: &str) -> Result<Vec<u8>, StrError> {
+    let mut chars = s.chars();
+    let prefix = chars
+        .next()
+        .ok_or_else(|| StrError::new(0, "missing multibase prefix"))?;
+    let data = chars.as_str();
+    let result = match prefix {
+        'f' | 'F' => hex::decode(data),
+        'b' | 'c' => base32::decode(
+            &data.to_ascii_uppercase(),
+            base32::Alphabet::Standard,
+            pad(prefix == 'c'),
+        ),
+        'B' | 'C' => base32::decode(data, base32::Alphabet::Standard, pad(prefix == 'C')),
+        'v' | 't' => base32::decode(
+            &data.to_ascii_uppercase(),
+            base32::Alphabet::Hex,
+            pad(prefix == 't'),
+        ),
+        'V' | 'T' => base32::decode(data, base32::Alphabet::Hex, pad(prefix == 'T')),
+        'z' => base58::decode(data),
+        'm' | 'M' => base64::decode(data, base64::Alphabet::Standard),
+        'u' | 'U' => base64::decode(data, base64::Alphabet::Url),
+        _ => {
+            return Err(StrError::new(
+                0,
+                format!("unsupported multibase prefix {:?}", prefix),
+            ))
+        }
+    };
+    // Offset errors by the length of the prefix.
+    result.map_err(|e| StrError::new(e.index + prefix.len_utf8(), e.message))
+}
+
+fn pad(padded: bool) -> base32::Pad {
+    if padded {
+        base32::Pad::Required
+    } else {
+        base32::Pad::None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode, multibase};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn prefixes() {
+        // Vectors for "yes mani !" from the multibase specification.
+        let table: &[&str] = &[
+            "f796573206d616e692021",
+            "F796573206D616E692021",
+            "bpfsxgidnmfxgsibb",
+            "BPFSXGIDNMFXGSIBB",
+            "cpfsxgidnmfxgsibb",
+            "CPFSXGIDNMFXGSIBB",
+            "vf5in683dc5n6i811",
+            "VF5IN683DC5N6I811",
+            "z7paNL19xttacUY",
+            "meWVzIG1hbmkgIQ",
+            "MeWVzIG1hbmkgIQ==",
+            "ueWVzIG1hbmkgIQ",
+            "UeWVzIG1hbmkgIQ==",
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                decode(t).as_deref(),
+                Ok(&b"yes mani !"[..]),
+                "table entry: {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            decode(""),
+            Err(StrError::new(0, "missing multibase prefix"))
+        );
+        assert_eq!(
+            decode("Qabc"),
+            Err(StrError::new(0, "unsupported multibase prefix 'Q'"))
+        );
+        assert_eq!(
+            decode("f00zz"),
+            Err(StrError::new(3, "invalid hex character 'z'"))
+        );
+    }
+
+    #[test]
+    fn macro_output() {
+        let tokens = multibase(quote! {"f00ff"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([0u8, 255u8]);
+        assert_eq!(parsed, expect);
+
+        let tokens = multibase(quote! {"z0"});
+        let expect = Error::new(Span::call_site(), "invalid base58 character '0' at index 1")
+            .to_compile_error()
+            .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
@@ -0,0 +1,198 @@
+use num_bigint::BigUint;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, Ident, Token,
+};
+
+use crate::{bytes, diagnostic, emit, varint};
+
+/// A multihash function: its name, its code in the multicodec table, and the
+/// length of its digest.
+pub struct Code {
+    pub name: &'static str,
+    pub code: u64,
+    pub len: usize,
+}
+
+/// The supported multihash functions.
+pub const CODES: &[Code] = &[
+    Code {
+        name: "sha1",
+        code: 0x11,
+        len: 20,
+    },
+    Code {
+        name: "sha2_256",
+        code: 0x12,
+        len: 32,
+    },
+    Code {
+        name: "sha2_512",
+        code: 0x13,
+        len: 64,
+    },
+    Code {
+        name: "sha3_512",
+        code: 0x14,
+        len: 64,
+    },
+    Code {
+        name: "sha3_384",
+        code: 0x15,
+        len: 48,
+    },
+    Code {
+        name: "sha3_256",
+        code: 0x16,
+        len: 32,
+    },
+    Code {
+        name: "sha3_224",
+        code: 0x17,
+        len: 28,
+    },
+    Code {
+        name: "keccak_256",
+        code: 0x1b,
+        len: 32,
+    },
+    Code {
+        name: "blake3",
+        code: 0x1e,
+        len: 32,
+    },
+    Code {
+        name: "blake2b_256",
+        code: 0xb220,
+        len: 32,
+    },
+    Code {
+        name: "blake2s_256",
+        code: 0xb260,
+        len: 32,
+    },
+];
+
+/// Returns the multihash function with the code, if it is supported.
+pub fn from_code(code: u64) -> Option<&'static Code> {
+    CODES.iter().find(|c| c.code == code)
+}
+
+/// Input to the multihash macro: the name of the hash function, followed by
+/// `,` and the input of the bytes macro for the digest.
+struct Input {
+    name: Ident,
+    digest: bytes::Input,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let digest = input.parse()?;
+        Ok(Self { name, digest })
+    }
+}
+
+pub fn multihash(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(input: Input) -> Result<TokenStream2, Error> {
+    let code = CODES.iter().find(|c| input.name == c.name).ok_or_else(|| {
+        let names: Vec<&str> = CODES.iter().map(|c| c.name).collect();
+        Error::new(
+            input.name.span(),
+            format!(
+                "unsupported multihash function `{}`, expected one of: {}",
+                input.name,
+                names.join(", ")
+            ),
+        )
+    })?;
+    let bytes::Output { bytes, warnings } = bytes::value(input.digest)?;
+    if bytes.len() != code.len {
+        return Err(Error::new(
+            input.name.span(),
+            format!(
+                "invalid {} digest length, expected {} bytes, found {}",
+                code.name,
+                code.len,
+                bytes.len()
+            ),
+        ));
+    }
+    Ok(diagnostic::with_warnings(
+        &warnings,
+        emit::array(&encode(code, &bytes)),
+    ))
+}
+
+/// Encodes a digest as a multihash: the code and the length of the digest as
+/// unsigned varints, followed by the digest.
+pub fn encode(code: &Code, digest: &[u8]) -> Vec<u8> {
+    let mut bytes = varint::encode_unsigned(&BigUint::from(code.code));
+    bytes.extend(varint::encode_unsigned(&BigUint::from(digest.len())));
+    bytes.extend(digest);
+    bytes
+}
+
+#[cfg(test)]
+mod test {
+    use super::multihash;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn codes() {
+        let table: &[(_, ExprArray)] = &[
+            (
+                quote!(sha1, 0xa9993e364706816aba3e25717850c26c9cd0d89d),
+                parse_quote!([
+                    17u8, 20u8, 169u8, 153u8, 62u8, 54u8, 71u8, 6u8, 129u8, 106u8, 186u8, 62u8,
+                    37u8, 113u8, 120u8, 80u8, 194u8, 108u8, 156u8, 208u8, 216u8, 157u8
+                ]),
+            ),
+            (
+                quote!(blake2b_256, 0x00; 32),
+                parse_quote!([
+                    160u8, 228u8, 2u8, 32u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8,
+                    0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8,
+                    0u8, 0u8, 0u8, 0u8, 0u8
+                ]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = multihash(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table = &[
+            (
+                quote!(sha2_256, 0x0102),
+                "invalid sha2_256 digest length, expected 32 bytes, found 2",
+            ),
+            (
+                quote!(md5, 0x0102),
+                "unsupported multihash function `md5`, expected one of: sha1, sha2_256, sha2_512, sha3_512, sha3_384, sha3_256, sha3_224, keccak_256, blake3, blake2b_256, blake2s_256",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = multihash(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use proc_macro2::TokenStream as TokenStream2;
+use sha2::{Digest, Sha256};
+use syn::LitStr;
+
+use crate::emit;
+
+pub fn network_id(input: TokenStream2) -> TokenStream2 {
+    let lit = match syn::parse2::<LitStr>(input) {
+        Ok(lit) => lit,
+        Err(e) => return e.to_compile_error(),
+    };
+    let id = Sha256::digest(lit.value().as_bytes());
+    emit::array(&id)
+}
+
+#[cfg(test)]
+mod test {
+    use super::network_id;
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray};
+
+    #[test]
+    fn networks() {
+        let table: &[(_, ExprArray)] = &[
+            (
+                quote!("Test SDF Network ; September 2015"),
+                parse_quote!([
+                    206u8, 224u8, 48u8, 45u8, 89u8, 132u8, 77u8, 50u8, 189u8, 202u8, 145u8, 92u8,
+                    130u8, 3u8, 221u8, 68u8, 179u8, 63u8, 187u8, 126u8, 220u8, 25u8, 5u8, 30u8,
+                    163u8, 122u8, 190u8, 223u8, 40u8, 236u8, 212u8, 114u8
+                ]),
+            ),
+            (
+                quote!("Public Global Stellar Network ; September 2015"),
+                parse_quote!([
+                    122u8, 195u8, 57u8, 151u8, 84u8, 78u8, 49u8, 117u8, 210u8, 102u8, 189u8, 2u8,
+                    36u8, 57u8, 178u8, 44u8, 219u8, 22u8, 80u8, 140u8, 1u8, 22u8, 63u8, 38u8,
+                    229u8, 203u8, 42u8, 62u8, 16u8, 69u8, 169u8, 121u8
+                ]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = network_id(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Error, LitInt};
+
+use crate::{bytes::parse_int, emit};
+
+pub fn nibbles(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<LitInt>(input).and_then(|lit| expand(&lit)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(lit: &LitInt) -> Result<TokenStream2, Error> {
+    let int_lit = parse_int(lit)?;
+    let total_len = (int_lit.total_bits().checked_add(3).expect("overflow")) / 4;
+
+    // Split the bytes of the value into nibbles, least significant first, then
+    // zero fill or drop zeros and reverse for the leading zeros.
+    let mut nibbles = int_lit
+        .bytes
+        .iter()
+        .rev()
+        .flat_map(|b| [b & 0xf, b >> 4])
+        .collect::<Vec<u8>>();
+    nibbles.resize(total_len, 0);
+    nibbles.reverse();
+
+    Ok(emit::array(&nibbles))
+}
+
+#[cfg(test)]
+mod test {
+    use super::nibbles;
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray};
+
+    #[test]
+    fn leading_zeros_preserved() {
+        let table: &[(_, ExprArray)] = &[
+            // Base 16.
+            (quote!(0xA1F), parse_quote!([10u8, 1u8, 15u8])),
+            (quote!(0x0A1F), parse_quote!([0u8, 10u8, 1u8, 15u8])),
+            (quote!(0x00_1), parse_quote!([0u8, 0u8, 1u8])),
+            (quote!(0x0), parse_quote!([0u8])),
+            // Base 2.
+            (quote!(0b101), parse_quote!([5u8])),
+            (quote!(0b10100), parse_quote!([1u8, 4u8])),
+            (quote!(0b0000_0001), parse_quote!([0u8, 1u8])),
+            // Base 10.
+            (quote!(255), parse_quote!([15u8, 15u8])),
+            (quote!(256), parse_quote!([1u8, 0u8, 0u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = nibbles(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitStr,
+};
+
+use crate::{args::Args, base64, emit, error::StrError};
+
+struct Input {
+    lit: LitStr,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn pem(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let expected_label = input.args.str("label")?;
+    input.args.finish()?;
+
+    let (label, der) = decode(&input.lit.value()).map_err(|e| e.into_error(&input.lit))?;
+    if let Some(expected) = expected_label {
+        if label != expected.value() {
+            return Err(Error::new(
+                input.lit.span(),
+                format!(
+                    "PEM label {:?} does not match expected {:?}",
+                    label,
+                    expected.value()
+                ),
+            ));
+        }
+    }
+    Ok(emit::array(&der))
+}
+
+/// Decodes a single PEM block, returning the label and the DER bytes of the
+/// base64 decoded body. Whitespace surrounding the block is ignored.
+pub fn decode(s: &str) -> Result<(String, Vec<u8>), StrError> {
+    // Split into lines, keeping the index of the start of each line.
+    let mut lines = s
+        .split('\n')
+        .scan(0, |start, line| {
+            let line_start = *start;
+            *start += line.len() + 1;
+            Some((line_start, line.trim_end_matches('\r')))
+        })
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (begin_index, begin) = lines
+        .next()
+        .ok_or_else(|| StrError::new(0, "missing PEM BEGIN line"))?;
+    let label = begin
+        .trim()
+        .strip_prefix("-----BEGIN ")
+        .and_then(|l| l.strip_suffix("-----"))
+        .ok_or_else(|| StrError::new(begin_index, "expected PEM BEGIN line"))?;
+
+    let mut body = String::new();
+    let mut body_offsets = Vec::new();
+    let mut end = None;
+    for (index, line) in lines.by_ref() {
+        let line = line.trim();
+        if line.starts_with("-----END ") {
+            end = Some((index, line));
+            break;
+        }
+        // Track the offset of each character of the body in the string, so
+        // that decoding errors point at the right place.
+        let line_offset = index + s[index..].find(line).unwrap_or(0);
+        body_offsets.extend(line_offset..line_offset + line.len());
+        body.push_str(line);
+    }
+    let (end_index, end) = end.ok_or_else(|| StrError::new(s.len(), "missing PEM END line"))?;
+    let end_label = end
+        .strip_prefix("-----END ")
+        .and_then(|l| l.strip_suffix("-----"))
+        .ok_or_else(|| StrError::new(end_index, "invalid PEM END line"))?;
+    if end_label != label {
+        return Err(StrError::new(
+            end_index,
+            format!(
+                "PEM END label {:?} does not match BEGIN label {:?}",
+                end_label, label
+            ),
+        ));
+    }
+    if let Some((index, _)) = lines.next() {
+        return Err(StrError::new(
+            index,
+            "unexpected content after PEM END line",
+        ));
+    }
+
+    let der = base64::decode(&body, base64::Alphabet::Standard).map_err(|e| {
+        let index = body_offsets.get(e.index).copied().unwrap_or(end_index);
+        StrError::new(index, e.message)
+    })?;
+    Ok((label.to_string(), der))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode, pem};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn blocks() {
+        let table: &[&str] = &[
+            "-----BEGIN DATA-----\n3q2+7w==\n-----END DATA-----",
+            "\n    -----BEGIN DATA-----\n    3q2+\n    7w==\n    -----END DATA-----\n",
+            "-----BEGIN DATA-----\r\n3q2+7w==\r\n-----END DATA-----\r\n",
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                decode(t),
+                Ok(("DATA".to_string(), vec![0xde, 0xad, 0xbe, 0xef])),
+                "table entry: {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(&str, StrError)] = &[
+            ("", StrError::new(0, "missing PEM BEGIN line")),
+            ("3q2+7w==", StrError::new(0, "expected PEM BEGIN line")),
+            (
+                "-----BEGIN DATA-----\n3q2+7w==\n",
+                StrError::new(30, "missing PEM END line"),
+            ),
+            (
+                "-----BEGIN DATA-----\n3q2+7w==\n-----END KEY-----",
+                StrError::new(
+                    30,
+                    "PEM END label \"KEY\" does not match BEGIN label \"DATA\"",
+                ),
+            ),
+            (
+                "-----BEGIN DATA-----\n3q2+7w==\n-----END DATA-----\nmore",
+                StrError::new(49, "unexpected content after PEM END line"),
+            ),
+            (
+                "-----BEGIN DATA-----\n3q2+\n 7!==\n-----END DATA-----",
+                StrError::new(28, "invalid base64 character '!'"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(decode(t.0).as_ref(), Err(&t.1), "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn label() {
+        let tokens = pem(quote! {
+            "-----BEGIN DATA-----\n3q2+7w==\n-----END DATA-----", label = "DATA"
+        });
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([222u8, 173u8, 190u8, 239u8]);
+        assert_eq!(parsed, expect);
+
+        let tokens = pem(quote! {
+            "-----BEGIN DATA-----\n3q2+7w==\n-----END DATA-----", label = "CERTIFICATE"
+        });
+        let expect = Error::new(
+            Span::call_site(),
+            "PEM label \"DATA\" does not match expected \"CERTIFICATE\"",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
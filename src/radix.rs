@@ -0,0 +1,182 @@
+use num_bigint::BigUint;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitInt, LitStr, Token,
+};
+
+use crate::{args::Args, emit, error::StrError};
+
+/// Input to the bytes_radix macro: the radix, followed by `,` and the string
+/// of digits, and then options.
+struct Input {
+    radix: LitInt,
+    lit: LitStr,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let radix = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { radix, lit, args })
+    }
+}
+
+pub fn bytes_radix(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let size = input.args.int::<usize>("size")?;
+    input.args.finish()?;
+    let radix = input.radix.base10_parse::<u32>()?;
+    if !(2..=62).contains(&radix) {
+        return Err(Error::new(input.radix.span(), "radix must be from 2 to 62"));
+    }
+
+    let int = parse(&input.lit.value(), radix).map_err(|e| e.into_error(&input.lit))?;
+    let mut bytes = int.to_bytes_be();
+    match size {
+        Some(0) => {
+            return Err(Error::new(
+                input.lit.span(),
+                "size must be greater than zero",
+            ))
+        }
+        Some(size) if bytes.len() > size => {
+            return Err(Error::new(
+                input.lit.span(),
+                format!(
+                    "{} byte(s) do not fit in the size of {} byte(s)",
+                    bytes.len(),
+                    size
+                ),
+            ))
+        }
+        Some(size) => {
+            bytes.splice(0..0, vec![0; size - bytes.len()]);
+        }
+        None => {}
+    }
+    Ok(emit::array(&bytes))
+}
+
+/// Returns the value of a digit. Up to radix 36 the digits are `0-9` and the
+/// letters in either case. Above radix 36 upper case letters are 10 to 35 and
+/// lower case letters are 36 to 61, as in GMP.
+fn digit(c: u8, radix: u32) -> Option<u32> {
+    let v = match c {
+        b'0'..=b'9' => c - b'0',
+        b'A'..=b'Z' => c - b'A' + 10,
+        b'a'..=b'z' if radix <= 36 => c - b'a' + 10,
+        b'a'..=b'z' => c - b'a' + 36,
+        _ => return None,
+    };
+    Some(u32::from(v)).filter(|v| *v < radix)
+}
+
+/// Parses the digits of an unsigned integer in the radix.
+pub fn parse(s: &str, radix: u32) -> Result<BigUint, StrError> {
+    if s.is_empty() {
+        return Err(StrError::new(0, "expected at least one digit"));
+    }
+    let mut int = BigUint::default();
+    for (i, c) in s.bytes().enumerate() {
+        let v = digit(c, radix).ok_or_else(|| {
+            StrError::new(
+                i,
+                format!("invalid digit {:?} for radix {}", c as char, radix),
+            )
+        })?;
+        int = int * radix + v;
+    }
+    Ok(int)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bytes_radix, parse};
+    use crate::error::StrError;
+    use num_bigint::BigUint;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn values() {
+        let table: &[(&str, u32, u64)] = &[
+            ("0", 2, 0),
+            ("1010", 2, 10),
+            ("zik0zj", 36, 2147483647),
+            ("ZIK0ZJ", 36, 2147483647),
+            ("2LKcb1", 62, 2147483647),
+            ("z", 62, 61),
+            ("Z", 62, 35),
+            ("ff", 16, 255),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                parse(t.0, t.1),
+                Ok(BigUint::from(t.2)),
+                "table entry: {}",
+                i
+            );
+        }
+
+        let table: &[(&str, u32, StrError)] = &[
+            ("", 10, StrError::new(0, "expected at least one digit")),
+            ("102", 2, StrError::new(2, "invalid digit '2' for radix 2")),
+            (
+                "zz-",
+                36,
+                StrError::new(2, "invalid digit '-' for radix 36"),
+            ),
+            ("g", 16, StrError::new(0, "invalid digit 'g' for radix 16")),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse(t.0, t.1).as_ref(), Err(&t.2), "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn macros() {
+        let table: &[(_, ExprArray)] = &[
+            (
+                quote!(36, "zik0zj"),
+                parse_quote!([127u8, 255u8, 255u8, 255u8]),
+            ),
+            (
+                quote!(36, "zz", size = 4),
+                parse_quote!([0u8, 0u8, 5u8, 15u8]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = bytes_radix(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+
+        let table = &[
+            (quote!(63, "0"), "radix must be from 2 to 62"),
+            (quote!(1, "0"), "radix must be from 2 to 62"),
+            (
+                quote!(36, "zik0zj", size = 3),
+                "4 byte(s) do not fit in the size of 3 byte(s)",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = bytes_radix(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{parse::Parser, Error};
+
+use crate::{args::Args, emit};
+
+pub fn rand_bytes(input: TokenStream2) -> TokenStream2 {
+    match Parser::parse2(Args::parse_only, input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut args: Args) -> Result<TokenStream2, Error> {
+    let seed = args.int::<u64>("seed")?;
+    let len = args.int::<usize>("len")?;
+    args.finish()?;
+    let seed = seed.ok_or_else(|| missing("seed"))?;
+    let len = len.ok_or_else(|| missing("len"))?;
+    Ok(emit::array(&generate(seed, len)))
+}
+
+fn missing(name: &str) -> Error {
+    Error::new(
+        Span::call_site(),
+        format!("missing required option `{}`", name),
+    )
+}
+
+/// Generates bytes from the xoshiro256** generator, with its state seeded by
+/// SplitMix64 from the seed. Each output is taken as 8 little endian bytes.
+/// The output for a seed must never change, since fixtures depend on it.
+pub fn generate(seed: u64, len: usize) -> Vec<u8> {
+    let mut sm = seed;
+    let mut s = [0u64; 4];
+    for word in &mut s {
+        sm = sm.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = sm;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        *word = z ^ (z >> 31);
+    }
+
+    let mut bytes = Vec::with_capacity(len + 8);
+    while bytes.len() < len {
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        bytes.extend(result.to_le_bytes());
+    }
+    bytes.truncate(len);
+    bytes
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generate, rand_bytes};
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn stable() {
+        // The first output of xoshiro256** seeded by SplitMix64 from 0 is
+        // 0x99ec5f36cb75f2b4.
+        assert_eq!(
+            generate(0, 8),
+            [0xb4, 0xf2, 0x75, 0xcb, 0x36, 0x5f, 0xec, 0x99]
+        );
+        assert_eq!(generate(42, 1024)[..5], generate(42, 5)[..]);
+        assert_ne!(generate(42, 8), generate(43, 8));
+        assert_eq!(generate(42, 0), []);
+
+        let tokens = rand_bytes(quote!(seed = 0, len = 3));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([180u8, 242u8, 117u8]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn errors() {
+        let table = &[
+            (quote!(len = 4), "missing required option `seed`"),
+            (quote!(seed = 4), "missing required option `len`"),
+            (quote!(seed = 4, len = 4, size = 2), "unknown option `size`"),
+            (
+                quote!(seed = -1, len = 4),
+                "option `seed` requires an integer value",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = rand_bytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::str::FromStr;
+
+use num_bigint::BigUint;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Error, Lit};
+
+use crate::{
+    bytes::{allowed_form, lit_bytes, raw_digits, unsigned},
+    emit,
+};
+
+pub fn rlp(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Lit>(input).and_then(|lit| item(&lit)) {
+        Ok(bytes) => emit::array(&encode(&bytes)),
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Returns the bytes of the item a literal encodes. Integers are the minimal
+/// big endian bytes of their value, without leading zeros, so zero is empty.
+/// Byte strings are their bytes, and hex strings the bytes they decode to.
+fn item(lit: &Lit) -> Result<Vec<u8>, Error> {
+    match lit {
+        Lit::Int(int) => {
+            unsigned(int)?;
+            allowed_form(int, raw_digits(int).form)?;
+            let value = BigUint::from_str(int.base10_digits()).expect("valid integer literal");
+            if value == BigUint::default() {
+                return Ok(Vec::new());
+            }
+            Ok(value.to_bytes_be())
+        }
+        Lit::ByteStr(s) => Ok(s.value()),
+        Lit::Str(_) => lit_bytes(lit),
+        _ => Err(Error::new(
+            lit.span(),
+            "expected an integer, string, or byte string literal",
+        )),
+    }
+}
+
+/// Encodes bytes as an RLP string. A single byte below 0x80 is itself, a
+/// string of up to 55 bytes is prefixed by 0x80 plus its length, and a longer
+/// string by 0xb7 plus the length of its big endian length, and the length.
+pub fn encode(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len() + 9);
+    match bytes.len() {
+        1 if bytes[0] < 0x80 => {}
+        len @ 0..=55 => out.push(0x80 + len as u8),
+        len => {
+            let len = len.to_be_bytes();
+            let start = len.iter().position(|b| *b != 0).expect("nonzero length");
+            out.push(0xb7 + (len.len() - start) as u8);
+            out.extend_from_slice(&len[start..]);
+        }
+    }
+    out.extend_from_slice(bytes);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::{encode, rlp};
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn strings() {
+        assert_eq!(encode(b""), [0x80]);
+        assert_eq!(encode(&[0x00]), [0x00]);
+        assert_eq!(encode(&[0x7f]), [0x7f]);
+        assert_eq!(encode(&[0x80]), [0x81, 0x80]);
+        assert_eq!(encode(b"dog"), [0x83, b'd', b'o', b'g']);
+
+        let max_short = [0xaa; 55];
+        let encoded = encode(&max_short);
+        assert_eq!(encoded[0], 0xb7);
+        assert_eq!(&encoded[1..], &max_short[..]);
+
+        let long = [0xaa; 56];
+        let encoded = encode(&long);
+        assert_eq!(encoded[..2], [0xb8, 56]);
+        assert_eq!(&encoded[2..], &long[..]);
+
+        let long = [0xaa; 1024];
+        let encoded = encode(&long);
+        assert_eq!(encoded[..3], [0xb9, 0x04, 0x00]);
+        assert_eq!(encoded.len(), 1027);
+    }
+
+    #[test]
+    fn macros() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(0x0400), parse_quote!([130u8, 4u8, 0u8])),
+            (quote!(1024), parse_quote!([130u8, 4u8, 0u8])),
+            (quote!(0), parse_quote!([128u8])),
+            (quote!(0x00), parse_quote!([128u8])),
+            (quote!(15), parse_quote!([15u8])),
+            (quote!(0x80), parse_quote!([129u8, 128u8])),
+            (quote!(b"dog"), parse_quote!([131u8, 100u8, 111u8, 103u8])),
+            (quote!(b""), parse_quote!([128u8])),
+            (quote!("0400"), parse_quote!([130u8, 4u8, 0u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = rlp(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+
+        let table = &[
+            (
+                quote!(-1),
+                "negative values unsupported, use ibytes! with `size = 1` for two's complement bytes",
+            ),
+            (
+                quote!('a'),
+                "expected an integer, string, or byte string literal",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = rlp(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
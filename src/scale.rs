@@ -0,0 +1,118 @@
+use std::str::FromStr;
+
+use num_bigint::BigUint;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Error, LitInt};
+
+use crate::emit;
+
+pub fn scale_compact(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<LitInt>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(lit: LitInt) -> Result<TokenStream2, Error> {
+    let int = BigUint::from_str(lit.base10_digits())
+        .map_err(|_| Error::new(lit.span(), "negative values unsupported"))?;
+    match encode(&int) {
+        Some(bytes) => Ok(emit::array(&bytes)),
+        None => Err(Error::new(
+            lit.span(),
+            "value too large for SCALE compact encoding, the maximum is 2^536 - 1",
+        )),
+    }
+}
+
+/// Encodes an integer in SCALE compact form, returning None if the integer is
+/// too large to be encoded.
+///
+/// The two least significant bits of the first byte select the mode:
+/// - `0b00`: single byte mode, for values up to 2^6 - 1.
+/// - `0b01`: two byte mode, for values up to 2^14 - 1.
+/// - `0b10`: four byte mode, for values up to 2^30 - 1.
+/// - `0b11`: big integer mode, where the upper six bits of the first byte are
+///   the number of bytes that follow minus four, and the value follows in
+///   little endian.
+fn encode(int: &BigUint) -> Option<Vec<u8>> {
+    let mode = |size: usize, mode: u32| {
+        let mut bytes = ((int << 2u32) | BigUint::from(mode)).to_bytes_le();
+        bytes.resize(size, 0);
+        bytes
+    };
+    let bits = int.bits();
+    if bits <= 6 {
+        Some(mode(1, 0b00))
+    } else if bits <= 14 {
+        Some(mode(2, 0b01))
+    } else if bits <= 30 {
+        Some(mode(4, 0b10))
+    } else {
+        let value = int.to_bytes_le();
+        if value.len() > 67 {
+            return None;
+        }
+        let len = u8::try_from(value.len() - 4).expect("length fits in six bits");
+        let mut bytes = vec![(len << 2) | 0b11];
+        bytes.extend(value);
+        Some(bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::scale_compact;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn modes() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(0), parse_quote!([0u8])),
+            (quote!(1), parse_quote!([4u8])),
+            (quote!(42), parse_quote!([168u8])),
+            (quote!(63), parse_quote!([252u8])),
+            (quote!(64), parse_quote!([1u8, 1u8])),
+            (quote!(69), parse_quote!([21u8, 1u8])),
+            (quote!(16383), parse_quote!([253u8, 255u8])),
+            (quote!(16384), parse_quote!([2u8, 0u8, 1u8, 0u8])),
+            (quote!(65535), parse_quote!([254u8, 255u8, 3u8, 0u8])),
+            (
+                quote!(1073741823),
+                parse_quote!([254u8, 255u8, 255u8, 255u8]),
+            ),
+            (quote!(1073741824), parse_quote!([3u8, 0u8, 0u8, 0u8, 64u8])),
+            (
+                quote!(100000000000000),
+                parse_quote!([11u8, 0u8, 64u8, 122u8, 16u8, 243u8, 90u8]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = scale_compact(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn largest() {
+        // 2^536 - 1 is the largest value, encoded as 67 bytes of 0xff.
+        let max = format!("0x{}", "ff".repeat(67));
+        let tokens = scale_compact(max.parse().unwrap());
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        assert_eq!(parsed.elems.len(), 68);
+
+        let over = format!("0x1{}", "00".repeat(67));
+        let tokens = scale_compact(over.parse().unwrap());
+        let expect = Error::new(
+            Span::call_site(),
+            "value too large for SCALE compact encoding, the maximum is 2^536 - 1",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
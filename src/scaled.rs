@@ -0,0 +1,259 @@
+use std::str::FromStr;
+
+use num_bigint::{BigInt, Sign};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, Lit,
+};
+
+use crate::{args::Args, emit, suggest};
+
+struct Input {
+    lit: Lit,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn scaled_bytes(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let scale = input.args.int::<u32>("scale")?;
+    let size = input.args.int::<usize>("size")?;
+    input.args.finish()?;
+    let scale =
+        scale.ok_or_else(|| Error::new(Span::call_site(), "missing required option `scale`"))?;
+    let size = match size {
+        Some(0) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "size must be greater than zero",
+            ))
+        }
+        Some(size) => size,
+        None => {
+            return Err(Error::new(
+                Span::call_site(),
+                "missing required option `size`",
+            ))
+        }
+    };
+
+    let (digits, span) = match &input.lit {
+        Lit::Int(lit) => (lit.base10_digits(), lit.span()),
+        Lit::Float(lit) => (lit.base10_digits(), lit.span()),
+        lit => {
+            return Err(Error::new(
+                lit.span(),
+                "expected a decimal literal, such as `1.25`",
+            ))
+        }
+    };
+    let int = scale_decimal(digits, scale, size).map_err(|e| Error::new(span, e))?;
+
+    let bytes = emit::signed_bytes(&int, size).map_err(|min| {
+        Error::new(
+            span,
+            format!(
+                "scaled value {} does not fit in {} byte(s) as a two's complement integer, {}",
+                int,
+                size,
+                suggest::size(min)
+            ),
+        )
+    })?;
+    Ok(emit::array(&bytes))
+}
+
+/// Returns the exact value of the digits of a decimal literal multiplied by
+/// 10^scale, erroring if the result is not an integer, or has more digits than
+/// fit in the size in bytes. The digits are those of a literal validated by
+/// syn, with an optional leading minus sign, fraction, and exponent.
+pub fn scale_decimal(digits: &str, scale: u32, size: usize) -> Result<BigInt, String> {
+    let (mantissa, exponent) = match digits.find(['e', 'E']) {
+        Some(i) => (
+            &digits[..i],
+            BigInt::from_str(&digits[i + 1..]).expect("valid exponent"),
+        ),
+        None => (digits, BigInt::from(0)),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let int = BigInt::from_str(&format!("{}{}", whole, fraction)).expect("valid digits");
+    if int.sign() == Sign::NoSign {
+        return Ok(int);
+    }
+
+    // The power of ten the digits are multiplied by, which is bounded before
+    // the power is computed: a value of N bytes has at most N * 8 * log10(2)
+    // digits, rounded up, and dividing by more than the digits of the value
+    // leaves a fraction.
+    let shift = BigInt::from(scale) + exponent - BigInt::from(fraction.len());
+    let max_digits = BigInt::from(size) * 8u8 * 30103u32 / 100000u32 + 1u8;
+    if shift > max_digits {
+        return Err(format!(
+            "scaled value does not fit in {} byte(s) as a two's complement integer",
+            size
+        ));
+    }
+    let int_digits = int.magnitude().to_string().len();
+    if -&shift > BigInt::from(int_digits) {
+        return Err(fraction_error(scale));
+    }
+    let power = u32::try_from(shift.magnitude()).map_err(|_| {
+        format!(
+            "scaled value does not fit in {} byte(s) as a two's complement integer",
+            size
+        )
+    })?;
+    let ten = BigInt::from(10u8);
+    if shift.sign() != Sign::Minus {
+        return Ok(int * ten.pow(power));
+    }
+    let divisor = ten.pow(power);
+    if &int % &divisor != BigInt::from(0) {
+        return Err(fraction_error(scale));
+    }
+    Ok(int / divisor)
+}
+
+fn fraction_error(scale: u32) -> String {
+    format!("value has more decimal places than the scale of {}", scale)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{scale_decimal, scaled_bytes};
+    use num_bigint::BigInt;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn decimals() {
+        let table: &[(&str, u32, usize, Result<i64, &str>)] = &[
+            ("1.2345678", 7, 8, Ok(12345678)),
+            ("1", 7, 8, Ok(10000000)),
+            ("-1.5", 2, 8, Ok(-150)),
+            ("0.10", 1, 8, Ok(1)),
+            (
+                "1.25",
+                1,
+                8,
+                Err("value has more decimal places than the scale of 1"),
+            ),
+            ("1e3", 0, 8, Ok(1000)),
+            ("1.5e-1", 2, 8, Ok(15)),
+            (
+                "1.5e-2",
+                2,
+                8,
+                Err("value has more decimal places than the scale of 2"),
+            ),
+            ("0.0", 0, 8, Ok(0)),
+            ("0e99999999999999999999", 0, 8, Ok(0)),
+            (
+                "1e99999999999999999999",
+                0,
+                8,
+                Err("scaled value does not fit in 8 byte(s) as a two's complement integer"),
+            ),
+            (
+                "1e-99999999999999999999",
+                0,
+                8,
+                Err("value has more decimal places than the scale of 0"),
+            ),
+            (
+                "1",
+                4000000000,
+                8,
+                Err("scaled value does not fit in 8 byte(s) as a two's complement integer"),
+            ),
+            (
+                "1e4300000000",
+                0,
+                2000000000,
+                Err(
+                    "scaled value does not fit in 2000000000 byte(s) as a two's complement integer",
+                ),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(
+                scale_decimal(t.0, t.1, t.2),
+                t.3.map(BigInt::from).map_err(str::to_string),
+                "table entry: {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn bytes() {
+        let table: &[(_, ExprArray)] = &[
+            (
+                quote!(1.2345678, scale = 7, size = 8),
+                parse_quote!([0u8, 0u8, 0u8, 0u8, 0u8, 188u8, 97u8, 78u8]),
+            ),
+            (quote!(100, scale = 2, size = 2), parse_quote!([39u8, 16u8])),
+            (
+                quote!(-0.01, scale = 2, size = 2),
+                parse_quote!([255u8, 255u8]),
+            ),
+            (quote!(1.5f64, scale = 1, size = 1), parse_quote!([15u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = scaled_bytes(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table = &[
+            (
+                quote!(1.23456789, scale = 7, size = 8),
+                "value has more decimal places than the scale of 7",
+            ),
+            (
+                quote!(1.28, scale = 2, size = 1),
+                "scaled value 128 does not fit in 1 byte(s) as a two's complement integer, use `size = 2` or greater",
+            ),
+            (
+                quote!(1e99999999999999999999, scale = 0, size = 8),
+                "scaled value does not fit in 8 byte(s) as a two's complement integer",
+            ),
+            (
+                quote!(1, scale = 4000000000, size = 8),
+                "scaled value does not fit in 8 byte(s) as a two's complement integer",
+            ),
+            (quote!(1.5, size = 8), "missing required option `scale`"),
+            (quote!(1.5, scale = 1), "missing required option `size`"),
+            (
+                quote!("1.5", scale = 1, size = 1),
+                "expected a decimal literal, such as `1.25`",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = scaled_bytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
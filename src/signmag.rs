@@ -0,0 +1,142 @@
+use std::str::FromStr;
+
+use num_bigint::{BigInt, Sign};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitInt,
+};
+
+use crate::{args::Args, emit, suggest};
+
+struct Input {
+    lit: LitInt,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn signmag_bytes(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let size = input.args.int::<usize>("size")?;
+    let positive = input.args.int::<u8>("positive")?.unwrap_or(0x00);
+    let negative = input.args.int::<u8>("negative")?.unwrap_or(0x01);
+    input.args.finish()?;
+    if positive == negative {
+        return Err(Error::new(
+            Span::call_site(),
+            "options `positive` and `negative` must be different sign bytes",
+        ));
+    }
+
+    // The base10 digits of a literal validated by syn always parse, with a
+    // leading minus sign for negative values.
+    let int = BigInt::from_str(input.lit.base10_digits()).expect("valid integer");
+    let (sign, magnitude) = int.to_bytes_be();
+
+    // The size includes the sign byte, and the magnitude is zero padded on the
+    // left to fill the rest.
+    let len = match size {
+        Some(size) if size < 2 => {
+            return Err(Error::new(
+                Span::call_site(),
+                "size must be at least 2, for the sign byte and one magnitude byte",
+            ))
+        }
+        Some(size) if magnitude.len() > size - 1 => {
+            return Err(Error::new(
+                input.lit.span(),
+                format!(
+                    "magnitude does not fit in the {} byte(s) after the sign byte, {}",
+                    size - 1,
+                    suggest::size(magnitude.len() + 1)
+                ),
+            ))
+        }
+        Some(size) => size - 1,
+        None => magnitude.len(),
+    };
+    let mut bytes = Vec::with_capacity(len + 1);
+    bytes.push(if sign == Sign::Minus {
+        negative
+    } else {
+        positive
+    });
+    bytes.resize(1 + len - magnitude.len(), 0);
+    bytes.extend_from_slice(&magnitude);
+    Ok(emit::array(&bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::signmag_bytes;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn values() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(-255, size = 4), parse_quote!([1u8, 0u8, 0u8, 255u8])),
+            (quote!(255, size = 4), parse_quote!([0u8, 0u8, 0u8, 255u8])),
+            (quote!(-0x1234), parse_quote!([1u8, 18u8, 52u8])),
+            (quote!(0), parse_quote!([0u8, 0u8])),
+            (quote!(-0, size = 2), parse_quote!([0u8, 0u8])),
+            (
+                quote!(-1, size = 2, negative = 0x80),
+                parse_quote!([128u8, 1u8]),
+            ),
+            (
+                quote!(1, size = 2, positive = 0x2b, negative = 0x2d),
+                parse_quote!([43u8, 1u8]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = signmag_bytes(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table = &[
+            (
+                quote!(-256, size = 2),
+                "magnitude does not fit in the 1 byte(s) after the sign byte, use `size = 3` or greater",
+            ),
+            (
+                quote!(1, size = 1),
+                "size must be at least 2, for the sign byte and one magnitude byte",
+            ),
+            (
+                quote!(1, negative = 0),
+                "options `positive` and `negative` must be different sign bytes",
+            ),
+            (
+                quote!(1, negative = 256),
+                "number too large to fit in target type",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = signmag_bytes(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
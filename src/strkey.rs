@@ -0,0 +1,213 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitStr,
+};
+
+use crate::{args::Args, base32, crc::CRC16_XMODEM, emit};
+
+/// The kinds of strkeys, identified by the version byte and the first
+/// character of their encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// G: An ed25519 public key of an account.
+    PublicKeyEd25519,
+    /// S: An ed25519 secret seed.
+    PrivateKeyEd25519,
+    /// M: A muxed account, an ed25519 public key followed by a u64 ID.
+    MuxedAccountEd25519,
+    /// T: A pre-authorized transaction hash.
+    PreAuthTx,
+    /// X: A sha256 hash signer.
+    HashX,
+    /// C: A contract ID.
+    Contract,
+}
+
+impl Kind {
+    fn from_version(version: u8) -> Option<Self> {
+        match version {
+            48 => Some(Kind::PublicKeyEd25519),
+            144 => Some(Kind::PrivateKeyEd25519),
+            96 => Some(Kind::MuxedAccountEd25519),
+            152 => Some(Kind::PreAuthTx),
+            184 => Some(Kind::HashX),
+            16 => Some(Kind::Contract),
+            _ => None,
+        }
+    }
+
+    fn payload_len(self) -> usize {
+        match self {
+            Kind::MuxedAccountEd25519 => 40,
+            _ => 32,
+        }
+    }
+}
+
+struct Input {
+    lit: LitStr,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn strkey(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let allow_secret = input.args.flag("allow_secret")?;
+    input.args.finish()?;
+    let (kind, payload) = decode(&input.lit)?;
+    // Secret seeds pasted into source end up in binaries and repositories, so
+    // they are rejected unless explicitly allowed.
+    if kind == Kind::PrivateKeyEd25519 && !allow_secret {
+        return Err(Error::new(
+            input.lit.span(),
+            "strkey is a secret seed, which should not be in source code, use the `allow_secret` option if it must be",
+        ));
+    }
+    Ok(emit::array(&payload))
+}
+
+/// Decodes a strkey, verifying the version byte, length, and checksum, and
+/// returning the kind and payload.
+pub fn decode(lit: &LitStr) -> Result<(Kind, Vec<u8>), Error> {
+    let value = lit.value();
+    let data = base32::decode(&value, base32::Alphabet::Standard, base32::Pad::None)
+        .map_err(|e| e.into_error(lit))?;
+    let (version, rest) = data
+        .split_first()
+        .ok_or_else(|| Error::new(lit.span(), "strkey is empty"))?;
+    let kind = Kind::from_version(*version).ok_or_else(|| {
+        Error::new(
+            lit.span(),
+            format!(
+                "unsupported strkey version, expected the first character to be one of G, S, M, T, X, or C, found {:?}",
+                value.chars().next().unwrap_or_default()
+            ),
+        )
+    })?;
+    if rest.len() != kind.payload_len() + 2 {
+        return Err(Error::new(
+            lit.span(),
+            format!(
+                "invalid strkey length, expected a {} byte payload, found {} bytes",
+                kind.payload_len(),
+                rest.len().saturating_sub(2)
+            ),
+        ));
+    }
+
+    let (payload, checksum) = rest.split_at(kind.payload_len());
+    let expected = (CRC16_XMODEM.checksum(&data[..data.len() - 2]) as u16).to_le_bytes();
+    if checksum != expected {
+        return Err(Error::new(
+            lit.span(),
+            format!(
+                "strkey checksum mismatch: expected {:02x}{:02x}, found {:02x}{:02x}",
+                expected[0], expected[1], checksum[0], checksum[1]
+            ),
+        ));
+    }
+    Ok((kind, payload.to_vec()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::strkey;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn kinds() {
+        let table: &[(_, ExprArray)] = &[
+            (
+                quote!("GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ"),
+                parse_quote!([
+                    63u8, 12u8, 52u8, 191u8, 147u8, 173u8, 13u8, 153u8, 113u8, 208u8, 76u8, 204u8,
+                    144u8, 247u8, 5u8, 81u8, 28u8, 131u8, 138u8, 173u8, 151u8, 52u8, 164u8, 162u8,
+                    251u8, 13u8, 122u8, 3u8, 252u8, 127u8, 232u8, 154u8
+                ]),
+            ),
+            (
+                quote!("MA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVAAAAAAAAAAAAAJLK"),
+                parse_quote!([
+                    63u8, 12u8, 52u8, 191u8, 147u8, 173u8, 13u8, 153u8, 113u8, 208u8, 76u8, 204u8,
+                    144u8, 247u8, 5u8, 81u8, 28u8, 131u8, 138u8, 173u8, 151u8, 52u8, 164u8, 162u8,
+                    251u8, 13u8, 122u8, 3u8, 252u8, 127u8, 232u8, 154u8, 128u8, 0u8, 0u8, 0u8, 0u8,
+                    0u8, 0u8, 0u8
+                ]),
+            ),
+            (
+                quote!("CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE"),
+                parse_quote!([
+                    54u8, 62u8, 170u8, 56u8, 103u8, 132u8, 31u8, 186u8, 208u8, 244u8, 237u8, 136u8,
+                    199u8, 121u8, 228u8, 254u8, 102u8, 229u8, 106u8, 36u8, 112u8, 220u8, 152u8,
+                    192u8, 236u8, 156u8, 7u8, 61u8, 5u8, 199u8, 177u8, 3u8
+                ]),
+            ),
+            (
+                quote!(
+                    "SBU2RRGLXH3E5CQHTD3ODLDF2BWDCYUSSBLLZ5GNW7JXHDIYKXZWHOKR",
+                    allow_secret
+                ),
+                parse_quote!([
+                    105u8, 168u8, 196u8, 203u8, 185u8, 246u8, 78u8, 138u8, 7u8, 152u8, 246u8,
+                    225u8, 172u8, 101u8, 208u8, 108u8, 49u8, 98u8, 146u8, 144u8, 86u8, 188u8,
+                    244u8, 205u8, 183u8, 211u8, 115u8, 141u8, 24u8, 85u8, 243u8, 99u8
+                ]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = strkey(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table = &[
+            (
+                quote!("GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGA"),
+                "strkey checksum mismatch: expected c8d9, found c8c0",
+            ),
+            (
+                quote!("GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSG1"),
+                "invalid base32 character '1' at index 55",
+            ),
+            (
+                quote!("GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJU"),
+                "invalid strkey length, expected a 32 byte payload, found 30 bytes",
+            ),
+            (
+                quote!("AA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ"),
+                "unsupported strkey version, expected the first character to be one of G, S, M, T, X, or C, found 'A'",
+            ),
+            (
+                quote!("SBU2RRGLXH3E5CQHTD3ODLDF2BWDCYUSSBLLZ5GNW7JXHDIYKXZWHOKR"),
+                "strkey is a secret seed, which should not be in source code, use the `allow_secret` option if it must be",
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            let tokens = strkey(t.0.clone());
+            let expect = Error::new(Span::call_site(), t.1)
+                .to_compile_error()
+                .to_string();
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::str::FromStr;
+
+use num_bigint::{BigInt, BigUint};
+use syn::LitInt;
+
+/// Returns the value as a hex integer literal, with two digits per byte.
+pub fn hex_literal(int: &BigUint) -> String {
+    let digits: String = int
+        .to_bytes_be()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("0x{}", digits)
+}
+
+/// Suggests writing a value that has leading zeros in hex, where the leading
+/// zero digits are preserved.
+pub fn leading_zeros(int: &BigUint) -> String {
+    format!(
+        "write the value in hex as `{}`, where leading zero digits are preserved, or remove the zeros",
+        hex_literal(int)
+    )
+}
+
+/// Suggests the size option for a decimal literal with leading zeros, with the
+/// size that holds any value of the number of digits written, and otherwise
+/// writing the value in hex.
+pub fn fixed_width(int: &BigUint, digits: usize) -> String {
+    let max = BigUint::from(10u32).pow(digits as u32) - 1u32;
+    let size = (max.bits() as usize + 7) / 8;
+    format!(
+        "remove the zeros and use the `size` option for a fixed width, such as `{}, size = {}`, or write the value in hex as `{}`, where leading zero digits are preserved",
+        int,
+        size.max(1),
+        hex_literal(int)
+    )
+}
+
+/// Suggests ibytes! for a negative integer literal, with the smallest size
+/// that holds the value.
+pub fn negative(lit: &LitInt) -> String {
+    let size = BigInt::from_str(lit.base10_digits())
+        .map(|int| int.to_signed_bytes_be().len())
+        .unwrap_or(1);
+    format!(
+        "use ibytes! with `size = {}` for two's complement bytes",
+        size
+    )
+}
+
+/// Suggests the smallest size option that holds a value.
+pub fn size(min: usize) -> String {
+    format!("use `size = {}` or greater", min)
+}
+
+/// Returns the size in bytes of the integer type of a literal suffix, or None
+/// if there is no suffix or the type has a platform dependent size.
+pub fn suffix_size(suffix: &str) -> Option<usize> {
+    match suffix {
+        "u8" | "i8" => Some(1),
+        "u16" | "i16" => Some(2),
+        "u32" | "i32" => Some(4),
+        "u64" | "i64" => Some(8),
+        "u128" | "i128" => Some(16),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hex_literal, negative, suffix_size};
+    use num_bigint::BigUint;
+    use pretty_assertions::assert_eq;
+    use syn::parse_quote;
+
+    #[test]
+    fn suggestions() {
+        assert_eq!(hex_literal(&BigUint::from(255u32)), "0xff");
+        assert_eq!(hex_literal(&BigUint::from(256u32)), "0x0100");
+        assert_eq!(hex_literal(&BigUint::from(0u32)), "0x00");
+        assert_eq!(
+            negative(&parse_quote!(-129)),
+            "use ibytes! with `size = 2` for two's complement bytes"
+        );
+        assert_eq!(suffix_size("i16"), Some(2));
+        assert_eq!(suffix_size("usize"), None);
+        assert_eq!(suffix_size(""), None);
+    }
+}
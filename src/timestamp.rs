@@ -0,0 +1,345 @@
+use num_bigint::BigInt;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitStr,
+};
+
+use crate::{args::Args, emit, error::StrError, suggest};
+
+struct Input {
+    lit: LitStr,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn timestamp(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let digits = match input
+        .args
+        .ident("unit", &["seconds", "millis", "micros", "nanos"])?
+    {
+        Some(u) if u == "millis" => 3,
+        Some(u) if u == "micros" => 6,
+        Some(u) if u == "nanos" => 9,
+        _ => 0,
+    };
+    let size = input.args.int::<usize>("size")?.unwrap_or(8);
+    input.args.finish()?;
+    if size == 0 {
+        return Err(Error::new(
+            Span::call_site(),
+            "size must be greater than zero",
+        ));
+    }
+
+    let value = parse(&input.lit.value(), digits).map_err(|e| e.into_error(&input.lit))?;
+
+    let int = BigInt::from(value);
+    let bytes = emit::signed_bytes(&int, size).map_err(|min| {
+        Error::new(
+            input.lit.span(),
+            format!(
+                "timestamp does not fit in {} byte(s) as a two's complement integer, {}",
+                size,
+                suggest::size(min)
+            ),
+        )
+    })?;
+    Ok(emit::array(&bytes))
+}
+
+/// Parses an RFC 3339 timestamp, such as `2024-06-01T12:00:00Z`, into the
+/// time since the Unix epoch in units of 10^-digits seconds. Fractional
+/// seconds finer than the unit must be zero. A leap second, such as
+/// `2016-12-31T23:59:60Z`, is folded into the second after it, as Unix time
+/// has no leap seconds.
+pub fn parse(s: &str, digits: usize) -> Result<i128, StrError> {
+    let mut p = Parser {
+        s: s.as_bytes(),
+        i: 0,
+    };
+    let year = p.number(4, "year")?;
+    p.expect(b"-")?;
+    let month = p.field(2, "month", 1, 12)?;
+    p.expect(b"-")?;
+    let day = p.field(2, "day", 1, days_in_month(year, month))?;
+    p.expect(b"Tt ")?;
+    let hour = p.field(2, "hour", 0, 23)?;
+    p.expect(b":")?;
+    let minute = p.field(2, "minute", 0, 59)?;
+    p.expect(b":")?;
+    // A leap second, 60, is the same Unix time as the second after it.
+    let second = p.field(2, "second", 0, 60)?;
+
+    let mut fraction = 0i128;
+    if p.peek() == Some(b'.') {
+        p.i += 1;
+        let start = p.i;
+        while let Some(c) = p.peek().filter(u8::is_ascii_digit) {
+            let n = p.i - start;
+            if n < digits {
+                fraction = fraction * 10 + i128::from(c - b'0');
+            } else if c != b'0' {
+                return Err(StrError::new(
+                    p.i,
+                    "fractional seconds are finer than the unit",
+                ));
+            }
+            p.i += 1;
+        }
+        let n = p.i - start;
+        if n == 0 {
+            return Err(StrError::new(p.i, "expected fractional seconds"));
+        }
+        for _ in n..digits {
+            fraction *= 10;
+        }
+    }
+
+    let offset = match p.peek() {
+        Some(b'Z' | b'z') => {
+            p.i += 1;
+            0
+        }
+        Some(c @ (b'+' | b'-')) => {
+            p.i += 1;
+            let hours = p.field(2, "offset hour", 0, 23)?;
+            p.expect(b":")?;
+            let minutes = p.field(2, "offset minute", 0, 59)?;
+            let offset = hours * 3600 + minutes * 60;
+            if c == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => {
+            return Err(StrError::new(
+                p.i,
+                "expected `Z` or an offset such as `+01:00`",
+            ))
+        }
+    };
+    if p.i != s.len() {
+        return Err(StrError::new(
+            p.i,
+            "unexpected characters after the timestamp",
+        ));
+    }
+
+    let days = days_from_civil(year, month, day);
+    let seconds = days * 86400 + hour * 3600 + minute * 60 + second - offset;
+    Ok(i128::from(seconds) * 10i128.pow(digits as u32) + fraction)
+}
+
+struct Parser<'a> {
+    s: &'a [u8],
+    i: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.i).copied()
+    }
+
+    /// Consumes one of the characters.
+    fn expect(&mut self, any: &[u8]) -> Result<(), StrError> {
+        match self.peek() {
+            Some(c) if any.contains(&c) => {
+                self.i += 1;
+                Ok(())
+            }
+            _ => Err(StrError::new(
+                self.i,
+                format!("expected `{}`", any[0] as char),
+            )),
+        }
+    }
+
+    /// Consumes a number of exactly the digits.
+    fn number(&mut self, digits: usize, name: &str) -> Result<i64, StrError> {
+        let start = self.i;
+        let mut n = 0;
+        for _ in 0..digits {
+            match self.peek().filter(u8::is_ascii_digit) {
+                Some(c) => n = n * 10 + i64::from(c - b'0'),
+                None => {
+                    return Err(StrError::new(
+                        start,
+                        format!("expected a {} of {} digits", name, digits),
+                    ))
+                }
+            }
+            self.i += 1;
+        }
+        Ok(n)
+    }
+
+    /// Consumes a number of exactly the digits within the range.
+    fn field(&mut self, digits: usize, name: &str, min: i64, max: i64) -> Result<i64, StrError> {
+        let start = self.i;
+        let n = self.number(digits, name)?;
+        if n < min || n > max {
+            return Err(StrError::new(
+                start,
+                format!("invalid {} {}, expected {} to {}", name, n, min, max),
+            ));
+        }
+        Ok(n)
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days from 1970-01-01 to the date in the proleptic
+/// Gregorian calendar, using the algorithm of Howard Hinnant.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, timestamp};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn values() {
+        let table: &[(&str, usize, i128)] = &[
+            ("1970-01-01T00:00:00Z", 0, 0),
+            ("2024-06-01T12:00:00Z", 0, 1717243200),
+            ("2024-06-01t12:00:00z", 0, 1717243200),
+            ("2024-06-01 12:00:00Z", 0, 1717243200),
+            ("2024-06-01T14:00:00+02:00", 0, 1717243200),
+            ("2024-06-01T07:30:00-04:30", 0, 1717243200),
+            ("2024-02-29T00:00:00Z", 0, 1709164800),
+            ("1969-12-31T23:59:59Z", 0, -1),
+            ("2016-12-31T23:59:60Z", 0, 1483228800),
+            ("2017-01-01T00:00:00Z", 0, 1483228800),
+            ("2016-12-31T23:59:60.5Z", 3, 1483228800500),
+            ("2000-01-01T00:00:00.000Z", 0, 946684800),
+            ("2000-01-01T00:00:00.5Z", 3, 946684800500),
+            ("2000-01-01T00:00:00.123456Z", 6, 946684800123456),
+            ("2000-01-01T00:00:00.1230Z", 3, 946684800123),
+            ("2000-01-01T00:00:00Z", 9, 946684800000000000),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse(t.0, t.1), Ok(t.2), "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(&str, StrError)] = &[
+            (
+                "2023-02-29T00:00:00Z",
+                StrError::new(8, "invalid day 29, expected 1 to 28"),
+            ),
+            (
+                "2024-13-01T00:00:00Z",
+                StrError::new(5, "invalid month 13, expected 1 to 12"),
+            ),
+            (
+                "2024-06-01T24:00:00Z",
+                StrError::new(11, "invalid hour 24, expected 0 to 23"),
+            ),
+            (
+                "2024-06-01T12:00:61Z",
+                StrError::new(17, "invalid second 61, expected 0 to 60"),
+            ),
+            (
+                "24-06-01T12:00:00Z",
+                StrError::new(0, "expected a year of 4 digits"),
+            ),
+            ("2024-06-01", StrError::new(10, "expected `T`")),
+            (
+                "2024-06-01T12:00:00",
+                StrError::new(19, "expected `Z` or an offset such as `+01:00`"),
+            ),
+            (
+                "2024-06-01T12:00:00ZZ",
+                StrError::new(20, "unexpected characters after the timestamp"),
+            ),
+            (
+                "2024-06-01T12:00:00.5Z",
+                StrError::new(20, "fractional seconds are finer than the unit"),
+            ),
+            (
+                "2024-06-01T12:00:00.Z",
+                StrError::new(20, "expected fractional seconds"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse(t.0, 0).as_ref(), Err(&t.1), "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn options() {
+        let table: &[(_, ExprArray)] = &[
+            (
+                quote!("2024-06-01T12:00:00Z"),
+                parse_quote!([0u8, 0u8, 0u8, 0u8, 102u8, 91u8, 13u8, 64u8]),
+            ),
+            (
+                quote!("2024-06-01T12:00:00Z", size = 4),
+                parse_quote!([102u8, 91u8, 13u8, 64u8]),
+            ),
+            (
+                quote!("2024-06-01T12:00:00.250Z", unit = millis),
+                parse_quote!([0u8, 0u8, 1u8, 143u8, 211u8, 171u8, 194u8, 250u8]),
+            ),
+            (
+                quote!("1969-12-31T23:59:59Z", size = 4),
+                parse_quote!([255u8, 255u8, 255u8, 255u8]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = timestamp(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+
+        let tokens = timestamp(quote!("2024-06-01T12:00:00Z", unit = millis, size = 4));
+        let expect = Error::new(
+            Span::call_site(),
+            "timestamp does not fit in 4 byte(s) as a two's complement integer, use `size = 6` or greater",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
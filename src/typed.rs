@@ -0,0 +1,171 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Data, DeriveInput, Error, Fields, Token, Type,
+};
+
+use crate::{bytes, diagnostic, emit};
+
+/// Input to the typed_bytes macro: the type, followed by `,` and the input of
+/// the bytes macro.
+struct Input {
+    ty: Type,
+    bytes: bytes::Input,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let bytes = input.parse()?;
+        Ok(Self { ty, bytes })
+    }
+}
+
+pub fn typed_bytes(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Expands to a call of the constructor that FromBytesLit derives, whose
+/// parameter is the wrapped array, so the compiler checks the length.
+fn expand(input: Input) -> Result<TokenStream2, Error> {
+    let ty = input.ty;
+    let bytes::Output { bytes, warnings } = bytes::value(input.bytes)?;
+    let array = emit::array(&bytes);
+    Ok(diagnostic::with_warnings(
+        &warnings,
+        quote!(<#ty>::from_bytes_lit(#array)),
+    ))
+}
+
+pub fn derive_from_bytes_lit(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<DeriveInput>(input).and_then(derive) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Derives a const constructor from the wrapped array, for a struct with a
+/// single field that is an array of bytes.
+fn derive(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => return Err(single_array_field(&input)),
+    };
+    let field = match fields.iter().collect::<Vec<_>>().as_slice() {
+        [field] if matches!(field.ty, Type::Array(_)) => *field,
+        _ => return Err(single_array_field(&input)),
+    };
+    let ty = &field.ty;
+    let value = match (fields, &field.ident) {
+        (Fields::Named(_), Some(ident)) => quote!(Self { #ident: bytes }),
+        _ => quote!(Self(bytes)),
+    };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Creates the value from its bytes, as used by typed_bytes!.
+            #[doc(hidden)]
+            pub const fn from_bytes_lit(bytes: #ty) -> Self {
+                #value
+            }
+        }
+    })
+}
+
+fn single_array_field(input: &DeriveInput) -> Error {
+    Error::new(
+        input.ident.span(),
+        "FromBytesLit requires a struct with a single field of type `[u8; N]`",
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{derive_from_bytes_lit, typed_bytes};
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, Expr, ItemImpl};
+
+    #[test]
+    fn typed() {
+        let tokens = typed_bytes(quote!(ContractId, 0x0102));
+        let parsed = syn::parse2::<Expr>(tokens).unwrap();
+        let expect: Expr = parse_quote!(<ContractId>::from_bytes_lit([1u8, 2u8]));
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn derive() {
+        let table: &[(_, ItemImpl)] = &[
+            (
+                quote!(
+                    struct ContractId([u8; 32]);
+                ),
+                parse_quote!(
+                    impl ContractId {
+                        /// Creates the value from its bytes, as used by typed_bytes!.
+                        #[doc(hidden)]
+                        pub const fn from_bytes_lit(bytes: [u8; 32]) -> Self {
+                            Self(bytes)
+                        }
+                    }
+                ),
+            ),
+            (
+                quote!(
+                    pub struct Key {
+                        bytes: [u8; 4],
+                    }
+                ),
+                parse_quote!(
+                    impl Key {
+                        /// Creates the value from its bytes, as used by typed_bytes!.
+                        #[doc(hidden)]
+                        pub const fn from_bytes_lit(bytes: [u8; 4]) -> Self {
+                            Self { bytes: bytes }
+                        }
+                    }
+                ),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = derive_from_bytes_lit(t.0);
+            let parsed = syn::parse2::<ItemImpl>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn derive_errors() {
+        let table = [
+            quote!(
+                struct Id(u32);
+            ),
+            quote!(
+                struct Id([u8; 4], [u8; 4]);
+            ),
+            quote!(
+                enum Id {
+                    A,
+                }
+            ),
+        ];
+        let expect = Error::new(
+            Span::call_site(),
+            "FromBytesLit requires a struct with a single field of type `[u8; N]`",
+        )
+        .to_compile_error()
+        .to_string();
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = derive_from_bytes_lit(t);
+            assert_eq!(tokens.to_string(), expect, "table entry: {}", i);
+        }
+    }
+}
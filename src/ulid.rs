@@ -0,0 +1,123 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::LitStr;
+
+use crate::{emit, error::StrError};
+
+/// The Crockford base32 alphabet of ULIDs, which excludes I, L, O and U.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+pub fn ulid(input: TokenStream2) -> TokenStream2 {
+    let lit = match syn::parse2::<LitStr>(input) {
+        Ok(lit) => lit,
+        Err(e) => return e.to_compile_error(),
+    };
+    match parse(&lit.value()) {
+        Ok(bytes) => emit::array(&bytes),
+        Err(e) => e.into_error(&lit).to_compile_error(),
+    }
+}
+
+/// Parses a ULID of 26 Crockford base32 characters, in either case, into its
+/// 16 bytes in big endian order. The 26 characters hold 130 bits, so the first
+/// character must be at most 7 for the value to fit in 128 bits.
+pub fn parse(s: &str) -> Result<[u8; 16], StrError> {
+    if s.len() != 26 {
+        return Err(StrError::new(
+            s.len().min(26),
+            format!(
+                "invalid ULID length, expected 26 characters, found {}",
+                s.len()
+            ),
+        ));
+    }
+
+    let mut value = 0u128;
+    for (i, c) in s.bytes().enumerate() {
+        let v = ALPHABET
+            .iter()
+            .position(|&a| a == c.to_ascii_uppercase())
+            .ok_or_else(|| StrError::new(i, format!("invalid ULID character {:?}", c as char)))?;
+        if i == 0 && v > 7 {
+            return Err(StrError::new(
+                0,
+                "ULID overflows 128 bits, the first character must be 0 to 7",
+            ));
+        }
+        value = value << 5 | v as u128;
+    }
+    Ok(value.to_be_bytes())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse, ulid};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray};
+
+    #[test]
+    fn values() {
+        let table: &[(&str, [u8; 16])] = &[
+            (
+                "01ARZ3NDEKTSV4RRFFQ69G5FAV",
+                [
+                    0x01, 0x56, 0x3e, 0x3a, 0xb5, 0xd3, 0xd6, 0x76, 0x4c, 0x61, 0xef, 0xb9, 0x93,
+                    0x02, 0xbd, 0x5b,
+                ],
+            ),
+            (
+                "01arz3ndektsv4rrffq69g5fav",
+                [
+                    0x01, 0x56, 0x3e, 0x3a, 0xb5, 0xd3, 0xd6, 0x76, 0x4c, 0x61, 0xef, 0xb9, 0x93,
+                    0x02, 0xbd, 0x5b,
+                ],
+            ),
+            ("00000000000000000000000000", [0x00; 16]),
+            ("7ZZZZZZZZZZZZZZZZZZZZZZZZZ", [0xff; 16]),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse(t.0), Ok(t.1), "table entry: {}", i);
+        }
+
+        let tokens = ulid(quote!("01ARZ3NDEKTSV4RRFFQ69G5FAV"));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            1u8, 86u8, 62u8, 58u8, 181u8, 211u8, 214u8, 118u8, 76u8, 97u8, 239u8, 185u8, 147u8,
+            2u8, 189u8, 91u8
+        ]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(&str, StrError)] = &[
+            (
+                "01ARZ3NDEKTSV4RRFFQ69G5FA",
+                StrError::new(25, "invalid ULID length, expected 26 characters, found 25"),
+            ),
+            (
+                "01ARZ3NDEKTSV4RRFFQ69G5FAVX",
+                StrError::new(26, "invalid ULID length, expected 26 characters, found 27"),
+            ),
+            (
+                "01ARZ3NDEKTSV4RRFFQ69G5FAU",
+                StrError::new(25, "invalid ULID character 'U'"),
+            ),
+            (
+                "0IARZ3NDEKTSV4RRFFQ69G5FAV",
+                StrError::new(1, "invalid ULID character 'I'"),
+            ),
+            (
+                "8ZZZZZZZZZZZZZZZZZZZZZZZZZ",
+                StrError::new(
+                    0,
+                    "ULID overflows 128 bits, the first character must be 0 to 7",
+                ),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse(t.0).as_ref(), Err(&t.1), "table entry: {}", i);
+        }
+    }
+}
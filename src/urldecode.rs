@@ -0,0 +1,106 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitStr,
+};
+
+use crate::{args::Args, emit, error::StrError, hex};
+
+struct Input {
+    lit: LitStr,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn urldecode(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let plus = input.args.flag("plus")?;
+    input.args.finish()?;
+    let bytes = decode(&input.lit.value(), plus).map_err(|e| e.into_error(&input.lit))?;
+    Ok(emit::array(&bytes))
+}
+
+/// Decodes a percent encoded string, where `%XX` is the byte of the two hex
+/// digits and any other character is its UTF-8 bytes. If plus is set, `+` is
+/// a space, as in form encoded query strings.
+pub fn decode(s: &str, plus: bool) -> Result<Vec<u8>, StrError> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes().iter().enumerate();
+    while let Some((i, &c)) = rest.next() {
+        match c {
+            b'%' => {
+                let hi = rest.next().and_then(|(_, &c)| hex::value(c));
+                let lo = rest.next().and_then(|(_, &c)| hex::value(c));
+                match (hi, lo) {
+                    (Some(hi), Some(lo)) => bytes.push(hi << 4 | lo),
+                    _ => {
+                        return Err(StrError::new(
+                            i,
+                            "invalid percent escape, expected two hex digits after `%`",
+                        ))
+                    }
+                }
+            }
+            b'+' if plus => bytes.push(b' '),
+            _ => bytes.push(c),
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode, urldecode};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray};
+
+    #[test]
+    fn values() {
+        let table: &[(&str, bool, &[u8])] = &[
+            ("", false, b""),
+            ("%DE%AD%BE%EF", false, &[0xde, 0xad, 0xbe, 0xef]),
+            ("%de%ad", false, &[0xde, 0xad]),
+            ("a+b%20c", false, b"a+b c"),
+            ("a+b%20c", true, b"a b c"),
+            ("%2B", true, b"+"),
+            ("caf\u{e9}", false, b"caf\xc3\xa9"),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(decode(t.0, t.1), Ok(t.2.to_vec()), "table entry: {}", i);
+        }
+
+        let tokens = urldecode(quote!("a+%FF", plus));
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([97u8, 32u8, 255u8]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn errors() {
+        let expect = "invalid percent escape, expected two hex digits after `%`";
+        let table: &[(&str, StrError)] = &[
+            ("%DE%A", StrError::new(3, expect)),
+            ("%DE%", StrError::new(3, expect)),
+            ("a%G0", StrError::new(1, expect)),
+            ("a%0G", StrError::new(1, expect)),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(decode(t.0, false).as_ref(), Err(&t.1), "table entry: {}", i);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::LitStr;
+
+use crate::{emit, error::StrError, hex};
+
+pub fn uuid(input: TokenStream2) -> TokenStream2 {
+    let lit = match syn::parse2::<LitStr>(input) {
+        Ok(lit) => lit,
+        Err(e) => return e.to_compile_error(),
+    };
+    match parse(&lit.value()) {
+        Ok(bytes) => emit::array(&bytes),
+        Err(e) => e.into_error(&lit).to_compile_error(),
+    }
+}
+
+pub fn guid(input: TokenStream2) -> TokenStream2 {
+    let lit = match syn::parse2::<LitStr>(input) {
+        Ok(lit) => lit,
+        Err(e) => return e.to_compile_error(),
+    };
+    match parse(&lit.value()) {
+        Ok(bytes) => emit::array(&to_guid(bytes)),
+        Err(e) => e.into_error(&lit).to_compile_error(),
+    }
+}
+
+/// Converts the big endian bytes of a UUID into the Microsoft GUID layout,
+/// where the first three fields are little endian.
+fn to_guid(mut bytes: [u8; 16]) -> [u8; 16] {
+    bytes[0..4].reverse();
+    bytes[4..6].reverse();
+    bytes[6..8].reverse();
+    bytes
+}
+
+/// Parses a UUID in hyphenated, simple, or braced form, into its 16 bytes in
+/// big endian order.
+pub fn parse(s: &str) -> Result<[u8; 16], StrError> {
+    // Strip braces, keeping the offset of the remaining characters for errors.
+    let (offset, inner) = match s.strip_prefix('{') {
+        Some(rest) => match rest.strip_suffix('}') {
+            Some(inner) => (1, inner),
+            None => return Err(StrError::new(s.len(), "missing closing brace")),
+        },
+        None => (0, s),
+    };
+
+    let hyphenated = match inner.len() {
+        36 => true,
+        32 => false,
+        len => {
+            return Err(StrError::new(
+                offset + len.min(36),
+                format!(
+                    "invalid UUID length, expected 32 hex digits or 36 characters with hyphens, found {}",
+                    len
+                ),
+            ))
+        }
+    };
+
+    let mut bytes = [0u8; 16];
+    let mut digits = 0;
+    for (i, c) in inner.bytes().enumerate() {
+        if hyphenated && matches!(i, 8 | 13 | 18 | 23) {
+            if c != b'-' {
+                return Err(StrError::new(
+                    offset + i,
+                    format!("expected '-', found {:?}", c as char),
+                ));
+            }
+            continue;
+        }
+        let v = hex::value(c).ok_or_else(|| {
+            StrError::new(offset + i, format!("invalid hex character {:?}", c as char))
+        })?;
+        bytes[digits / 2] |= v << (4 * (1 - digits % 2));
+        digits += 1;
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{guid, parse, uuid};
+    use crate::error::StrError;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    const BYTES: [u8; 16] = [
+        0x55, 0x0e, 0x84, 0x00, 0xe2, 0x9b, 0x41, 0xd4, 0xa7, 0x16, 0x44, 0x66, 0x55, 0x44, 0x00,
+        0x00,
+    ];
+
+    #[test]
+    fn forms() {
+        let table: &[&str] = &[
+            "550e8400-e29b-41d4-a716-446655440000",
+            "550E8400-E29B-41D4-A716-446655440000",
+            "550e8400e29b41d4a716446655440000",
+            "{550e8400-e29b-41d4-a716-446655440000}",
+            "{550e8400e29b41d4a716446655440000}",
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse(t), Ok(BYTES), "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let table: &[(&str, StrError)] = &[
+            (
+                "550e8400-e29b-41d4-a716-44665544000",
+                StrError::new(
+                    35,
+                    "invalid UUID length, expected 32 hex digits or 36 characters with hyphens, found 35",
+                ),
+            ),
+            (
+                "550e8400-e29b-41d4-a716_446655440000",
+                StrError::new(23, "expected '-', found '_'"),
+            ),
+            (
+                "{550e8400-e29b-41d4-a716-44665544000g}",
+                StrError::new(36, "invalid hex character 'g'"),
+            ),
+            (
+                "{550e8400-e29b-41d4-a716-446655440000",
+                StrError::new(37, "missing closing brace"),
+            ),
+        ];
+        for (i, t) in table.iter().enumerate() {
+            assert_eq!(parse(t.0).as_ref(), Err(&t.1), "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn macro_output() {
+        let tokens = uuid(quote! {"550e8400-e29b-41d4-a716-446655440000"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            85u8, 14u8, 132u8, 0u8, 226u8, 155u8, 65u8, 212u8, 167u8, 22u8, 68u8, 102u8, 85u8,
+            68u8, 0u8, 0u8
+        ]);
+        assert_eq!(parsed, expect);
+
+        let tokens = uuid(quote! {"550e8400-e29b-41d4-a716-4466554400z0"});
+        let expect = Error::new(Span::call_site(), "invalid hex character 'z' at index 34")
+            .to_compile_error()
+            .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+
+    #[test]
+    fn guid_layout() {
+        let tokens = guid(quote! {"{00112233-4455-6677-8899-aabbccddeeff}"});
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect: ExprArray = parse_quote!([
+            51u8, 34u8, 17u8, 0u8, 85u8, 68u8, 119u8, 102u8, 136u8, 153u8, 170u8, 187u8, 204u8,
+            221u8, 238u8, 255u8
+        ]);
+        assert_eq!(parsed, expect);
+    }
+}
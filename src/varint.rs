@@ -0,0 +1,188 @@
+use std::str::FromStr;
+
+use num_bigint::{BigInt, BigUint, Sign};
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Error, LitInt};
+
+use crate::emit;
+
+pub fn varint(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<LitInt>(input).and_then(|lit| unsigned_int(&lit)) {
+        Ok(int) => emit::array(&encode_unsigned(&int)),
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn sleb128(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<LitInt>(input) {
+        Ok(lit) => emit::array(&encode_signed(&signed_int(&lit))),
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+pub fn zigzag(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<LitInt>(input) {
+        Ok(lit) => emit::array(&encode_unsigned(&zigzag_encode(&signed_int(&lit)))),
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Returns the value of an integer literal, erroring if it is negative. Unlike
+/// the bytes macro leading zeros carry no meaning in a varint and are ignored.
+fn unsigned_int(lit: &LitInt) -> Result<BigUint, Error> {
+    BigUint::from_str(lit.base10_digits()).map_err(|_| {
+        Error::new(
+            lit.span(),
+            "negative values unsupported, use sleb128! or zigzag! for signed values",
+        )
+    })
+}
+
+/// Returns the value of an integer literal that may be negative.
+fn signed_int(lit: &LitInt) -> BigInt {
+    // The base10 digits of a literal validated by syn always parse, with a
+    // leading minus sign for negative values.
+    BigInt::from_str(lit.base10_digits()).expect("valid integer")
+}
+
+/// Encodes an integer as unsigned LEB128, seven bits per byte, least
+/// significant group first, with the high bit set on all but the last byte.
+pub fn encode_unsigned(int: &BigUint) -> Vec<u8> {
+    let groups = int.to_radix_le(128);
+    let last = groups.len() - 1;
+    groups
+        .iter()
+        .enumerate()
+        .map(|(i, g)| if i == last { *g } else { g | 0x80 })
+        .collect()
+}
+
+/// Maps a signed integer to an unsigned integer by interleaving the positive
+/// and negative values, so that values of small magnitude stay small: 0, -1,
+/// 1, -2, 2 map to 0, 1, 2, 3, 4.
+fn zigzag_encode(int: &BigInt) -> BigUint {
+    let mapped = if int.sign() == Sign::Minus {
+        -(int * 2u32) - 1u32
+    } else {
+        int * 2u32
+    };
+    mapped.to_biguint().expect("non-negative")
+}
+
+/// Encodes an integer as signed LEB128, seven bits per byte of the two's
+/// complement value, ending when the remaining bits are all sign bits.
+pub fn encode_signed(int: &BigInt) -> Vec<u8> {
+    let radix = BigInt::from(128);
+    let mut value = int.clone();
+    let mut bytes = Vec::new();
+    loop {
+        // The low seven bits, taken as the floored remainder so negative
+        // values yield their two's complement bits.
+        let low = ((&value % &radix) + &radix) % &radix;
+        value = (value - &low) / &radix;
+        let byte = u8::try_from(&low).expect("seven bits");
+        let sign_bit = byte & 0x40 != 0;
+        let done =
+            (value == BigInt::from(0) && !sign_bit) || (value == BigInt::from(-1) && sign_bit);
+        if done {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{sleb128, varint, zigzag};
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn unsigned() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(0), parse_quote!([0u8])),
+            (quote!(1), parse_quote!([1u8])),
+            (quote!(127), parse_quote!([127u8])),
+            (quote!(128), parse_quote!([128u8, 1u8])),
+            (quote!(300), parse_quote!([172u8, 2u8])),
+            (quote!(0x00), parse_quote!([0u8])),
+            (quote!(007), parse_quote!([7u8])),
+            (quote!(624485), parse_quote!([229u8, 142u8, 38u8])),
+            (
+                quote!(0xffffffffffffffff),
+                parse_quote!([255u8, 255u8, 255u8, 255u8, 255u8, 255u8, 255u8, 255u8, 255u8, 1u8]),
+            ),
+            (
+                quote!(0x10000000000000000),
+                parse_quote!([128u8, 128u8, 128u8, 128u8, 128u8, 128u8, 128u8, 128u8, 128u8, 2u8]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = varint(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn unsigned_negative() {
+        let tokens = varint(quote! {-1});
+        let expect = Error::new(
+            Span::call_site(),
+            "negative values unsupported, use sleb128! or zigzag! for signed values",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+
+    #[test]
+    fn signed() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(0), parse_quote!([0u8])),
+            (quote!(2), parse_quote!([2u8])),
+            (quote!(-1), parse_quote!([127u8])),
+            (quote!(63), parse_quote!([63u8])),
+            (quote!(64), parse_quote!([192u8, 0u8])),
+            (quote!(-64), parse_quote!([64u8])),
+            (quote!(-65), parse_quote!([191u8, 127u8])),
+            (quote!(127), parse_quote!([255u8, 0u8])),
+            (quote!(-128), parse_quote!([128u8, 127u8])),
+            (quote!(-123456), parse_quote!([192u8, 187u8, 120u8])),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = sleb128(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn zigzag_varint() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(0), parse_quote!([0u8])),
+            (quote!(-1), parse_quote!([1u8])),
+            (quote!(1), parse_quote!([2u8])),
+            (quote!(-3), parse_quote!([5u8])),
+            (quote!(63), parse_quote!([126u8])),
+            (quote!(-64), parse_quote!([127u8])),
+            (quote!(64), parse_quote!([128u8, 1u8])),
+            (
+                quote!(-2147483648),
+                parse_quote!([255u8, 255u8, 255u8, 255u8, 15u8]),
+            ),
+            (
+                quote!(-0x8000000000000000),
+                parse_quote!([255u8, 255u8, 255u8, 255u8, 255u8, 255u8, 255u8, 255u8, 255u8, 1u8]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = zigzag(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+}
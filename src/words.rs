@@ -0,0 +1,137 @@
+use proc_macro2::{Literal, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, LitInt,
+};
+
+use crate::{args::Args, bytes::int_bytes};
+
+struct Input {
+    lit: LitInt,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+/// Converts the literal into an array of words, where each word is `size`
+/// bytes.
+pub fn words(input: TokenStream2, size: usize) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(|input| expand(input, size)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input, size: usize) -> Result<TokenStream2, Error> {
+    let little = input
+        .args
+        .ident("endian", &["big", "little"])?
+        .map_or(false, |e| e == "little");
+    let pad = input.args.flag("pad")?;
+    input.args.finish()?;
+
+    let mut bytes = int_bytes(&input.lit)?;
+
+    // Zero pad the front of the bytes, as leading zeros of the integer, if
+    // padding is enabled.
+    let rem = bytes.len() % size;
+    if rem != 0 {
+        if !pad {
+            return Err(Error::new(
+                input.lit.span(),
+                format!(
+                    "literal is {} byte(s) which is not a multiple of the word size of {} bytes, use the `pad` option to zero pad",
+                    bytes.len(),
+                    size
+                ),
+            ));
+        }
+        bytes.splice(0..0, vec![0; size - rem]);
+    }
+
+    let words = bytes.chunks(size).map(|chunk| {
+        let word = if little {
+            chunk.iter().rev().fold(0u64, |w, b| w << 8 | u64::from(*b))
+        } else {
+            chunk.iter().fold(0u64, |w, b| w << 8 | u64::from(*b))
+        };
+        match size {
+            2 => Literal::u16_suffixed(word as u16),
+            4 => Literal::u32_suffixed(word as u32),
+            8 => Literal::u64_suffixed(word),
+            _ => unreachable!(),
+        }
+    });
+    Ok(quote! { [#(#words),*] })
+}
+
+#[cfg(test)]
+mod test {
+    use super::words;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{parse_quote, Error, ExprArray};
+
+    #[test]
+    fn big_endian() {
+        let tokens = words(quote! {0x0001abcd}, 2);
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect = parse_quote!([1u16, 43981u16]);
+        assert_eq!(parsed, expect);
+
+        let tokens = words(quote! {0x0001abcd, endian = big}, 4);
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect = parse_quote!([109517u32]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn little_endian() {
+        let tokens = words(quote! {0x0001abcd; endian = little}, 2);
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect = parse_quote!([256u16, 52651u16]);
+        assert_eq!(parsed, expect);
+
+        let tokens = words(quote! {0x0100000000000000, endian = little}, 8);
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect = parse_quote!([1u64]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn pad() {
+        let tokens = words(quote! {0x123456, pad}, 4);
+        let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+        let expect = parse_quote!([1193046u32]);
+        assert_eq!(parsed, expect);
+
+        let tokens = words(quote! {0x123456}, 4);
+        let expect = Error::new(
+            Span::call_site(),
+            "literal is 3 byte(s) which is not a multiple of the word size of 4 bytes, use the `pad` option to zero pad",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+
+    #[test]
+    fn bad_endian() {
+        let tokens = words(quote! {0x1234, endian = middle}, 2);
+        let expect = Error::new(
+            Span::call_site(),
+            "option `endian` requires one of: big, little",
+        )
+        .to_compile_error()
+        .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}
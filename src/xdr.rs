@@ -0,0 +1,175 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Error, Lit, Path,
+};
+
+use crate::{args::Args, emit, hash::message};
+
+struct Input {
+    lit: Lit,
+    args: Args,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lit = input.parse()?;
+        let args = Args::parse(input)?;
+        Ok(Self { lit, args })
+    }
+}
+
+pub fn xdr_opaque(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<Input>(input).and_then(expand) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn expand(mut input: Input) -> Result<TokenStream2, Error> {
+    let fixed = input.args.flag("fixed")?;
+    input.args.finish()?;
+
+    let data = message(&input.lit)?;
+
+    // Variable length opaque data is prefixed with its length as a 4 byte big
+    // endian unsigned integer, while fixed length opaque data is not.
+    let mut bytes = Vec::with_capacity(data.len() + 7);
+    if !fixed {
+        let len = u32::try_from(data.len()).map_err(|_| {
+            Error::new(
+                input.lit.span(),
+                "literal is too long for XDR opaque data, the maximum length is 2^32 - 1 bytes",
+            )
+        })?;
+        bytes.extend(len.to_be_bytes());
+    }
+    bytes.extend(&data);
+
+    // The data is zero padded to a multiple of 4 bytes.
+    let padding = (4 - data.len() % 4) % 4;
+    bytes.extend(vec![0; padding]);
+
+    Ok(emit::array(&bytes))
+}
+
+/// The types of the stellar-xdr crate that wrap an array of bytes, and the
+/// length of the array.
+const TYPES: &[(&str, usize)] = &[("Hash", 32), ("Uint256", 32)];
+
+/// An output type of the bytes macro, `as Hash`, that is a type of the
+/// stellar-xdr crate wrapping an array of bytes. The type is referred to by
+/// the path given, and identified by the last segment of the path.
+pub struct Conversion {
+    path: Path,
+}
+
+impl Parse for Conversion {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        Ok(Self { path })
+    }
+}
+
+impl Conversion {
+    /// Returns the span of the type.
+    pub fn span(&self) -> Span {
+        self.path.span()
+    }
+
+    /// Expands to a call of the constructor of the type with the array of the
+    /// bytes, erroring if the bytes are not the length of the array the type
+    /// wraps.
+    pub fn expand(&self, bytes: &[u8]) -> Result<TokenStream2, Error> {
+        if !cfg!(feature = "xdr") {
+            return Err(Error::new_spanned(
+                &self.path,
+                "output `as` a type requires the `xdr` feature of bytes-lit",
+            ));
+        }
+        let name = match self.path.segments.last() {
+            Some(segment) if segment.arguments.is_empty() => segment.ident.to_string(),
+            _ => String::new(),
+        };
+        let len = match TYPES.iter().find(|(n, _)| *n == name) {
+            Some((_, len)) => *len,
+            None => {
+                let names: Vec<&str> = TYPES.iter().map(|(n, _)| *n).collect();
+                return Err(Error::new_spanned(
+                    &self.path,
+                    format!(
+                        "unsupported output type, expected one of: {}",
+                        names.join(", ")
+                    ),
+                ));
+            }
+        };
+        if bytes.len() != len {
+            let hint = if bytes.len() < len {
+                format!(", use `size = {}` to zero pad", len)
+            } else {
+                String::new()
+            };
+            return Err(Error::new_spanned(
+                &self.path,
+                format!(
+                    "`{}` wraps {} bytes, found {} byte(s){}",
+                    name,
+                    len,
+                    bytes.len(),
+                    hint
+                ),
+            ));
+        }
+        let path = &self.path;
+        let array = emit::array(bytes);
+        Ok(quote!(#path(#array)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::xdr_opaque;
+    use pretty_assertions::assert_eq;
+    use quote::quote;
+    use syn::{parse_quote, ExprArray, ExprRepeat};
+
+    #[test]
+    fn opaque() {
+        let table: &[(_, ExprArray)] = &[
+            (quote!(""), parse_quote!([0u8, 0u8, 0u8, 0u8])),
+            (
+                quote!("abc"),
+                parse_quote!([0u8, 0u8, 0u8, 3u8, 97u8, 98u8, 99u8, 0u8]),
+            ),
+            (
+                quote!(b"abcd"),
+                parse_quote!([0u8, 0u8, 0u8, 4u8, 97u8, 98u8, 99u8, 100u8]),
+            ),
+            (
+                quote!(0x0102030405),
+                parse_quote!([0u8, 0u8, 0u8, 5u8, 1u8, 2u8, 3u8, 4u8, 5u8, 0u8, 0u8, 0u8]),
+            ),
+            (quote!(0x01, fixed), parse_quote!([1u8, 0u8, 0u8, 0u8])),
+            (
+                quote!("abcd", fixed),
+                parse_quote!([97u8, 98u8, 99u8, 100u8]),
+            ),
+        ];
+        for (i, t) in table.iter().cloned().enumerate() {
+            let tokens = xdr_opaque(t.0);
+            let parsed = syn::parse2::<ExprArray>(tokens).unwrap();
+            assert_eq!(parsed, t.1, "table entry: {}", i);
+        }
+    }
+
+    #[test]
+    fn fixed_empty() {
+        let tokens = xdr_opaque(quote! {"", fixed});
+        let parsed = syn::parse2::<ExprRepeat>(tokens).unwrap();
+        let expect: ExprRepeat = parse_quote!([0u8; 0]);
+        assert_eq!(parsed, expect);
+    }
+}
@@ -0,0 +1,68 @@
+use proc_macro2::{Literal, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{Error, LitInt};
+
+pub fn zeros(input: TokenStream2) -> TokenStream2 {
+    match syn::parse2::<LitInt>(input).and_then(|lit| expand(&lit)) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Expands to a repeat expression of zero bytes, `[0u8; N]`, so that no bytes
+/// are built at expansion time however long the array is.
+fn expand(lit: &LitInt) -> Result<TokenStream2, Error> {
+    if lit.base10_digits().starts_with('-') {
+        return Err(Error::new(
+            lit.span(),
+            "length must be a non-negative integer",
+        ));
+    }
+    let len = Literal::usize_unsuffixed(lit.base10_parse()?);
+    Ok(quote! { [0u8; #len] })
+}
+
+#[cfg(test)]
+mod test {
+    use super::zeros;
+    use pretty_assertions::assert_eq;
+    use proc_macro2::Span;
+    use quote::quote;
+    use syn::{Error, ExprRepeat};
+
+    #[test]
+    fn zero_filled() {
+        let tokens = zeros(quote! {4});
+        let parsed = syn::parse2::<ExprRepeat>(tokens).unwrap();
+        let expect = syn::parse_quote!([0u8; 4]);
+        assert_eq!(parsed, expect);
+
+        let tokens = zeros(quote! {0x2});
+        let parsed = syn::parse2::<ExprRepeat>(tokens).unwrap();
+        let expect = syn::parse_quote!([0u8; 2]);
+        assert_eq!(parsed, expect);
+
+        // A long array is not built at expansion time.
+        let tokens = zeros(quote! {4000000000});
+        let parsed = syn::parse2::<ExprRepeat>(tokens).unwrap();
+        let expect = syn::parse_quote!([0u8; 4000000000]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn empty() {
+        let tokens = zeros(quote! {0});
+        let parsed = syn::parse2::<ExprRepeat>(tokens).unwrap();
+        let expect = syn::parse_quote!([0u8; 0]);
+        assert_eq!(parsed, expect);
+    }
+
+    #[test]
+    fn neg() {
+        let tokens = zeros(quote! {-1});
+        let expect = Error::new(Span::call_site(), "length must be a non-negative integer")
+            .to_compile_error()
+            .to_string();
+        assert_eq!(tokens.to_string(), expect);
+    }
+}